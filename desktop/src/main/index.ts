@@ -63,6 +63,19 @@ function initializeState(): void {
   })
 }
 
+// Forward PTY output to renderer (kept out of state updates)
+function initializeTerminal(): void {
+  core.terminalSetOutputListener((err: Error | null, sessionId: string, data: Buffer) => {
+    if (err) {
+      console.error('Terminal output error:', err)
+      return
+    }
+    BrowserWindow.getAllWindows().forEach((win) => {
+      win.webContents.send('terminal:output', sessionId, new Uint8Array(data))
+    })
+  })
+}
+
 // IPC Handlers for state management
 function setupStateIPC(): void {
   // Handle state dispatch from renderer
@@ -102,6 +115,25 @@ function setupExplorerIPC(): void {
   })
 }
 
+// ============================================================================
+// Terminal Handlers
+// ============================================================================
+
+function setupTerminalIPC(): void {
+  // Fetch scrollback so a reloaded renderer can restore terminal history
+  ipcMain.handle(
+    'terminal:getScrollback',
+    async (_event, sessionId: string, start: number | null, count: number) => {
+      try {
+        return core.terminalGetScrollback(sessionId, start, count)
+      } catch (error) {
+        console.error('Terminal scrollback error:', error)
+        throw error
+      }
+    }
+  )
+}
+
 // ============================================================================
 // Dialog Handlers
 // ============================================================================
@@ -211,8 +243,10 @@ app.whenReady().then(async () => {
 
   // Initialize state management (State-first architecture)
   initializeState()
+  initializeTerminal()
   setupStateIPC()
   setupExplorerIPC()
+  setupTerminalIPC()
   setupDialogIPC()
   setupScreenshotIPC()
 
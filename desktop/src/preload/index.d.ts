@@ -42,6 +42,33 @@ interface ScreenshotApi {
   capture(): Promise<{ success: boolean; filePath?: string; error?: string }>
 }
 
+// Terminal API (PTY output stream)
+interface TerminalApi {
+  /**
+   * Subscribe to PTY output for all terminal sessions.
+   * @param callback - Called with session ID and raw output bytes
+   * @returns Unsubscribe function
+   */
+  onOutput(callback: (sessionId: string, data: Uint8Array) => void): () => void
+
+  /**
+   * Fetch a page of raw scrollback lines for a session.
+   * @param sessionId - Terminal session ID
+   * @param start - First line number, or null for the last `count` lines
+   * @param count - Maximum number of lines
+   */
+  getScrollback(sessionId: string, start: number | null, count: number): Promise<TerminalScrollbackPage>
+}
+
+interface TerminalScrollbackPage {
+  /** Line number of the first returned line */
+  start: number
+  lines: string[]
+  /** Oldest line number still available */
+  firstLine: number
+  totalLines: number
+}
+
 // State-first API
 // This is the new architecture where Rust owns all state
 interface StateApi {
@@ -71,5 +98,6 @@ declare global {
     stateApi: StateApi
     dialogApi: DialogApi
     screenshotApi: ScreenshotApi
+    terminalApi: TerminalApi
   }
 }
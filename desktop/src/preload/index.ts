@@ -58,6 +58,45 @@ const stateApi = {
   },
 }
 
+interface TerminalScrollbackPage {
+  start: number
+  lines: string[]
+  firstLine: number
+  totalLines: number
+}
+
+// Terminal API (PTY output stream)
+const terminalApi = {
+  /**
+   * Subscribe to PTY output for all terminal sessions.
+   * @param callback - Called with session ID and raw output bytes
+   * @returns Unsubscribe function
+   */
+  onOutput: (callback: (sessionId: string, data: Uint8Array) => void): (() => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, sessionId: string, data: Uint8Array): void => {
+      callback(sessionId, data)
+    }
+    ipcRenderer.on('terminal:output', handler)
+    return () => {
+      ipcRenderer.removeListener('terminal:output', handler)
+    }
+  },
+
+  /**
+   * Fetch a page of raw scrollback lines for a session.
+   * @param sessionId - Terminal session ID
+   * @param start - First line number, or null for the last `count` lines
+   * @param count - Maximum number of lines
+   */
+  getScrollback: (
+    sessionId: string,
+    start: number | null,
+    count: number
+  ): Promise<TerminalScrollbackPage> => {
+    return ipcRenderer.invoke('terminal:getScrollback', sessionId, start, count)
+  },
+}
+
 // Expose electron APIs to renderer
 if (process.contextIsolated) {
   try {
@@ -65,6 +104,7 @@ if (process.contextIsolated) {
     contextBridge.exposeInMainWorld('stateApi', stateApi)
     contextBridge.exposeInMainWorld('dialogApi', dialogApi)
     contextBridge.exposeInMainWorld('screenshotApi', screenshotApi)
+    contextBridge.exposeInMainWorld('terminalApi', terminalApi)
   } catch (error) {
     console.error(error)
   }
@@ -77,4 +117,6 @@ if (process.contextIsolated) {
   window.dialogApi = dialogApi
   // @ts-ignore (define in dts)
   window.screenshotApi = screenshotApi
+  // @ts-ignore (define in dts)
+  window.terminalApi = terminalApi
 }
@@ -70,6 +70,18 @@ interface StateApi {
   onStateUpdate(callback: (stateJson: string) => void): () => void
 }
 
+interface TerminalScrollbackPage {
+  start: number
+  lines: string[]
+  firstLine: number
+  totalLines: number
+}
+
+interface TerminalApi {
+  onOutput(callback: (sessionId: string, data: Uint8Array) => void): () => void
+  getScrollback(sessionId: string, start: number | null, count: number): Promise<TerminalScrollbackPage>
+}
+
 interface ExplorerFileEntry {
   name: string
   path: string
@@ -94,6 +106,7 @@ declare global {
     explorerApi: ExplorerApi
     dialogApi: DialogApi
     screenshotApi: ScreenshotApi
+    terminalApi: TerminalApi
   }
 }
 
@@ -0,0 +1,156 @@
+import { useState } from 'react'
+import {
+  Link as LinkIcon,
+  ContentCopy as CopyIcon,
+  Check as CheckIcon,
+  Save as SaveIcon
+} from '@mui/icons-material'
+import {
+  Button,
+  Dialog,
+  DialogActions,
+  DialogContent,
+  DialogTitle,
+  IconButton,
+  InputAdornment,
+  LinearProgress,
+  MenuItem,
+  Stack,
+  TextField,
+  Typography
+} from '@mui/material'
+import { useActiveWorktree, useDockersState } from '@/hooks/useAppState'
+
+interface ConnectionInfoDialogProps {
+  serviceId: string
+  serviceName: string
+}
+
+/**
+ * Connection URL, host, port and credentials of a service, with export of
+ * the URL to a worktree's .env.
+ */
+export function ConnectionInfoDialog({ serviceId, serviceName }: ConnectionInfoDialogProps) {
+  const { dockers, dispatch } = useDockersState()
+  const { worktree, worktrees } = useActiveWorktree()
+  const [open, setOpen] = useState(false)
+  const [worktreePath, setWorktreePath] = useState('')
+  const [copiedField, setCopiedField] = useState<string | null>(null)
+
+  const info = dockers?.connection_info?.service_id === serviceId ? dockers.connection_info : undefined
+
+  const handleOpen = () => {
+    setOpen(true)
+    setWorktreePath(worktree?.path ?? '')
+    dispatch({ type: 'LoadConnectionInfo', payload: { service_id: serviceId } })
+  }
+
+  const handleCopy = async (field: string, value: string) => {
+    await navigator.clipboard.writeText(value)
+    setCopiedField(field)
+    setTimeout(() => setCopiedField(null), 2000)
+  }
+
+  const handleExport = () => {
+    if (!worktreePath) return
+    dispatch({
+      type: 'ExportConnectionInfo',
+      payload: { service_id: serviceId, worktree_path: worktreePath }
+    })
+  }
+
+  const fields: [string, string | null | undefined][] = [
+    ['URL', info?.url],
+    ['Host', info?.host],
+    ['Port', info ? String(info.port) : undefined],
+    ['User', info?.user],
+    ['Password', info?.password],
+  ]
+
+  return (
+    <>
+      <Button
+        variant="text"
+        size="small"
+        onClick={(e) => {
+          e.stopPropagation()
+          handleOpen()
+        }}
+        startIcon={<LinkIcon />}
+      >
+        Connect
+      </Button>
+
+      <Dialog
+        open={open}
+        onClose={() => setOpen(false)}
+        onClick={(e) => e.stopPropagation()}
+        maxWidth="sm"
+        fullWidth
+      >
+        <DialogTitle>Connect to {serviceName}</DialogTitle>
+        <DialogContent>
+          {!info && <LinearProgress sx={{ mt: 1 }} />}
+          {info && (
+            <Stack spacing={2} sx={{ mt: 1 }}>
+              {fields
+                .filter(([, value]) => value)
+                .map(([label, value]) => (
+                  <TextField
+                    key={label}
+                    label={label}
+                    value={value}
+                    size="small"
+                    fullWidth
+                    InputProps={{
+                      readOnly: true,
+                      sx: { fontFamily: 'monospace', fontSize: '0.85rem' },
+                      endAdornment: (
+                        <InputAdornment position="end">
+                          <IconButton size="small" onClick={() => handleCopy(label, value!)}>
+                            {copiedField === label
+                              ? <CheckIcon fontSize="small" color="success" />
+                              : <CopyIcon fontSize="small" />}
+                          </IconButton>
+                        </InputAdornment>
+                      ),
+                    }}
+                  />
+                ))}
+
+              <Typography variant="body2" color="text.secondary">
+                Write <code>{info.env_var}</code> to a worktree's .env (other lines are kept)
+              </Typography>
+              <Stack direction="row" spacing={1} alignItems="center">
+                <TextField
+                  select
+                  size="small"
+                  label="Worktree"
+                  value={worktreePath}
+                  onChange={(e) => setWorktreePath(e.target.value)}
+                  sx={{ flex: 1 }}
+                >
+                  {worktrees.map((wt) => (
+                    <MenuItem key={wt.path} value={wt.path}>{wt.branch}</MenuItem>
+                  ))}
+                </TextField>
+                <Button
+                  variant="contained"
+                  size="small"
+                  startIcon={<SaveIcon />}
+                  disabled={!worktreePath}
+                  onClick={handleExport}
+                >
+                  Write to .env
+                </Button>
+              </Stack>
+            </Stack>
+          )}
+        </DialogContent>
+        <DialogActions>
+          <Button onClick={() => setOpen(false)}>Close</Button>
+        </DialogActions>
+      </Dialog>
+    </>
+  )
+}
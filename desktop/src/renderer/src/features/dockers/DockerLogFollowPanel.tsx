@@ -0,0 +1,226 @@
+import { useEffect, useRef, useState } from 'react'
+import {
+  Code,
+  PlayArrow as FollowIcon,
+  Pause as PauseIcon,
+  Search as SearchIcon
+} from '@mui/icons-material'
+import {
+  Box,
+  Chip,
+  IconButton,
+  InputAdornment,
+  MenuItem,
+  Paper,
+  Stack,
+  TextField,
+  Typography
+} from '@mui/material'
+import { useDockersState } from '@/hooks/useAppState'
+import type { DockerLogFollow, DockerServiceInfo } from '@/types/state'
+
+/** "Since" choices, in seconds before now (0 = recent tail) */
+const SINCE_OPTIONS = [
+  { value: 0, label: 'Recent' },
+  { value: 5 * 60, label: 'Last 5 min' },
+  { value: 60 * 60, label: 'Last hour' },
+  { value: 24 * 60 * 60, label: 'Last 24 hours' },
+]
+
+interface FollowOptions {
+  stdout: boolean
+  stderr: boolean
+  timestamps: boolean
+  sinceSeconds: number
+  filter: string
+}
+
+const DEFAULT_OPTIONS: FollowOptions = {
+  stdout: true,
+  stderr: true,
+  timestamps: false,
+  sinceSeconds: 0,
+  filter: '',
+}
+
+interface DockerLogFollowPanelProps {
+  service?: DockerServiceInfo
+}
+
+/**
+ * Live log view for the selected service, streamed by a follow subscription.
+ */
+export function DockerLogFollowPanel({ service }: DockerLogFollowPanelProps) {
+  const { dockers, dispatch } = useDockersState()
+  const [options, setOptions] = useState<FollowOptions>(DEFAULT_OPTIONS)
+  const [filterInput, setFilterInput] = useState('')
+  const scrollRef = useRef<HTMLDivElement>(null)
+
+  const follow: DockerLogFollow | undefined =
+    dockers?.log_follow?.service_id === service?.id ? dockers?.log_follow : undefined
+  const lines = follow ? dockers?.log_lines ?? [] : []
+
+  const startFollowing = (next: FollowOptions) => {
+    if (!service) return
+    setOptions(next)
+    dispatch({
+      type: 'FollowDockerLogs',
+      payload: {
+        service_id: service.id,
+        stdout: next.stdout,
+        stderr: next.stderr,
+        timestamps: next.timestamps,
+        since: next.sinceSeconds ? Math.floor(Date.now() / 1000) - next.sinceSeconds : undefined,
+        filter: next.filter || undefined,
+      }
+    })
+  }
+
+  // Follow a newly selected service with the current options
+  useEffect(() => {
+    if (service && dockers?.log_follow?.service_id !== service.id) {
+      startFollowing(options)
+    }
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [service?.id])
+
+  // Unsubscribe when the panel goes away
+  useEffect(() => () => {
+    dispatch({ type: 'StopFollowingDockerLogs' })
+  }, [dispatch])
+
+  // Keep the newest lines in view
+  useEffect(() => {
+    if (scrollRef.current) {
+      scrollRef.current.scrollTop = scrollRef.current.scrollHeight
+    }
+  }, [lines])
+
+  const update = (patch: Partial<FollowOptions>) => startFollowing({ ...options, ...patch })
+
+  return (
+    <Paper variant="outlined" sx={{ display: 'flex', flexDirection: 'column', height: '100%' }}>
+      {/* Header */}
+      <Stack
+        direction="row"
+        alignItems="center"
+        justifyContent="space-between"
+        sx={{ borderBottom: 1, borderColor: 'divider', px: 2, py: 1 }}
+      >
+        <Stack direction="row" alignItems="center" spacing={1}>
+          <Code fontSize="small" />
+          <Typography variant="subtitle2">{service ? `${service.name} Logs` : 'Logs'}</Typography>
+          {follow?.is_streaming && (
+            <Chip label="live" size="small" color="success" sx={{ height: 20, fontSize: '0.65rem' }} />
+          )}
+        </Stack>
+        {service && (
+          follow?.is_streaming ? (
+            <IconButton
+              size="small"
+              title="Stop following"
+              onClick={() => dispatch({ type: 'StopFollowingDockerLogs' })}
+            >
+              <PauseIcon fontSize="small" />
+            </IconButton>
+          ) : (
+            <IconButton size="small" title="Follow" onClick={() => startFollowing(options)}>
+              <FollowIcon fontSize="small" />
+            </IconButton>
+          )
+        )}
+      </Stack>
+
+      {/* Follow options */}
+      {service && (
+        <Stack
+          direction="row"
+          spacing={1}
+          alignItems="center"
+          sx={{ px: 2, py: 1, borderBottom: 1, borderColor: 'divider' }}
+        >
+          <Chip
+            label="stdout"
+            size="small"
+            variant={options.stdout ? 'filled' : 'outlined'}
+            onClick={() => update({ stdout: !options.stdout })}
+            disabled={options.stdout && !options.stderr}
+          />
+          <Chip
+            label="stderr"
+            size="small"
+            color="error"
+            variant={options.stderr ? 'filled' : 'outlined'}
+            onClick={() => update({ stderr: !options.stderr })}
+            disabled={options.stderr && !options.stdout}
+          />
+          <Chip
+            label="timestamps"
+            size="small"
+            variant={options.timestamps ? 'filled' : 'outlined'}
+            onClick={() => update({ timestamps: !options.timestamps })}
+          />
+          <TextField
+            select
+            size="small"
+            value={options.sinceSeconds}
+            onChange={(e) => update({ sinceSeconds: Number(e.target.value) })}
+            sx={{ minWidth: 130 }}
+          >
+            {SINCE_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>{option.label}</MenuItem>
+            ))}
+          </TextField>
+          <TextField
+            size="small"
+            placeholder="Filter"
+            value={filterInput}
+            onChange={(e) => setFilterInput(e.target.value)}
+            onBlur={() => filterInput !== options.filter && update({ filter: filterInput })}
+            onKeyDown={(e) => {
+              if (e.key === 'Enter' && filterInput !== options.filter) update({ filter: filterInput })
+            }}
+            sx={{ flex: 1 }}
+            InputProps={{
+              startAdornment: (
+                <InputAdornment position="start">
+                  <SearchIcon fontSize="small" />
+                </InputAdornment>
+              )
+            }}
+          />
+        </Stack>
+      )}
+
+      {/* Content */}
+      <Box ref={scrollRef} sx={{ flex: 1, overflow: 'auto', p: 2 }}>
+        {lines.length > 0 ? (
+          <Box component="pre" sx={{ m: 0, whiteSpace: 'pre-wrap', fontFamily: 'monospace', fontSize: '0.75rem' }}>
+            {lines.map((line, i) => (
+              <Box
+                key={i}
+                component="div"
+                sx={{ color: line.stream === 'stderr' ? 'error.main' : undefined }}
+              >
+                {line.timestamp && (
+                  <Box component="span" sx={{ color: 'text.secondary', mr: 1 }}>
+                    {line.timestamp}
+                  </Box>
+                )}
+                {line.text}
+              </Box>
+            ))}
+          </Box>
+        ) : (
+          <Typography variant="body2" color="text.secondary">
+            {!service
+              ? 'Click a service to view its logs'
+              : follow?.is_streaming
+                ? 'Waiting for output...'
+                : 'No output'}
+          </Typography>
+        )}
+      </Box>
+    </Paper>
+  )
+}
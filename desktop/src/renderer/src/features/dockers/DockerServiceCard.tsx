@@ -5,7 +5,9 @@ import {
   Refresh as RotateCwIcon,
   Description as FileTextIcon,
   ContentCopy as CopyIcon,
-  Check as CheckIcon
+  Check as CheckIcon,
+  Terminal as TerminalIcon,
+  SystemUpdateAlt as UpdateIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -18,12 +20,17 @@ import {
   Box,
   IconButton,
   Tooltip,
-  Chip
+  Chip,
+  LinearProgress
 } from '@mui/material'
 import type { DockerServiceInfo } from '@/types/state'
-import { statusColors, statusLabels } from '@/types/state'
+import { healthLabels, statusColors, statusLabels } from '@/types/state'
 import { AddDbDialog } from './AddDbDialog'
 import { AddVhostDialog } from './AddVhostDialog'
+import { ConnectionInfoDialog } from './ConnectionInfoDialog'
+import { ServiceStatsBar } from './ServiceStatsBar'
+import { SnapshotsDialog, SNAPSHOT_SERVICES } from './SnapshotsDialog'
+import { SqlConsoleDialog, SQL_SERVICES } from './SqlConsoleDialog'
 
 // Connection string templates with default credentials
 function getConnectionString(service: DockerServiceInfo): string {
@@ -55,6 +62,8 @@ interface DockerServiceCardProps {
   onToggle?: (id: string) => void
   onRestart?: (id: string) => void
   onViewLogs?: (id: string) => void
+  onOpenShell?: (id: string) => void
+  onUpdateImage?: (id: string) => void
 }
 
 export function DockerServiceCard({
@@ -64,12 +73,16 @@ export function DockerServiceCard({
   onToggle,
   onRestart,
   onViewLogs,
+  onOpenShell,
+  onUpdateImage,
 }: DockerServiceCardProps) {
   const [copied, setCopied] = useState(false)
   const isRunning = service.status === 'running'
   const isStarting = service.status === 'starting'
   const isRstnManaged = service.is_rstn_managed
-  const canControl = !isStarting && isRstnManaged
+  const pull = service.pull
+  // Compose services are controlled through `docker compose`
+  const canControl = !isStarting && (isRstnManaged || !!service.compose_service)
 
   const handleCopyConnectionString = async () => {
     const connectionString = getConnectionString(service)
@@ -78,6 +91,8 @@ export function DockerServiceCard({
     setTimeout(() => setCopied(false), 2000)
   }
 
+  const health = isRunning ? service.health : undefined
+
   // Map status labels to M3 colors
   const getStatusColor = (status: string) => {
     switch (status) {
@@ -115,9 +130,22 @@ export function DockerServiceCard({
                   width: 8,
                   height: 8,
                   borderRadius: '50%',
-                  bgcolor: isRunning ? 'success.main' : isStarting ? 'warning.main' : 'text.disabled'
+                  bgcolor: health === 'unhealthy'
+                    ? 'error.main'
+                    : isRunning && health !== 'starting'
+                      ? 'success.main'
+                      : isStarting || health === 'starting' ? 'warning.main' : 'text.disabled'
                 }}
               />
+              {health && (
+                <Chip
+                  label={healthLabels[health]}
+                  size="small"
+                  color={health === 'healthy' ? 'success' : health === 'unhealthy' ? 'error' : 'warning'}
+                  variant="outlined"
+                  sx={{ fontSize: '0.65rem', height: 20 }}
+                />
+              )}
               <Chip
                 label={statusLabels[service.status]}
                 size="small"
@@ -141,6 +169,16 @@ export function DockerServiceCard({
             Port: <Box component="span" sx={{ fontFamily: 'monospace', fontWeight: 600 }}>{service.port}</Box>
           </Typography>
         )}
+        {isRunning && service.stats && <ServiceStatsBar stats={service.stats} />}
+        {pull && (
+          <Box sx={{ mt: 1 }}>
+            <Typography variant="caption" sx={{ color: 'onSurfaceVariant.main' }}>
+              Pulling {pull.image}: {pull.status}
+              {pull.layers_total > 0 && ` (${pull.layers_done}/${pull.layers_total} layers)`}
+            </Typography>
+            <LinearProgress variant="determinate" value={pull.progress * 100} />
+          </Box>
+        )}
       </CardContent>
 
       <CardActions sx={{ px: 2, pb: 2, pt: 0, flexWrap: 'wrap', gap: 1 }}>
@@ -185,6 +223,38 @@ export function DockerServiceCard({
           Logs
         </Button>
 
+        <Button
+          variant="text"
+          size="small"
+          disabled={!isRunning}
+          onClick={(e) => {
+            e.stopPropagation()
+            onOpenShell?.(service.id)
+          }}
+          startIcon={<TerminalIcon />}
+        >
+          Shell
+        </Button>
+
+        {isRstnManaged && (
+          <Tooltip title="Pull the latest image (recreates the container if it changed)">
+            <span>
+              <Button
+                variant="text"
+                size="small"
+                disabled={isStarting || !!pull}
+                onClick={(e) => {
+                  e.stopPropagation()
+                  onUpdateImage?.(service.id)
+                }}
+                startIcon={<UpdateIcon />}
+              >
+                Update
+              </Button>
+            </span>
+          </Tooltip>
+        )}
+
         {/* Conditional Add DB button for databases */}
         {service.service_type === 'Database' && (
           <AddDbDialog
@@ -194,6 +264,24 @@ export function DockerServiceCard({
           />
         )}
 
+        {/* Snapshot and restore for managed data stores */}
+        {SNAPSHOT_SERVICES.includes(service.id) && (
+          <SnapshotsDialog
+            serviceId={service.id}
+            serviceName={service.name}
+            disabled={!isRunning}
+          />
+        )}
+
+        {/* Schema browser and queries for SQL databases */}
+        {SQL_SERVICES.includes(service.id) && (
+          <SqlConsoleDialog
+            serviceId={service.id}
+            serviceName={service.name}
+            disabled={!isRunning}
+          />
+        )}
+
         {/* Conditional Add vhost button for RabbitMQ */}
         {service.service_type === 'MessageBroker' && (
           <AddVhostDialog
@@ -202,6 +290,14 @@ export function DockerServiceCard({
           />
         )}
 
+        {/* Connection details and export to a worktree's .env */}
+        {service.port && (
+          <ConnectionInfoDialog
+            serviceId={service.id}
+            serviceName={service.name}
+          />
+        )}
+
         <Box sx={{ ml: 'auto' }}>
           <Tooltip title="Copy Connection URL">
             <Button
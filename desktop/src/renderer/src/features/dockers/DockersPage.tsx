@@ -5,7 +5,9 @@ import {
   ChevronRight,
   ExpandMore as ChevronDown,
   LockOutlined as LockIcon,
-  Dns as ContainerIcon
+  Dns as ContainerIcon,
+  PlayArrow as PlayIcon,
+  Stop as StopIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -18,11 +20,11 @@ import {
   IconButton,
   Collapse
 } from '@mui/material'
-import { LogPanel } from '@/components/shared/LogPanel'
 import { PageHeader } from '@/components/shared/PageHeader'
 import { LoadingState } from '@/components/shared/LoadingState'
 import { EmptyState } from '@/components/shared/EmptyState'
 import { DockerServiceCard } from './DockerServiceCard'
+import { DockerLogFollowPanel } from './DockerLogFollowPanel'
 import { PortConflictDialog } from './PortConflictDialog'
 import { useDockersState } from '@/hooks/useAppState'
 import type { DockerServiceInfo } from '@/types/state'
@@ -42,11 +44,10 @@ export function DockersPage() {
   // Derive values from state
   const services = dockers?.services ?? []
   const selectedServiceId = dockers?.selected_service_id ?? null
-  const logs = dockers?.logs ?? []
   const isRefreshing = dockers?.is_loading ?? false
-  const isRefreshingLogs = dockers?.is_loading_logs ?? false
   const dockerAvailable = dockers?.docker_available ?? null
   const pendingConflict = dockers?.pending_conflict ?? null
+  const compose = dockers?.compose ?? null
 
   const selectedService = services.find((s) => s.id === selectedServiceId)
 
@@ -102,6 +103,16 @@ export function DockersPage() {
     const service = services.find((s) => s.id === id)
     if (!service) return
 
+    if (service.compose_service) {
+      const payload = { services: [service.compose_service] }
+      if (service.status === 'running') {
+        await dispatch({ type: 'ComposeStop', payload })
+      } else {
+        await dispatch({ type: 'ComposeUp', payload })
+      }
+      return
+    }
+
     if (service.status === 'running') {
       await dispatch({ type: 'StopDockerService', payload: { service_id: id } })
     } else {
@@ -110,18 +121,36 @@ export function DockersPage() {
   }, [services, dispatch])
 
   const handleRestart = useCallback(async (id: string) => {
+    const service = services.find((s) => s.id === id)
+    if (service?.compose_service) {
+      await dispatch({ type: 'ComposeRestart', payload: { services: [service.compose_service] } })
+      return
+    }
     await dispatch({ type: 'RestartDockerService', payload: { service_id: id } })
+  }, [services, dispatch])
+
+  const handleComposeUp = useCallback(async () => {
+    await dispatch({ type: 'ComposeUp', payload: {} })
   }, [dispatch])
 
+  const handleComposeStop = useCallback(async () => {
+    await dispatch({ type: 'ComposeStop', payload: {} })
+  }, [dispatch])
+
+  // Selecting a service starts following its logs (see DockerLogFollowPanel)
   const handleViewLogs = useCallback(async (id: string) => {
     await dispatch({ type: 'SelectDockerService', payload: { service_id: id } })
-    await dispatch({ type: 'FetchDockerLogs', payload: { service_id: id, tail: 100 } })
   }, [dispatch])
 
-  const refreshLogs = useCallback(async () => {
-    if (!selectedServiceId) return
-    await dispatch({ type: 'FetchDockerLogs', payload: { service_id: selectedServiceId, tail: 100 } })
-  }, [selectedServiceId, dispatch])
+  // Shells open as terminal sessions in the active worktree
+  const handleOpenShell = useCallback(async (id: string) => {
+    await dispatch({ type: 'ExecContainerShell', payload: { service_id: id, shell: null } })
+    await dispatch({ type: 'SetActiveView', payload: { view: 'terminal' } })
+  }, [dispatch])
+
+  const handleUpdateImage = useCallback(async (id: string) => {
+    await dispatch({ type: 'UpdateDockerImage', payload: { service_id: id } })
+  }, [dispatch])
 
   const handleRefreshAll = useCallback(async () => {
     await dispatch({ type: 'RefreshDockerServices' })
@@ -218,6 +247,7 @@ export function DockersPage() {
             <Stack spacing={2}>
               {serviceGroups.map((group) => {
                 const isCollapsed = collapsedGroups.has(group.name)
+                const isComposeGroup = compose?.name === group.name
                 return (
                   <Paper
                     key={group.name}
@@ -226,39 +256,62 @@ export function DockersPage() {
                     sx={{ overflow: 'hidden', borderColor: 'outlineVariant' }}
                   >
                     {/* Group Header */}
-                    <Box
-                      component="button"
-                      onClick={() => toggleGroup(group.name)}
-                      sx={{
-                        display: 'flex',
-                        width: '100%',
-                        alignItems: 'center',
-                        justifyContent: 'space-between',
-                        px: 2,
-                        py: 1,
-                        border: 'none',
-                        bgcolor: 'transparent',
-                        cursor: 'pointer',
-                        textAlign: 'left',
-                        '&:hover': { bgcolor: 'action.hover' }
-                      }}
-                    >
-                      <Stack direction="row" spacing={1} alignItems="center">
-                        {isCollapsed ? <ChevronRight fontSize="small" /> : <ChevronDown fontSize="small" />}
-                        <Typography variant="subtitle2" fontWeight={600}>{group.name}</Typography>
-                        <Chip
-                          label={`${group.runningCount}/${group.services.length}`}
-                          size="small"
-                          sx={{ height: 20, fontSize: '0.65rem' }}
-                        />
-                      </Stack>
-                      {!group.isRstnManaged && (
-                        <Stack direction="row" spacing={0.5} alignItems="center" sx={{ color: 'text.secondary' }}>
-                          <LockIcon sx={{ fontSize: 14 }} />
-                          <Typography variant="caption">read-only</Typography>
+                    <Stack direction="row" alignItems="center">
+                      <Box
+                        component="button"
+                        onClick={() => toggleGroup(group.name)}
+                        sx={{
+                          display: 'flex',
+                          width: '100%',
+                          alignItems: 'center',
+                          justifyContent: 'space-between',
+                          px: 2,
+                          py: 1,
+                          border: 'none',
+                          bgcolor: 'transparent',
+                          cursor: 'pointer',
+                          textAlign: 'left',
+                          '&:hover': { bgcolor: 'action.hover' }
+                        }}
+                      >
+                        <Stack direction="row" spacing={1} alignItems="center">
+                          {isCollapsed ? <ChevronRight fontSize="small" /> : <ChevronDown fontSize="small" />}
+                          <Typography variant="subtitle2" fontWeight={600}>{group.name}</Typography>
+                          <Chip
+                            label={`${group.runningCount}/${group.services.length}`}
+                            size="small"
+                            sx={{ height: 20, fontSize: '0.65rem' }}
+                          />
+                        </Stack>
+                        {!group.isRstnManaged && !isComposeGroup && (
+                          <Stack direction="row" spacing={0.5} alignItems="center" sx={{ color: 'text.secondary' }}>
+                            <LockIcon sx={{ fontSize: 14 }} />
+                            <Typography variant="caption">read-only</Typography>
+                          </Stack>
+                        )}
+                      </Box>
+                      {isComposeGroup && (
+                        <Stack
+                          direction="row"
+                          spacing={0.5}
+                          alignItems="center"
+                          sx={{ pr: 1 }}
+                        >
+                          <Typography variant="caption" color="text.secondary">compose</Typography>
+                          <IconButton size="small" title="Start all" onClick={handleComposeUp}>
+                            <PlayIcon fontSize="small" />
+                          </IconButton>
+                          <IconButton
+                            size="small"
+                            title="Stop all"
+                            onClick={handleComposeStop}
+                            disabled={group.runningCount === 0}
+                          >
+                            <StopIcon fontSize="small" />
+                          </IconButton>
                         </Stack>
                       )}
-                    </Box>
+                    </Stack>
 
                     {/* Group Services */}
                     <Collapse in={!isCollapsed}>
@@ -273,6 +326,8 @@ export function DockersPage() {
                             onToggle={handleToggle}
                             onRestart={handleRestart}
                             onViewLogs={handleViewLogs}
+                            onOpenShell={handleOpenShell}
+                            onUpdateImage={handleUpdateImage}
                           />
                         ))}
                       </Stack>
@@ -298,14 +353,7 @@ export function DockersPage() {
 
         {/* Right: Log Panel */}
         <Box sx={{ width: '50%', overflow: 'hidden' }}>
-          <LogPanel
-            title={selectedService ? `${selectedService.name} Logs` : 'Logs'}
-            logs={logs}
-            onRefresh={selectedServiceId ? () => refreshLogs() : undefined}
-            isRefreshing={isRefreshingLogs}
-            showCopy={true}
-            emptyMessage="Click a service to view its logs"
-          />
+          <DockerLogFollowPanel service={selectedService} />
         </Box>
       </Stack>
       <style>{`
@@ -0,0 +1,88 @@
+import { Box, Stack, Tooltip, Typography } from '@mui/material'
+import type { ServiceStats } from '@/types/state'
+
+/** Memory use (share of the limit) highlighted as a warning */
+const MEMORY_WARNING_RATIO = 0.8
+
+function formatBytes(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+  return `${(bytes / (1024 * 1024 * 1024)).toFixed(2)} GB`
+}
+
+interface SparklineProps {
+  values: number[]
+  max?: number
+  color: string
+}
+
+function Sparkline({ values, max, color }: SparklineProps) {
+  const width = 64
+  const height = 16
+  if (values.length < 2) return <Box sx={{ width, height }} />
+
+  const top = Math.max(max ?? 0, ...values, 1)
+  const step = width / (values.length - 1)
+  const points = values
+    .map((v, i) => `${(i * step).toFixed(1)},${(height - (v / top) * height).toFixed(1)}`)
+    .join(' ')
+
+  return (
+    <Box component="svg" width={width} height={height} sx={{ color, display: 'block' }}>
+      <polyline points={points} fill="none" stroke="currentColor" strokeWidth={1.5} />
+    </Box>
+  )
+}
+
+interface ServiceStatsBarProps {
+  stats: ServiceStats
+}
+
+/**
+ * CPU, memory, network and block I/O of a running service, with sparklines
+ * of recent CPU and memory use.
+ */
+export function ServiceStatsBar({ stats }: ServiceStatsBarProps) {
+  const memoryRatio = stats.memory_limit > 0 ? stats.memory_usage / stats.memory_limit : 0
+  const memoryColor = memoryRatio >= MEMORY_WARNING_RATIO ? 'error.main' : 'secondary.main'
+
+  return (
+    <Stack
+      direction="row"
+      spacing={2}
+      alignItems="center"
+      sx={{ mt: 1, flexWrap: 'wrap', rowGap: 1, color: 'onSurfaceVariant.main' }}
+    >
+      <Stack direction="row" spacing={0.75} alignItems="center">
+        <Typography variant="caption" sx={{ fontFamily: 'monospace', minWidth: 72 }}>
+          CPU {stats.cpu_percent.toFixed(1)}%
+        </Typography>
+        <Sparkline values={stats.cpu_history} max={100} color="primary.main" />
+      </Stack>
+
+      <Stack direction="row" spacing={0.75} alignItems="center">
+        <Typography
+          variant="caption"
+          sx={{ fontFamily: 'monospace', color: memoryRatio >= MEMORY_WARNING_RATIO ? 'error.main' : undefined }}
+        >
+          MEM {formatBytes(stats.memory_usage)}
+          {stats.memory_limit > 0 && ` / ${formatBytes(stats.memory_limit)}`}
+        </Typography>
+        <Sparkline values={stats.memory_history} color={memoryColor} />
+      </Stack>
+
+      <Tooltip title="Network received / sent">
+        <Typography variant="caption" sx={{ fontFamily: 'monospace' }}>
+          NET {formatBytes(stats.net_rx_bytes)} / {formatBytes(stats.net_tx_bytes)}
+        </Typography>
+      </Tooltip>
+
+      <Tooltip title="Block I/O read / written">
+        <Typography variant="caption" sx={{ fontFamily: 'monospace' }}>
+          BLOCK {formatBytes(stats.block_read_bytes)} / {formatBytes(stats.block_write_bytes)}
+        </Typography>
+      </Tooltip>
+    </Stack>
+  )
+}
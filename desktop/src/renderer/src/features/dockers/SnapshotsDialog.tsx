@@ -0,0 +1,282 @@
+import { useState, useEffect } from 'react'
+import {
+  Backup as BackupIcon,
+  Restore as RestoreIcon,
+  Delete as DeleteIcon,
+  ContentCopy as CopyIcon,
+  Check as CheckIcon
+} from '@mui/icons-material'
+import {
+  Button,
+  TextField,
+  Dialog,
+  DialogTitle,
+  DialogContent,
+  DialogContentText,
+  DialogActions,
+  Typography,
+  IconButton,
+  Stack,
+  InputAdornment,
+  List,
+  ListItemButton,
+  ListItemText,
+  Tooltip
+} from '@mui/material'
+import { useDockersState } from '@/hooks/useAppState'
+
+/** Services whose data can be snapshotted */
+export const SNAPSHOT_SERVICES = ['rstn-postgres', 'rstn-mysql', 'rstn-mongodb', 'rstn-redis']
+
+const DB_NAME_PATTERN = /^[a-zA-Z0-9_]+$/
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+}
+
+interface SnapshotsDialogProps {
+  serviceId: string
+  serviceName: string
+  disabled?: boolean
+}
+
+export function SnapshotsDialog({
+  serviceId,
+  serviceName,
+  disabled,
+}: SnapshotsDialogProps) {
+  const { dockers, dispatch } = useDockersState()
+  const [open, setOpen] = useState(false)
+  const [dbName, setDbName] = useState('')
+  const [selectedId, setSelectedId] = useState<string | null>(null)
+  const [targetName, setTargetName] = useState('')
+  const [restoreRequested, setRestoreRequested] = useState(false)
+  const [connectionString, setConnectionString] = useState<string | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [copied, setCopied] = useState(false)
+
+  // Redis has a single dataset: no database names, restores replace it
+  const hasDatabases = serviceId !== 'rstn-redis'
+  const snapshots = (dockers?.snapshots ?? []).filter((s) => s.service_id === serviceId)
+  const isBusy = dockers?.snapshot_in_progress === serviceId
+
+  // Watch for the restored database's connection string in global state
+  useEffect(() => {
+    if (open && restoreRequested && dockers?.last_connection_string) {
+      setConnectionString(dockers.last_connection_string)
+      setRestoreRequested(false)
+    }
+  }, [dockers?.last_connection_string, open, restoreRequested])
+
+  const handleSnapshot = async () => {
+    if (hasDatabases && !DB_NAME_PATTERN.test(dbName)) {
+      setError('Database name must contain only alphanumeric characters and underscores')
+      return
+    }
+    setError(null)
+    await dispatch({
+      type: 'CreateServiceSnapshot',
+      payload: { service_id: serviceId, database: hasDatabases ? dbName : undefined }
+    })
+  }
+
+  const handleRestore = async () => {
+    if (!selectedId) return
+    if (hasDatabases && !DB_NAME_PATTERN.test(targetName)) {
+      setError('Target database name must contain only alphanumeric characters and underscores')
+      return
+    }
+    setError(null)
+    setRestoreRequested(true)
+    await dispatch({
+      type: 'RestoreServiceSnapshot',
+      payload: {
+        service_id: serviceId,
+        snapshot_id: selectedId,
+        target_database: hasDatabases ? targetName : undefined
+      }
+    })
+  }
+
+  const handleDelete = async (snapshotId: string) => {
+    if (selectedId === snapshotId) setSelectedId(null)
+    await dispatch({
+      type: 'DeleteServiceSnapshot',
+      payload: { service_id: serviceId, snapshot_id: snapshotId }
+    })
+  }
+
+  const handleCopy = async () => {
+    if (connectionString) {
+      await navigator.clipboard.writeText(connectionString)
+      setCopied(true)
+      setTimeout(() => setCopied(false), 2000)
+    }
+  }
+
+  const handleOpenChange = (isOpen: boolean) => {
+    setOpen(isOpen)
+    if (isOpen) {
+      dispatch({ type: 'RefreshServiceSnapshots' })
+    } else {
+      setDbName('')
+      setSelectedId(null)
+      setTargetName('')
+      setConnectionString(null)
+      setError(null)
+      setCopied(false)
+      setRestoreRequested(false)
+      dispatch({ type: 'SetDockerConnectionString', payload: { connection_string: null } })
+    }
+  }
+
+  return (
+    <>
+      <Button
+        variant="text"
+        size="small"
+        disabled={disabled}
+        onClick={(e) => {
+          e.stopPropagation()
+          handleOpenChange(true)
+        }}
+        startIcon={<BackupIcon />}
+      >
+        Snapshots
+      </Button>
+
+      <Dialog
+        open={open}
+        onClose={() => handleOpenChange(false)}
+        onClick={(e) => e.stopPropagation()}
+        maxWidth="sm"
+        fullWidth
+      >
+        <DialogTitle>Snapshots</DialogTitle>
+        <DialogContent>
+          <DialogContentText sx={{ mb: 2 }}>
+            Dump {serviceName} data to ~/.rstn/snapshots and restore it
+            {hasDatabases ? ' into a new database' : ' (replaces the current dataset)'}
+          </DialogContentText>
+
+          <Stack direction="row" spacing={1} alignItems="flex-start" sx={{ mt: 1 }}>
+            {hasDatabases && (
+              <TextField
+                size="small"
+                label="Database"
+                placeholder="my_database"
+                fullWidth
+                value={dbName}
+                onChange={(e) => setDbName(e.target.value)}
+                disabled={isBusy}
+              />
+            )}
+            <Button
+              variant="contained"
+              onClick={handleSnapshot}
+              disabled={isBusy || (hasDatabases && !dbName.trim())}
+              startIcon={<BackupIcon />}
+              sx={{ whiteSpace: 'nowrap', flexShrink: 0 }}
+            >
+              {isBusy && !restoreRequested ? 'Saving...' : 'Take Snapshot'}
+            </Button>
+          </Stack>
+
+          {snapshots.length === 0 ? (
+            <Typography variant="body2" color="text.secondary" sx={{ mt: 3 }}>
+              No snapshots yet
+            </Typography>
+          ) : (
+            <List dense sx={{ mt: 2, maxHeight: 240, overflowY: 'auto' }}>
+              {snapshots.map((snapshot) => (
+                <ListItemButton
+                  key={snapshot.id}
+                  selected={selectedId === snapshot.id}
+                  onClick={() => {
+                    setSelectedId(snapshot.id)
+                    setConnectionString(null)
+                    setRestoreRequested(false)
+                  }}
+                >
+                  <ListItemText
+                    primary={snapshot.database}
+                    secondary={`${new Date(snapshot.created_at).toLocaleString()} · ${formatSize(snapshot.size_bytes)}`}
+                  />
+                  <Tooltip title="Delete snapshot">
+                    <IconButton
+                      edge="end"
+                      size="small"
+                      disabled={isBusy}
+                      onClick={(e) => {
+                        e.stopPropagation()
+                        handleDelete(snapshot.id)
+                      }}
+                    >
+                      <DeleteIcon fontSize="small" />
+                    </IconButton>
+                  </Tooltip>
+                </ListItemButton>
+              ))}
+            </List>
+          )}
+
+          {selectedId && !connectionString && (
+            <Stack direction="row" spacing={1} alignItems="flex-start" sx={{ mt: 2 }}>
+              {hasDatabases && (
+                <TextField
+                  size="small"
+                  label="Restore as"
+                  placeholder="new_database"
+                  fullWidth
+                  value={targetName}
+                  onChange={(e) => setTargetName(e.target.value)}
+                  disabled={isBusy}
+                />
+              )}
+              <Button
+                variant="outlined"
+                onClick={handleRestore}
+                disabled={isBusy || (hasDatabases && !targetName.trim())}
+                startIcon={<RestoreIcon />}
+                sx={{ whiteSpace: 'nowrap', flexShrink: 0 }}
+              >
+                {isBusy && restoreRequested ? 'Restoring...' : 'Restore'}
+              </Button>
+            </Stack>
+          )}
+
+          {error && (
+            <Typography variant="body2" color="error" sx={{ mt: 2 }}>
+              {error}
+            </Typography>
+          )}
+
+          {connectionString && (
+            <TextField
+              label="Connection String"
+              value={connectionString}
+              fullWidth
+              sx={{ mt: 3 }}
+              InputProps={{
+                readOnly: true,
+                endAdornment: (
+                  <InputAdornment position="end">
+                    <IconButton onClick={handleCopy} edge="end">
+                      {copied ? <CheckIcon color="success" /> : <CopyIcon />}
+                    </IconButton>
+                  </InputAdornment>
+                ),
+                sx: { fontFamily: 'monospace', fontSize: '0.8rem' }
+              }}
+            />
+          )}
+        </DialogContent>
+        <DialogActions sx={{ px: 3, pb: 3 }}>
+          <Button variant="contained" onClick={() => handleOpenChange(false)}>Close</Button>
+        </DialogActions>
+      </Dialog>
+    </>
+  )
+}
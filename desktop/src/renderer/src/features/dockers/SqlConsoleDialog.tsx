@@ -0,0 +1,286 @@
+import { useState } from 'react'
+import {
+  DataObject as SqlIcon,
+  PlayArrow as RunIcon,
+  TableChart as TableIcon,
+  ExpandLess,
+  ExpandMore
+} from '@mui/icons-material'
+import {
+  Box,
+  Button,
+  Collapse,
+  Dialog,
+  DialogActions,
+  DialogContent,
+  DialogTitle,
+  LinearProgress,
+  List,
+  ListItemButton,
+  ListItemIcon,
+  ListItemText,
+  MenuItem,
+  Stack,
+  Table,
+  TableBody,
+  TableCell,
+  TableContainer,
+  TableHead,
+  TableRow,
+  TextField,
+  Typography
+} from '@mui/material'
+import { useDockersState } from '@/hooks/useAppState'
+import type { SqlTableInfo, SqlValue } from '@/types/state'
+
+/** Services with a SQL console */
+export const SQL_SERVICES = ['rstn-postgres', 'rstn-mysql']
+
+const ROW_LIMITS = [100, 500, 1000, 5000]
+
+function formatValue(value: SqlValue): string {
+  if (value === null) return 'NULL'
+  if (typeof value === 'object') return JSON.stringify(value)
+  return String(value)
+}
+
+function tableLabel(table: SqlTableInfo, showSchema: boolean): string {
+  return showSchema ? `${table.schema}.${table.name}` : table.name
+}
+
+interface SqlConsoleDialogProps {
+  serviceId: string
+  serviceName: string
+  disabled?: boolean
+}
+
+/**
+ * Schema browser and ad-hoc query runner for a Postgres or MySQL service.
+ */
+export function SqlConsoleDialog({ serviceId, serviceName, disabled }: SqlConsoleDialogProps) {
+  const { dockers, dispatch } = useDockersState()
+  const [open, setOpen] = useState(false)
+  const [sql, setSql] = useState('')
+  const [limit, setLimit] = useState(500)
+  const [expanded, setExpanded] = useState<string | null>(null)
+
+  const sqlConsole = dockers?.sql_console?.service_id === serviceId ? dockers.sql_console : undefined
+  const schema = sqlConsole?.schema
+  // Postgres tables are listed per schema; MySQL databases are the schemas
+  const showSchema = (schema?.schemas.length ?? 0) > 1
+
+  const handleOpenChange = (isOpen: boolean) => {
+    setOpen(isOpen)
+    if (isOpen) {
+      dispatch({ type: 'OpenSqlConsole', payload: { service_id: serviceId } })
+    } else {
+      setExpanded(null)
+      dispatch({ type: 'CloseSqlConsole' })
+    }
+  }
+
+  const handleRun = () => {
+    if (!sql.trim() || sqlConsole?.is_running) return
+    dispatch({
+      type: 'RunSqlQuery',
+      payload: { service_id: serviceId, database: sqlConsole?.database, sql, limit }
+    })
+  }
+
+  return (
+    <>
+      <Button
+        variant="text"
+        size="small"
+        disabled={disabled}
+        onClick={(e) => {
+          e.stopPropagation()
+          handleOpenChange(true)
+        }}
+        startIcon={<SqlIcon />}
+      >
+        SQL
+      </Button>
+
+      <Dialog
+        open={open}
+        onClose={() => handleOpenChange(false)}
+        onClick={(e) => e.stopPropagation()}
+        maxWidth="lg"
+        fullWidth
+      >
+        <DialogTitle>{serviceName} SQL Console</DialogTitle>
+        <DialogContent sx={{ display: 'flex', gap: 2, height: '70vh' }}>
+          {/* Schema browser */}
+          <Stack sx={{ width: 260, flexShrink: 0 }} spacing={1}>
+            <TextField
+              select
+              size="small"
+              label="Database"
+              value={sqlConsole?.database ?? ''}
+              onChange={(e) => dispatch({
+                type: 'OpenSqlConsole',
+                payload: { service_id: serviceId, database: e.target.value || undefined }
+              })}
+              sx={{ mt: 1 }}
+            >
+              <MenuItem value="">(default)</MenuItem>
+              {schema?.databases.map((db) => (
+                <MenuItem key={db} value={db}>{db}</MenuItem>
+              ))}
+            </TextField>
+            {sqlConsole?.is_loading_schema && <LinearProgress />}
+            <List dense sx={{ flex: 1, overflowY: 'auto' }}>
+              {schema?.tables.map((table) => {
+                const key = `${table.schema}.${table.name}`
+                return (
+                  <Box key={key}>
+                    <ListItemButton onClick={() => setExpanded(expanded === key ? null : key)}>
+                      <ListItemIcon sx={{ minWidth: 28 }}>
+                        <TableIcon fontSize="small" />
+                      </ListItemIcon>
+                      <ListItemText
+                        primary={tableLabel(table, showSchema)}
+                        primaryTypographyProps={{ variant: 'body2', noWrap: true }}
+                      />
+                      {expanded === key ? <ExpandLess fontSize="small" /> : <ExpandMore fontSize="small" />}
+                    </ListItemButton>
+                    <Collapse in={expanded === key} unmountOnExit>
+                      <List dense disablePadding>
+                        {table.columns.map((column) => (
+                          <ListItemText
+                            key={column.name}
+                            sx={{ pl: 5, my: 0 }}
+                            primary={column.name}
+                            secondary={`${column.data_type}${column.nullable ? '' : ' not null'}`}
+                            primaryTypographyProps={{ variant: 'caption', fontFamily: 'monospace' }}
+                            secondaryTypographyProps={{ variant: 'caption' }}
+                          />
+                        ))}
+                      </List>
+                    </Collapse>
+                  </Box>
+                )
+              })}
+              {schema && !sqlConsole?.is_loading_schema && schema.tables.length === 0 && (
+                <Typography variant="body2" color="text.secondary" sx={{ px: 2 }}>
+                  No tables
+                </Typography>
+              )}
+            </List>
+          </Stack>
+
+          {/* Query editor and results */}
+          <Stack sx={{ flex: 1, minWidth: 0 }} spacing={1}>
+            <TextField
+              multiline
+              minRows={4}
+              maxRows={10}
+              placeholder="SELECT * FROM ..."
+              value={sql}
+              onChange={(e) => setSql(e.target.value)}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter' && (e.metaKey || e.ctrlKey)) {
+                  e.preventDefault()
+                  handleRun()
+                }
+              }}
+              InputProps={{ sx: { fontFamily: 'monospace', fontSize: '0.8rem' } }}
+              sx={{ mt: 1 }}
+            />
+            <Stack direction="row" spacing={1} alignItems="center">
+              <Button
+                variant="contained"
+                size="small"
+                startIcon={<RunIcon />}
+                disabled={!sql.trim() || sqlConsole?.is_running}
+                onClick={handleRun}
+              >
+                {sqlConsole?.is_running ? 'Running...' : 'Run'}
+              </Button>
+              <TextField
+                select
+                size="small"
+                label="Row limit"
+                value={limit}
+                onChange={(e) => setLimit(Number(e.target.value))}
+                sx={{ minWidth: 110 }}
+              >
+                {ROW_LIMITS.map((n) => (
+                  <MenuItem key={n} value={n}>{n}</MenuItem>
+                ))}
+              </TextField>
+              {sqlConsole?.result && (
+                <Typography variant="caption" color="text.secondary">
+                  {sqlConsole.result.rows.length} rows
+                  {sqlConsole.result.truncated && ` (limited to ${sqlConsole.result.rows.length})`}
+                  {' · '}{sqlConsole.result.elapsed_ms} ms
+                </Typography>
+              )}
+            </Stack>
+
+            {sqlConsole?.error && (
+              <Typography
+                variant="body2"
+                color="error"
+                sx={{ fontFamily: 'monospace', whiteSpace: 'pre-wrap' }}
+              >
+                {sqlConsole.error}
+              </Typography>
+            )}
+
+            {sqlConsole?.result && sqlConsole.result.columns.length > 0 ? (
+              <TableContainer sx={{ flex: 1, border: 1, borderColor: 'divider', borderRadius: 1 }}>
+                <Table size="small" stickyHeader>
+                  <TableHead>
+                    <TableRow>
+                      {sqlConsole.result.columns.map((column, i) => (
+                        <TableCell key={i} sx={{ whiteSpace: 'nowrap' }}>
+                          {column.name}
+                          {column.data_type && (
+                            <Typography component="span" variant="caption" color="text.secondary" sx={{ ml: 0.5 }}>
+                              {column.data_type}
+                            </Typography>
+                          )}
+                        </TableCell>
+                      ))}
+                    </TableRow>
+                  </TableHead>
+                  <TableBody>
+                    {sqlConsole.result.rows.map((row, i) => (
+                      <TableRow key={i} hover>
+                        {row.map((value, j) => (
+                          <TableCell
+                            key={j}
+                            sx={{
+                              fontFamily: 'monospace',
+                              fontSize: '0.75rem',
+                              whiteSpace: 'pre',
+                              color: value === null ? 'text.disabled' : undefined,
+                              textAlign: typeof value === 'number' ? 'right' : undefined,
+                            }}
+                          >
+                            {formatValue(value)}
+                          </TableCell>
+                        ))}
+                      </TableRow>
+                    ))}
+                  </TableBody>
+                </Table>
+              </TableContainer>
+            ) : (
+              sqlConsole?.result && !sqlConsole.error && (
+                <Typography variant="body2" color="text.secondary">
+                  Statement executed (no rows returned)
+                </Typography>
+              )
+            )}
+          </Stack>
+        </DialogContent>
+        <DialogActions>
+          <Button onClick={() => handleOpenChange(false)}>Close</Button>
+        </DialogActions>
+      </Dialog>
+    </>
+  )
+}
@@ -19,8 +19,10 @@ import {
 import { EnvPatternList } from './EnvPatternList'
 import { WorktreeSelector } from './WorktreeSelector'
 import { EnvCopyHistory } from './EnvCopyHistory'
+import { WorktreeDbSettings } from './WorktreeDbSettings'
 import { useEnvState } from '@/hooks/useAppState'
 import { PageHeader } from '@/components/shared/PageHeader'
+import type { WorktreeDbConfig } from '@/types/state'
 
 /**
  * Environment Management Page.
@@ -55,6 +57,21 @@ export function EnvPage() {
     [dispatch]
   )
 
+  const handleWorktreeDbChange = useCallback(
+    async (config: WorktreeDbConfig) => {
+      await dispatch({
+        type: 'SetWorktreeDbConfig',
+        payload: {
+          enabled: config.enabled,
+          service_id: config.service_id,
+          template_database: config.template_database ?? null,
+          env_var: config.env_var,
+        },
+      })
+    },
+    [dispatch]
+  )
+
   const handleCopyEnvFiles = useCallback(async () => {
     if (!effectiveSource || !selectedTarget) return
 
@@ -184,6 +201,15 @@ export function EnvPage() {
           </CardContent>
         </Card>
 
+        {/* Worktree Databases Card */}
+        {envConfig.worktree_db && (
+          <Card variant="outlined" sx={{ borderRadius: 4 }}>
+            <CardContent sx={{ p: 3 }}>
+              <WorktreeDbSettings config={envConfig.worktree_db} onChange={handleWorktreeDbChange} />
+            </CardContent>
+          </Card>
+        )}
+
         {/* Recent Activity Card */}
         <Card variant="outlined" sx={{ borderRadius: 4 }}>
           <CardContent sx={{ p: 3 }}>
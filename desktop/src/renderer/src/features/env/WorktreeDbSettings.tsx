@@ -0,0 +1,112 @@
+import { useState, useEffect } from 'react'
+import { Storage as DatabaseIcon } from '@mui/icons-material'
+import {
+  Box,
+  Typography,
+  Stack,
+  Switch,
+  FormControlLabel,
+  TextField,
+  MenuItem,
+  List,
+  ListItem,
+  ListItemText
+} from '@mui/material'
+import type { WorktreeDbConfig } from '@/types/state'
+
+const DB_SERVICES = [
+  { id: 'rstn-postgres', label: 'PostgreSQL' },
+  { id: 'rstn-mysql', label: 'MySQL' },
+]
+
+interface WorktreeDbSettingsProps {
+  config: WorktreeDbConfig
+  onChange: (config: WorktreeDbConfig) => void
+}
+
+/**
+ * Settings for databases created with each new worktree.
+ */
+export function WorktreeDbSettings({ config, onChange }: WorktreeDbSettingsProps) {
+  // Text fields are committed on blur
+  const [template, setTemplate] = useState(config.template_database ?? '')
+  const [envVar, setEnvVar] = useState(config.env_var)
+
+  useEffect(() => {
+    setTemplate(config.template_database ?? '')
+    setEnvVar(config.env_var)
+  }, [config.template_database, config.env_var])
+
+  const databases = Object.entries(config.databases ?? {})
+
+  return (
+    <Box>
+      <Stack direction="row" alignItems="center" justifyContent="space-between" sx={{ mb: 2 }}>
+        <Stack direction="row" alignItems="center" spacing={1}>
+          <DatabaseIcon fontSize="small" color="primary" />
+          <Typography variant="h6" fontWeight={600}>Worktree Databases</Typography>
+        </Stack>
+        <FormControlLabel
+          control={
+            <Switch
+              checked={config.enabled}
+              onChange={(e) => onChange({ ...config, enabled: e.target.checked })}
+            />
+          }
+          label="Create with new worktrees"
+        />
+      </Stack>
+
+      <Typography variant="body2" color="text.secondary" sx={{ mb: 2 }}>
+        Each new worktree gets its own database named after its branch. The connection string is
+        written to the worktree's .env.
+      </Typography>
+
+      <Stack direction="row" spacing={2}>
+        <TextField
+          select
+          size="small"
+          label="Service"
+          value={config.service_id}
+          onChange={(e) => onChange({ ...config, service_id: e.target.value })}
+          sx={{ minWidth: 160 }}
+        >
+          {DB_SERVICES.map((service) => (
+            <MenuItem key={service.id} value={service.id}>{service.label}</MenuItem>
+          ))}
+        </TextField>
+        <TextField
+          size="small"
+          label="Clone from"
+          placeholder="Empty database"
+          value={template}
+          onChange={(e) => setTemplate(e.target.value)}
+          onBlur={() => onChange({ ...config, template_database: template.trim() || undefined })}
+          sx={{ flex: 1 }}
+        />
+        <TextField
+          size="small"
+          label="Variable"
+          value={envVar}
+          onChange={(e) => setEnvVar(e.target.value)}
+          onBlur={() => envVar.trim() && onChange({ ...config, env_var: envVar.trim() })}
+          sx={{ flex: 1 }}
+        />
+      </Stack>
+
+      {databases.length > 0 && (
+        <List dense sx={{ mt: 2 }}>
+          {databases.map(([path, db]) => (
+            <ListItem key={path} disableGutters>
+              <ListItemText
+                primary={db.name}
+                secondary={path}
+                primaryTypographyProps={{ fontFamily: 'monospace' }}
+              />
+            </ListItem>
+          ))}
+        </List>
+      )}
+    </Box>
+  )
+}
@@ -1,5 +1,5 @@
-import { Box, Button, Paper, Stack, Typography } from '@mui/material'
-import { CheckCircle, Cancel, PlayArrow } from '@mui/icons-material'
+import { Box, Button, IconButton, Paper, Stack, Tooltip, Typography } from '@mui/material'
+import { CheckCircle, Cancel, PlayArrow, Visibility, VisibilityOutlined } from '@mui/icons-material'
 import type { JustCommand, TaskStatus } from '@/types/task'
 
 interface TaskCardProps {
@@ -7,9 +7,19 @@ interface TaskCardProps {
   status: TaskStatus
   isActive?: boolean
   onRun?: (name: string) => void
+  /** Whether the task re-runs on file changes */
+  isWatching?: boolean
+  onToggleWatch?: (name: string) => void
 }
 
-export function TaskCard({ command, status, isActive = false, onRun }: TaskCardProps) {
+export function TaskCard({
+  command,
+  status,
+  isActive = false,
+  onRun,
+  isWatching = false,
+  onToggleWatch,
+}: TaskCardProps) {
   const isRunning = status === 'running'
 
   return (
@@ -44,6 +54,20 @@ export function TaskCard({ command, status, isActive = false, onRun }: TaskCardP
         )}
       </Box>
 
+      {onToggleWatch && (
+        <Tooltip title={isWatching ? 'Stop watching' : 'Re-run on file changes'}>
+          <IconButton
+            size="small"
+            color={isWatching ? 'primary' : 'default'}
+            aria-label={isWatching ? 'Stop watching' : 'Watch'}
+            onClick={() => onToggleWatch(command.name)}
+            sx={{ ml: 1, flexShrink: 0 }}
+          >
+            {isWatching ? <Visibility fontSize="small" /> : <VisibilityOutlined fontSize="small" />}
+          </IconButton>
+        </Tooltip>
+      )}
+
       <Button
         variant={isRunning ? 'contained' : 'outlined'}
         size="small"
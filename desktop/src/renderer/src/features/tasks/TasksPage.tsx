@@ -1,6 +1,6 @@
 import { useEffect, useCallback } from 'react'
-import { Box, Button, Paper, Stack, Typography } from '@mui/material'
-import { ListAlt, Refresh } from '@mui/icons-material'
+import { Box, Button, Chip, Paper, Stack, Typography } from '@mui/material'
+import { ListAlt, Refresh, Stop } from '@mui/icons-material'
 import { LogPanel } from '@/components/shared/LogPanel'
 import { PageHeader } from '@/components/shared/PageHeader'
 import { EmptyState } from '@/components/shared/EmptyState'
@@ -20,8 +20,11 @@ export function TasksPage() {
   // Derive values from state
   const commands = tasks?.commands ?? []
   const taskStatuses = tasks?.task_statuses ?? {}
-  const output = tasks?.output ?? []
-  const activeCommand = tasks?.active_command ?? null
+  const runs = tasks?.runs ?? []
+  const watches = tasks?.watches ?? []
+  // Show the chosen run, falling back to the most recent one
+  const selectedRun =
+    runs.find((run) => run.id === tasks?.selected_run) ?? runs[runs.length - 1] ?? null
   const isRefreshing = tasks?.is_loading ?? false
   const error = tasks?.error ?? null
 
@@ -41,10 +44,27 @@ export function TasksPage() {
     [dispatch, projectPath]
   )
 
+  const handleToggleWatch = useCallback(
+    async (name: string) => {
+      if (!projectPath) return
+      if (watches.some((watch) => watch.name === name)) {
+        await dispatch({ type: 'StopTaskWatch', payload: { name, cwd: projectPath } })
+      } else {
+        await dispatch({ type: 'StartTaskWatch', payload: { name, cwd: projectPath, restart: true } })
+      }
+    },
+    [dispatch, projectPath, watches]
+  )
+
+  const handleCancel = useCallback(
+    async (runId: string) => {
+      await dispatch({ type: 'CancelJustCommand', payload: { run_id: runId } })
+    },
+    [dispatch]
+  )
+
   const handleRefresh = useCallback(async () => {
     if (projectPath) {
-      await dispatch({ type: 'ClearTaskOutput' })
-      await dispatch({ type: 'SetActiveCommand', payload: { name: null } })
       await dispatch({ type: 'RefreshJustfile' })
     }
   }, [dispatch, projectPath])
@@ -90,8 +110,10 @@ export function TasksPage() {
                   key={cmd.name}
                   command={cmd}
                   status={taskStatuses[cmd.name] || 'idle'}
-                  isActive={activeCommand === cmd.name}
+                  isActive={runs.some((run) => run.name === cmd.name && run.status === 'running')}
                   onRun={handleRun}
+                  isWatching={watches.some((watch) => watch.name === cmd.name)}
+                  onToggleWatch={handleToggleWatch}
                 />
               ))}
               {commands.length === 0 && !isRefreshing && (
@@ -111,14 +133,34 @@ export function TasksPage() {
         </Paper>
 
         {/* Column 2: Log Panel */}
-        <Box sx={{ width: '50%', overflow: 'hidden' }}>
+        <Stack sx={{ width: '50%', overflow: 'hidden' }} spacing={1}>
+          {runs.length > 0 && (
+            <Stack direction="row" spacing={1} sx={{ flexWrap: 'wrap', alignItems: 'center' }}>
+              {runs.map((run) => (
+                <Chip
+                  key={run.id}
+                  size="small"
+                  label={run.name}
+                  color={run.status === 'error' ? 'error' : run.status === 'success' ? 'success' : 'default'}
+                  variant={run.id === selectedRun?.id ? 'filled' : 'outlined'}
+                  onClick={() => dispatch({ type: 'SelectTaskRun', payload: { run_id: run.id } })}
+                  onDelete={
+                    run.status === 'running'
+                      ? () => handleCancel(run.id)
+                      : () => dispatch({ type: 'DismissTaskRun', payload: { run_id: run.id } })
+                  }
+                  deleteIcon={run.status === 'running' ? <Stop /> : undefined}
+                />
+              ))}
+            </Stack>
+          )}
           <LogPanel
-            title={activeCommand ? `just ${activeCommand}` : 'Output'}
-            logs={output}
+            title={selectedRun ? selectedRun.command_line : 'Output'}
+            logs={selectedRun?.output ?? []}
             showCopy={true}
             emptyMessage="Select a command to run"
           />
-        </Box>
+        </Stack>
       </Stack>
     </Stack>
   )
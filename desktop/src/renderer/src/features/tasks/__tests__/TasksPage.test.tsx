@@ -17,8 +17,8 @@ vi.mock('@/hooks/useAppState', () => ({
     tasks: {
       commands: [],
       task_statuses: {},
-      output: [],
-      active_command: null,
+      runs: [],
+      selected_run: null,
       is_loading: false,
       error: null,
     },
@@ -47,8 +47,8 @@ describe('TasksPage - with project', () => {
         tasks: {
           commands: mockCommands,
           task_statuses: {},
-          output: [],
-          active_command: null,
+          runs: [],
+          selected_run: null,
           is_loading: false,
           error: null,
         },
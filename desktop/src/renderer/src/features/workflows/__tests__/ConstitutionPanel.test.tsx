@@ -39,8 +39,8 @@ const createMockState = (options: {
           tasks: {
             commands: [],
             task_statuses: {},
-            active_command: 'constitution-management',
-            output: [],
+            runs: [],
+            selected_run: null,
             is_loading: false,
             error: null,
             constitution_exists: options.constitutionExists ?? null,
@@ -23,14 +23,15 @@ const createMockWorktree = (overrides?: Partial<WorktreeState>): WorktreeState =
     is_typing: false,
   },
   terminal: {
+    sessions: [],
     cols: 80,
     rows: 24,
   },
   tasks: {
     commands: [],
     task_statuses: {},
-    output: [],
-    active_command: null,
+    runs: [],
+    selected_run: null,
     is_loading: false,
     error: null,
     constitution_workflow: null,
@@ -66,6 +67,11 @@ const createMockProject = (overrides?: Partial<ProjectState>): ProjectState => (
     auto_copy_enabled: true,
     source_worktree: null,
     last_copy_result: null,
+    worktree_db: {
+      enabled: false,
+      service_id: 'rstn-postgres',
+      env_var: 'DATABASE_URL',
+    },
   },
   agent_rules_config: {
     enabled: false,
@@ -347,10 +353,10 @@ describe('useTasksState', () => {
 
   it('returns tasks state and project path', async () => {
     const tasks = {
-      commands: [{ name: 'build', description: 'Build project', recipe: 'cargo build' }],
+      commands: [{ name: 'build', source: 'just', description: 'Build project', recipe: 'cargo build', private: false, confirm: false }],
       task_statuses: {},
-      output: [],
-      active_command: null,
+      runs: [],
+      selected_run: null,
       is_loading: false,
       error: null,
       constitution_workflow: null,
@@ -90,6 +90,13 @@ export interface FileExplorerState {
   tabs: FileTab[]
   /** Currently active tab path */
   active_tab_path?: string
+  /** Error/warning counts from task diagnostics (absolute path -> counts; directories aggregate) */
+  diagnostic_badges?: Record<string, DiagnosticBadge>
+}
+
+export interface DiagnosticBadge {
+  errors: number
+  warnings: number
 }
 
 // ============================================================================
@@ -100,6 +107,9 @@ export type ServiceStatus = 'running' | 'stopped' | 'starting' | 'stopping' | 'e
 
 export type ServiceType = 'Database' | 'MessageBroker' | 'Cache' | 'Other'
 
+/** Readiness of a running service, from its readiness probe */
+export type ServiceHealth = 'starting' | 'healthy' | 'unhealthy'
+
 export interface DockerServiceInfo {
   id: string
   name: string
@@ -109,6 +119,37 @@ export interface DockerServiceInfo {
   service_type: ServiceType
   project_group: string | null
   is_rstn_managed: boolean
+  compose_service?: string
+  health?: ServiceHealth
+  stats?: ServiceStats
+  /** Image pull in progress */
+  pull?: ImagePull
+}
+
+/** Progress of an image pull, aggregated over its layers */
+export interface ImagePull {
+  image: string
+  /** Fraction done, from 0 to 1 */
+  progress: number
+  layers_done: number
+  layers_total: number
+  /** What the pull is doing (e.g. "Downloading", "Extracting") */
+  status: string
+}
+
+/** Live resource usage of a running service */
+export interface ServiceStats {
+  /** Percentage of one CPU (can exceed 100 on multi-core hosts) */
+  cpu_percent: number
+  memory_usage: number
+  memory_limit: number
+  net_rx_bytes: number
+  net_tx_bytes: number
+  block_read_bytes: number
+  block_write_bytes: number
+  /** Recent samples, oldest first */
+  cpu_history: number[]
+  memory_history: number[]
 }
 
 export interface ConflictingContainer {
@@ -139,6 +180,115 @@ export interface DockersState {
   pending_conflict: PendingConflict | null
   port_overrides: Record<string, number>
   last_connection_string: string | null
+  compose?: ComposeProjectInfo
+  snapshots?: ServiceSnapshotInfo[]
+  snapshot_in_progress?: string
+  log_follow?: DockerLogFollow
+  log_lines?: DockerLogLine[]
+  sql_console?: SqlConsoleState
+  /** Connection details of the last service looked up or exported */
+  connection_info?: ServiceConnectionInfo
+}
+
+/** How an app on the host connects to a service */
+export interface ServiceConnectionInfo {
+  service_id: string
+  url: string
+  host: string
+  /** Host port the container is mapped to */
+  port: number
+  user: string | null
+  password: string | null
+  /** Variable the URL is conventionally read from (e.g. DATABASE_URL) */
+  env_var: string
+}
+
+export interface SqlConsoleState {
+  service_id: string
+  /** Database queries run against (absent = the server's default) */
+  database?: string
+  schema: SqlSchema
+  is_loading_schema: boolean
+  is_running: boolean
+  result?: SqlQueryResult
+  error?: string
+}
+
+export interface SqlSchema {
+  databases: string[]
+  /** Schemas of the selected database (Postgres only) */
+  schemas: string[]
+  tables: SqlTableInfo[]
+}
+
+export interface SqlTableInfo {
+  schema: string
+  name: string
+  columns: SqlTableColumn[]
+}
+
+export interface SqlTableColumn {
+  name: string
+  data_type: string
+  nullable: boolean
+  default?: string
+}
+
+export interface SqlColumn {
+  name: string
+  data_type: string | null
+}
+
+/** Cell values: numbers, booleans and JSON typed; exact numerics and everything else as strings */
+export type SqlValue = string | number | boolean | null | Record<string, unknown> | unknown[]
+
+export interface SqlQueryResult {
+  columns: SqlColumn[]
+  rows: SqlValue[][]
+  /** More rows matched than the row limit */
+  truncated: boolean
+  elapsed_ms: number
+}
+
+export interface DockerLogFollow {
+  service_id: string
+  stdout: boolean
+  stderr: boolean
+  timestamps: boolean
+  /** Unix timestamp (seconds) to stream from */
+  since?: number
+  filter?: string
+  is_streaming: boolean
+}
+
+export type DockerLogStream = 'stdout' | 'stderr'
+
+export interface DockerLogLine {
+  stream: DockerLogStream
+  timestamp?: string
+  text: string
+}
+
+export interface ServiceSnapshotInfo {
+  id: string
+  service_id: string
+  database: string
+  created_at: string
+  size_bytes: number
+}
+
+export interface ComposeServiceInfo {
+  name: string
+  image: string | null
+  ports: string[]
+  depends_on: string[]
+  container_name: string
+}
+
+export interface ComposeProjectInfo {
+  name: string
+  file: string
+  services: ComposeServiceInfo[]
 }
 
 // ============================================================================
@@ -228,12 +378,31 @@ export interface ContextState {
 // Tasks State
 // ============================================================================
 
-export type TaskStatus = 'idle' | 'running' | 'success' | 'error'
+export type TaskStatus = 'idle' | 'running' | 'success' | 'error' | 'cancelled'
+
+export type JustParameterKind = 'singular' | 'plus' | 'star'
+
+/** Where a task is defined (tasks outside justfiles are named `npm:build` etc.) */
+export type TaskSourceKind = 'just' | 'npm' | 'make' | 'cargo'
+
+export interface JustParameterInfo {
+  name: string
+  default?: string
+  kind: JustParameterKind
+  export: boolean
+}
 
 export interface JustCommandInfo {
   name: string
+  source: TaskSourceKind
   description: string | null
   recipe: string
+  parameters?: JustParameterInfo[]
+  dependencies?: string[]
+  group?: string
+  private: boolean
+  aliases?: string[]
+  confirm: boolean
 }
 
 /**
@@ -289,11 +458,86 @@ export interface ConstitutionPresetsConfig {
   temp_file_path?: string
 }
 
+/** A finished task run recorded in SQLite */
+export interface TaskRunInfo {
+  id: string
+  name: string
+  command_line: string
+  args?: string[]
+  named_args?: Record<string, string>
+  started_at: string
+  finished_at: string
+  duration_ms: number
+  exit_code: number | null
+  status: TaskStatus
+  /** Only present on the viewed run */
+  output?: string
+  output_truncated: boolean
+}
+
+/** Reliability and duration stats for one task (cancelled runs excluded) */
+export interface TaskRunStatsInfo {
+  name: string
+  runs: number
+  failures: number
+  /** Times the outcome changed between consecutive runs */
+  flips: number
+  flaky: boolean
+  avg_duration_ms: number
+  max_duration_ms: number
+  last_duration_ms: number
+}
+
+/** A task in watch mode: re-run whenever matching files change */
+export interface TaskWatch {
+  name: string
+  /** Globs relative to the worktree (`!` excludes; empty = all files not ignored by .gitignore) */
+  patterns: string[]
+  /** Cancel a still-running run instead of waiting for it */
+  restart: boolean
+  debounce_ms: number
+  args: string[]
+  named_args: Record<string, string>
+  /** Number of change-triggered runs so far */
+  triggers: number
+  /** ISO 8601 */
+  last_triggered_at?: string
+  /** Files whose change caused the last run */
+  last_changed: string[]
+}
+
+/** A task run in this worktree (several may run at once) */
+export interface TaskRun {
+  id: string
+  name: string
+  cwd: string
+  /** Resolved command line */
+  command_line: string
+  status: TaskStatus
+  /** Exit code once finished (absent if killed by a signal) */
+  exit_code?: number
+  output: string[]
+  /** ISO 8601 */
+  started_at: string
+}
+
 export interface TasksState {
   commands: JustCommandInfo[]
   task_statuses: Record<string, TaskStatus>
-  active_command: string | null
-  output: string[]
+  /** Exit code of each task's last run (absent if killed by a signal) */
+  exit_codes?: Record<string, number>
+  /** Recent finished runs in this worktree, newest first (without output) */
+  history?: TaskRunInfo[]
+  /** Reliability and duration stats per task over its recent runs */
+  history_stats?: TaskRunStatsInfo[]
+  /** Past run opened for viewing (with output) */
+  viewed_run?: TaskRunInfo
+  /** Live and recently finished runs, oldest first */
+  runs: TaskRun[]
+  /** Run whose output is shown */
+  selected_run: string | null
+  /** Tasks re-run when their watched files change */
+  watches?: TaskWatch[]
   is_loading: boolean
   error: string | null
   /** Constitution workflow state (CESDD Phase 1) */
@@ -390,6 +634,28 @@ export interface EnvConfig {
   auto_copy_enabled: boolean
   source_worktree: string | null
   last_copy_result: EnvCopyResult | null
+  /** Overrides for variables injected into terminals (null = don't inject) */
+  terminal_env_overrides?: Record<string, string | null>
+  worktree_db: WorktreeDbConfig
+}
+
+/** Databases created with new worktrees */
+export interface WorktreeDbConfig {
+  enabled: boolean
+  /** rstn-postgres or rstn-mysql */
+  service_id: string
+  /** Database new ones are cloned from (empty databases when absent) */
+  template_database?: string
+  /** Variable set in the worktree's .env */
+  env_var: string
+  /** Databases created so far, by worktree path */
+  databases?: Record<string, WorktreeDatabase>
+}
+
+export interface WorktreeDatabase {
+  service_id: string
+  name: string
+  connection_string: string
 }
 
 // ============================================================================
@@ -488,8 +754,80 @@ export interface ChatState {
 // Terminal State
 // ============================================================================
 
+export interface CommandRecord {
+  id: number
+  command: string
+  cwd?: string
+  started_at: string
+  duration_ms?: number
+  exit_code?: number
+  /** First scrollback line of the command's output */
+  output_start: number
+  /** Scrollback line after the last output line (absent while running) */
+  output_end?: number
+}
+
+export interface TerminalSessionInfo {
+  id: string
+  name: string
+  is_running: boolean
+  /** Recent commands reported by shell integration (OSC 133/633) */
+  commands?: CommandRecord[]
+  /** Path of the in-progress asciicast recording */
+  recording_path?: string
+  /** Container the shell runs in (absent for local shells) */
+  container?: ContainerShellTarget
+}
+
+export interface ContainerShellTarget {
+  service_id: string
+  /** Command line, e.g. "psql -U postgres" or "sh" */
+  shell: string
+}
+
+export interface TerminalReplayState {
+  /** Output is streamed under this ID as if it were a session ID */
+  id: string
+  path: string
+  speed: number
+  width: number
+  height: number
+}
+
+export interface ScrollbackMatch {
+  line: number
+  text: string
+  start: number
+  end: number
+}
+
+export interface TerminalSearchState {
+  session_id: string
+  pattern: string
+  matches: ScrollbackMatch[]
+}
+
+export type TerminalEnvSource = 'service' | 'env_file' | 'override'
+
+export interface TerminalEnvVar {
+  key: string
+  value: string
+  source: TerminalEnvSource
+  /** Service ID, env file pattern, or project path */
+  origin: string
+}
+
 export interface TerminalState {
+  /** Active session ID */
   session_id?: string
+  /** Named sessions in display order */
+  sessions: TerminalSessionInfo[]
+  /** Results of the last scrollback search */
+  search?: TerminalSearchState
+  /** Recording currently being replayed */
+  replay?: TerminalReplayState
+  /** Variables injected into shells spawned for this worktree */
+  env?: TerminalEnvVar[]
   cols: number
   rows: number
 }
@@ -498,6 +836,24 @@ export interface TerminalState {
 // Worktree State
 // ============================================================================
 
+export type DiagnosticSeverity = 'error' | 'warning' | 'info'
+
+/** A problem matched in task output */
+export interface Diagnostic {
+  /** Relative to the worktree when inside it */
+  file: string
+  line: number
+  column?: number
+  severity: DiagnosticSeverity
+  message: string
+  /** Matcher: 'rustc' | 'tsc' | 'eslint' | 'pytest' | 'go' */
+  source: string
+  /** Task whose output contained it */
+  task: string
+  /** Run whose output contained it */
+  run_id: string
+}
+
 export interface WorktreeState {
   id: string
   path: string
@@ -512,6 +868,8 @@ export interface WorktreeState {
   tasks: TasksState
   changes: ChangesState
   context: ContextState
+  /** Problems matched in task output (compiler errors, lint, test failures) */
+  diagnostics?: Diagnostic[]
   // NOTE: dockers moved to AppState.docker (global scope)
 }
 
@@ -662,7 +1020,7 @@ export interface AddWorktreeNewBranchAction {
 
 export interface RemoveWorktreeAction {
   type: 'RemoveWorktree'
-  payload: { worktree_path: string }
+  payload: { worktree_path: string; drop_database?: boolean }
 }
 
 export interface FetchBranchesAction {
@@ -1187,11 +1545,35 @@ export interface RefreshDockerServicesAction {
   type: 'RefreshDockerServices'
 }
 
+export interface ReloadServiceTemplatesAction {
+  type: 'ReloadServiceTemplates'
+}
+
 export interface SetDockerServicesAction {
   type: 'SetDockerServices'
   payload: { services: DockerServiceData[] }
 }
 
+export interface SetComposeProjectAction {
+  type: 'SetComposeProject'
+  payload: { project: ComposeProjectData | null }
+}
+
+export interface ComposeUpAction {
+  type: 'ComposeUp'
+  payload: { services?: string[] }
+}
+
+export interface ComposeStopAction {
+  type: 'ComposeStop'
+  payload: { services?: string[] }
+}
+
+export interface ComposeRestartAction {
+  type: 'ComposeRestart'
+  payload: { services?: string[] }
+}
+
 export interface StartDockerServiceAction {
   type: 'StartDockerService'
   payload: { service_id: string }
@@ -1207,6 +1589,36 @@ export interface RestartDockerServiceAction {
   payload: { service_id: string }
 }
 
+export interface ServiceStatsData {
+  cpu_percent: number
+  memory_usage: number
+  memory_limit: number
+  net_rx_bytes: number
+  net_tx_bytes: number
+  block_read_bytes: number
+  block_write_bytes: number
+}
+
+export interface SetDockerServiceStatsAction {
+  type: 'SetDockerServiceStats'
+  payload: { service_id: string; stats: ServiceStatsData | null }
+}
+
+export interface UpdateDockerImageAction {
+  type: 'UpdateDockerImage'
+  payload: { service_id: string }
+}
+
+export interface SetDockerImagePullAction {
+  type: 'SetDockerImagePull'
+  payload: { service_id: string; pull: ImagePull | null }
+}
+
+export interface SetDockerServiceHealthAction {
+  type: 'SetDockerServiceHealth'
+  payload: { service_id: string; health: ServiceHealth | null }
+}
+
 export interface SelectDockerServiceAction {
   type: 'SelectDockerService'
   payload: { service_id: string | null }
@@ -1222,6 +1634,32 @@ export interface SetDockerLogsAction {
   payload: { logs: string[] }
 }
 
+export interface FollowDockerLogsAction {
+  type: 'FollowDockerLogs'
+  payload: {
+    service_id: string
+    stdout: boolean
+    stderr: boolean
+    timestamps?: boolean
+    since?: number
+    filter?: string
+  }
+}
+
+export interface StopFollowingDockerLogsAction {
+  type: 'StopFollowingDockerLogs'
+}
+
+export interface AppendDockerLogLinesAction {
+  type: 'AppendDockerLogLines'
+  payload: { service_id: string; lines: DockerLogLine[] }
+}
+
+export interface DockerLogStreamEndedAction {
+  type: 'DockerLogStreamEnded'
+  payload: { service_id: string }
+}
+
 export interface CreateDatabaseAction {
   type: 'CreateDatabase'
   payload: { service_id: string; db_name: string }
@@ -1232,6 +1670,80 @@ export interface CreateVhostAction {
   payload: { service_id: string; vhost_name: string }
 }
 
+export interface CreateServiceSnapshotAction {
+  type: 'CreateServiceSnapshot'
+  payload: { service_id: string; database?: string }
+}
+
+export interface RestoreServiceSnapshotAction {
+  type: 'RestoreServiceSnapshot'
+  payload: { service_id: string; snapshot_id: string; target_database?: string }
+}
+
+export interface DeleteServiceSnapshotAction {
+  type: 'DeleteServiceSnapshot'
+  payload: { service_id: string; snapshot_id: string }
+}
+
+export interface RefreshServiceSnapshotsAction {
+  type: 'RefreshServiceSnapshots'
+}
+
+export interface SetServiceSnapshotsAction {
+  type: 'SetServiceSnapshots'
+  payload: { snapshots: ServiceSnapshotData[] }
+}
+
+export interface OpenSqlConsoleAction {
+  type: 'OpenSqlConsole'
+  payload: { service_id: string; database?: string }
+}
+
+export interface CloseSqlConsoleAction {
+  type: 'CloseSqlConsole'
+}
+
+export interface SetSqlSchemaAction {
+  type: 'SetSqlSchema'
+  payload: { service_id: string; schema: SqlSchema }
+}
+
+export interface RunSqlQueryAction {
+  type: 'RunSqlQuery'
+  payload: {
+    service_id: string
+    database?: string
+    sql: string
+    limit?: number
+    timeout_ms?: number
+  }
+}
+
+export interface SetSqlQueryResultAction {
+  type: 'SetSqlQueryResult'
+  payload: { service_id: string; result: SqlQueryResult }
+}
+
+export interface SetSqlConsoleErrorAction {
+  type: 'SetSqlConsoleError'
+  payload: { service_id: string; error: string }
+}
+
+export interface LoadConnectionInfoAction {
+  type: 'LoadConnectionInfo'
+  payload: { service_id: string }
+}
+
+export interface ExportConnectionInfoAction {
+  type: 'ExportConnectionInfo'
+  payload: { service_id: string; worktree_path: string }
+}
+
+export interface SetConnectionInfoAction {
+  type: 'SetConnectionInfo'
+  payload: { info: ServiceConnectionInfo | null }
+}
+
 export interface SetDockerConnectionStringAction {
   type: 'SetDockerConnectionString'
   payload: { connection_string: string | null }
@@ -1282,26 +1794,65 @@ export interface SetJustfileCommandsAction {
 
 export interface RunJustCommandAction {
   type: 'RunJustCommand'
-  payload: { name: string; cwd: string }
+  payload: {
+    name: string
+    cwd: string
+    /** Positional arguments (validated against the recipe signature) */
+    args?: string[]
+    /** Values for recipe parameters by name */
+    named_args?: Record<string, string>
+  }
+}
+
+export interface CancelJustCommandAction {
+  type: 'CancelJustCommand'
+  payload: { run_id: string }
 }
 
 export interface SetTaskStatusAction {
   type: 'SetTaskStatus'
-  payload: { name: string; status: TaskStatusData }
+  payload: { run_id: string; status: TaskStatusData }
 }
 
-export interface SetActiveCommandAction {
-  type: 'SetActiveCommand'
-  payload: { name: string | null }
+export interface SelectTaskRunAction {
+  type: 'SelectTaskRun'
+  payload: { run_id: string | null }
 }
 
 export interface AppendTaskOutputAction {
   type: 'AppendTaskOutput'
-  payload: { line: string }
+  payload: { run_id: string; line: string }
 }
 
 export interface ClearTaskOutputAction {
   type: 'ClearTaskOutput'
+  payload: { run_id: string }
+}
+
+export interface DismissTaskRunAction {
+  type: 'DismissTaskRun'
+  payload: { run_id: string }
+}
+
+export interface StartTaskWatchAction {
+  type: 'StartTaskWatch'
+  payload: {
+    name: string
+    cwd: string
+    /** Globs relative to cwd (`!` excludes); empty = all files not ignored by .gitignore */
+    patterns?: string[]
+    /** Cancel a still-running run instead of waiting for it */
+    restart?: boolean
+    /** Quiet period before re-running (default 300ms) */
+    debounce_ms?: number
+    args?: string[]
+    named_args?: Record<string, string>
+  }
+}
+
+export interface StopTaskWatchAction {
+  type: 'StopTaskWatch'
+  payload: { name: string; cwd: string }
 }
 
 export interface SetTasksLoadingAction {
@@ -1314,6 +1865,28 @@ export interface SetTasksErrorAction {
   payload: { error: string | null }
 }
 
+export interface LoadTaskHistoryAction {
+  type: 'LoadTaskHistory'
+}
+
+export interface ViewTaskRunAction {
+  type: 'ViewTaskRun'
+  payload: { id: string }
+}
+
+export interface CloseTaskRunAction {
+  type: 'CloseTaskRun'
+}
+
+export interface RerunTaskAction {
+  type: 'RerunTask'
+  payload: { id: string }
+}
+
+export interface ClearDiagnosticsAction {
+  type: 'ClearDiagnostics'
+}
+
 // Settings Actions
 export interface SetThemeAction {
   type: 'SetTheme'
@@ -1355,6 +1928,31 @@ export interface SetEnvSourceWorktreeAction {
   payload: { worktree_path: string | null }
 }
 
+export interface SetTerminalEnvOverrideAction {
+  type: 'SetTerminalEnvOverride'
+  payload: { key: string; value: string | null }
+}
+
+export interface RemoveTerminalEnvOverrideAction {
+  type: 'RemoveTerminalEnvOverride'
+  payload: { key: string }
+}
+
+export interface SetWorktreeDbConfigAction {
+  type: 'SetWorktreeDbConfig'
+  payload: {
+    enabled: boolean
+    service_id: string
+    template_database?: string | null
+    env_var: string
+  }
+}
+
+export interface SetWorktreeDatabaseAction {
+  type: 'SetWorktreeDatabase'
+  payload: { worktree_path: string; database: WorktreeDatabase | null }
+}
+
 // Agent Rules Actions (Project scope)
 export interface SetAgentRulesEnabledAction {
   type: 'SetAgentRulesEnabled'
@@ -1427,6 +2025,36 @@ export interface SpawnTerminalAction {
   payload: { cols: number; rows: number }
 }
 
+export interface CreateTerminalSessionAction {
+  type: 'CreateTerminalSession'
+  payload: { name: string | null; cols: number; rows: number }
+}
+
+export interface ExecContainerShellAction {
+  type: 'ExecContainerShell'
+  payload: { service_id: string; shell: string | null }
+}
+
+export interface RenameTerminalSessionAction {
+  type: 'RenameTerminalSession'
+  payload: { session_id: string; name: string }
+}
+
+export interface MoveTerminalSessionAction {
+  type: 'MoveTerminalSession'
+  payload: { session_id: string; to_index: number }
+}
+
+export interface SelectTerminalSessionAction {
+  type: 'SelectTerminalSession'
+  payload: { session_id: string }
+}
+
+export interface CloseTerminalSessionAction {
+  type: 'CloseTerminalSession'
+  payload: { session_id: string }
+}
+
 export interface ResizeTerminalAction {
   type: 'ResizeTerminal'
   payload: { session_id: string; cols: number; rows: number }
@@ -1452,6 +2080,49 @@ export interface SetTerminalSizeAction {
   payload: { cols: number; rows: number }
 }
 
+export interface SearchTerminalScrollbackAction {
+  type: 'SearchTerminalScrollback'
+  payload: { session_id: string; pattern: string }
+}
+
+export interface ClearTerminalSearchAction {
+  type: 'ClearTerminalSearch'
+}
+
+export interface ClearTerminalScrollbackAction {
+  type: 'ClearTerminalScrollback'
+  payload: { session_id: string }
+}
+
+export interface ExplainTerminalFailureAction {
+  type: 'ExplainTerminalFailure'
+  payload: { session_id: string | null }
+}
+
+export interface StartTerminalRecordingAction {
+  type: 'StartTerminalRecording'
+  payload: { session_id: string }
+}
+
+export interface StopTerminalRecordingAction {
+  type: 'StopTerminalRecording'
+  payload: { session_id: string }
+}
+
+export interface ReplayTerminalRecordingAction {
+  type: 'ReplayTerminalRecording'
+  payload: { path: string; speed: number }
+}
+
+export interface StopTerminalReplayAction {
+  type: 'StopTerminalReplay'
+  payload: { replay_id: string }
+}
+
+export interface RefreshTerminalEnvAction {
+  type: 'RefreshTerminalEnv'
+}
+
 // Error Actions
 export interface SetErrorAction {
   type: 'SetError'
@@ -1472,6 +2143,30 @@ export interface DockerServiceData {
   service_type: string
   project_group: string | null
   is_rstn_managed: boolean
+  compose_service?: string
+  health?: string
+}
+
+export interface ServiceSnapshotData {
+  id: string
+  service_id: string
+  database: string
+  created_at: string
+  size_bytes: number
+}
+
+export interface ComposeServiceData {
+  name: string
+  image: string | null
+  ports: string[]
+  depends_on: string[]
+  container_name: string
+}
+
+export interface ComposeProjectData {
+  name: string
+  file: string
+  services: ComposeServiceData[]
 }
 
 export interface ConflictingContainerData {
@@ -1487,13 +2182,27 @@ export interface PortConflictData {
   suggested_port: number
 }
 
+export interface JustParameterData {
+  name: string
+  default: string | null
+  kind: JustParameterKind
+  export: boolean
+}
+
 export interface JustCommandData {
   name: string
+  source: TaskSourceKind
   description: string | null
   recipe: string
+  parameters: JustParameterData[]
+  dependencies: string[]
+  group: string | null
+  private: boolean
+  aliases: string[]
+  confirm: boolean
 }
 
-export type TaskStatusData = 'idle' | 'running' | 'success' | 'error'
+export type TaskStatusData = 'idle' | 'running' | 'success' | 'error' | 'cancelled'
 
 export interface WorktreeData {
   path: string
@@ -1849,15 +2558,42 @@ export type Action =
   | CheckDockerAvailabilityAction
   | SetDockerAvailableAction
   | RefreshDockerServicesAction
+  | ReloadServiceTemplatesAction
   | SetDockerServicesAction
+  | SetComposeProjectAction
+  | ComposeUpAction
+  | ComposeStopAction
+  | ComposeRestartAction
   | StartDockerServiceAction
   | StopDockerServiceAction
   | RestartDockerServiceAction
+  | SetDockerServiceHealthAction
+  | SetDockerServiceStatsAction
+  | UpdateDockerImageAction
+  | SetDockerImagePullAction
   | SelectDockerServiceAction
   | FetchDockerLogsAction
   | SetDockerLogsAction
+  | FollowDockerLogsAction
+  | StopFollowingDockerLogsAction
+  | AppendDockerLogLinesAction
+  | DockerLogStreamEndedAction
   | CreateDatabaseAction
   | CreateVhostAction
+  | CreateServiceSnapshotAction
+  | RestoreServiceSnapshotAction
+  | DeleteServiceSnapshotAction
+  | RefreshServiceSnapshotsAction
+  | SetServiceSnapshotsAction
+  | OpenSqlConsoleAction
+  | CloseSqlConsoleAction
+  | SetSqlSchemaAction
+  | RunSqlQueryAction
+  | SetSqlQueryResultAction
+  | SetSqlConsoleErrorAction
+  | LoadConnectionInfoAction
+  | ExportConnectionInfoAction
+  | SetConnectionInfoAction
   | SetDockerConnectionStringAction
   | SetDockerLoadingAction
   | SetDockerLogsLoadingAction
@@ -1869,12 +2605,21 @@ export type Action =
   | RefreshJustfileAction
   | SetJustfileCommandsAction
   | RunJustCommandAction
+  | CancelJustCommandAction
   | SetTaskStatusAction
-  | SetActiveCommandAction
+  | SelectTaskRunAction
   | AppendTaskOutputAction
   | ClearTaskOutputAction
+  | DismissTaskRunAction
+  | StartTaskWatchAction
+  | StopTaskWatchAction
   | SetTasksLoadingAction
   | SetTasksErrorAction
+  | LoadTaskHistoryAction
+  | ViewTaskRunAction
+  | CloseTaskRunAction
+  | RerunTaskAction
+  | ClearDiagnosticsAction
   | SetThemeAction
   | SetProjectPathAction
   | CopyEnvFilesAction
@@ -1882,6 +2627,10 @@ export type Action =
   | SetEnvTrackedPatternsAction
   | SetEnvAutoCopyAction
   | SetEnvSourceWorktreeAction
+  | SetTerminalEnvOverrideAction
+  | RemoveTerminalEnvOverrideAction
+  | SetWorktreeDbConfigAction
+  | SetWorktreeDatabaseAction
   | SetAgentRulesEnabledAction
   | SetAgentRulesPromptAction
   | SetAgentRulesTempFileAction
@@ -1896,11 +2645,26 @@ export type Action =
   | ClearNotificationsAction
   | SetActiveViewAction
   | SpawnTerminalAction
+  | CreateTerminalSessionAction
+  | ExecContainerShellAction
+  | RenameTerminalSessionAction
+  | MoveTerminalSessionAction
+  | SelectTerminalSessionAction
+  | CloseTerminalSessionAction
   | ResizeTerminalAction
   | WriteTerminalAction
   | KillTerminalAction
   | SetTerminalSessionAction
   | SetTerminalSizeAction
+  | SearchTerminalScrollbackAction
+  | ClearTerminalSearchAction
+  | ClearTerminalScrollbackAction
+  | ExplainTerminalFailureAction
+  | StartTerminalRecordingAction
+  | StopTerminalRecordingAction
+  | ReplayTerminalRecordingAction
+  | StopTerminalReplayAction
+  | RefreshTerminalEnvAction
   | SetErrorAction
   | ClearErrorAction
   | AddDevLogAction
@@ -1948,3 +2712,9 @@ export const statusLabels: Record<ServiceStatus, string> = {
   stopping: 'Stopping',
   error: 'Error',
 }
+
+export const healthLabels: Record<ServiceHealth, string> = {
+  starting: 'Not ready',
+  healthy: 'Ready',
+  unhealthy: 'Unhealthy',
+}
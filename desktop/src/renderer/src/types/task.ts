@@ -4,7 +4,7 @@ export interface JustCommand {
   recipe: string
 }
 
-export type TaskStatus = 'idle' | 'running' | 'success' | 'error'
+export type TaskStatus = 'idle' | 'running' | 'success' | 'error' | 'cancelled'
 
 export interface TaskState {
   commands: JustCommand[]
@@ -3,14 +3,37 @@
 
 /* auto-generated by NAPI-RS */
 
+/** A recipe parameter */
+export interface JustParameter {
+  /** Parameter name */
+  name: string
+  /** Default value (string literals unquoted, other expressions verbatim) */
+  default?: string
+  /** "singular", "plus" (`+args`, one or more) or "star" (`*args`, zero or more) */
+  kind: string
+  /** Exported to the recipe as an environment variable (`$name`) */
+  export: boolean
+}
 /** A command parsed from a justfile */
 export interface JustCommand {
-  /** Command name (e.g., "test", "build") */
+  /** Command name (e.g., "test", "build"; "module::recipe" inside modules) */
   name: string
-  /** Description from comment above the command */
+  /** Description from comment above the command (or `[doc]` attribute) */
   description?: string
   /** The recipe/shell commands */
   recipe: string
+  /** Recipe parameters in declaration order */
+  parameters: Array<JustParameter>
+  /** Recipes this one depends on (prior and subsequent) */
+  dependencies: Array<string>
+  /** Group from the `[group]` attribute */
+  group?: string
+  /** Hidden from listings (`[private]` or a leading underscore) */
+  private: boolean
+  /** Aliases pointing at this recipe */
+  aliases: Array<string>
+  /** Requires confirmation before running (`[confirm]`) */
+  confirm: boolean
 }
 /** Service status */
 export const enum ServiceStatus {
@@ -154,6 +177,43 @@ export declare function worktreeListBranches(repoPath: string): Array<NapiBranch
 export declare function envListFiles(dir: string, patterns: Array<string>): Array<string>
 /** Get default env patterns */
 export declare function envDefaultPatterns(): Array<string>
+/**
+ * Register a listener for PTY output.
+ *
+ * The callback receives raw output bytes for every terminal session.
+ * Terminal output is streamed separately from state updates to keep
+ * the state JSON small.
+ */
+export declare function terminalSetOutputListener(callback: (err: Error | null, sessionId: string, data: Buffer) => void): void
+/** Scrollback page for napi export */
+export interface NapiScrollbackPage {
+  /** Line number of the first returned line */
+  start: number
+  lines: Array<string>
+  /** Oldest line number still available */
+  firstLine: number
+  totalLines: number
+}
+/** Scrollback search match for napi export */
+export interface NapiScrollbackMatch {
+  line: number
+  /** Line text with escape sequences removed */
+  text: string
+  /** Byte range of the match within `text` */
+  start: number
+  end: number
+}
+/**
+ * Get a page of a terminal session's scrollback.
+ *
+ * Without `start`, returns the last `count` lines. Raw lines keep escape
+ * sequences so the renderer can replay them; pass `plain` to strip them.
+ */
+export declare function terminalGetScrollback(sessionId: string, start: number | undefined | null, count: number, plain?: boolean | undefined | null): NapiScrollbackPage
+/** Get the last `count` lines of a terminal session as plain text */
+export declare function terminalGetLastLines(sessionId: string, count: number): Array<string>
+/** Regex-search a terminal session's scrollback (most recent `limit` matches) */
+export declare function terminalSearchScrollback(sessionId: string, pattern: string, limit?: number | undefined | null): Array<NapiScrollbackMatch>
 /** Fetch available tools from MCP server */
 export declare function fetchMcpTools(): Promise<string>
 /** AI Context for napi export */
@@ -184,10 +244,12 @@ export interface NapiFileContext {
  * Build AI context for a project path
  *
  * Gathers context from git, files, and other sources within a token budget.
+ * When `task_output` is absent, recent output of `terminal_session_id` is used;
+ * its failed commands are reported as active errors.
  */
-export declare function contextBuild(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null): NapiAiContext
+export declare function contextBuild(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null, terminalSessionId?: string | undefined | null): NapiAiContext
 /** Build AI context and format as a system prompt string */
-export declare function contextBuildSystemPrompt(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null): string
+export declare function contextBuildSystemPrompt(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null, terminalSessionId?: string | undefined | null): string
 /**
  * Initialize the application state and register a listener for state updates.
  *
@@ -3,8 +3,15 @@
 //! All state changes go through dispatch(action) -> reducer -> new state.
 //! Actions are serializable for logging, debugging, and replay.
 
-use crate::app_state::{FeatureTab, Theme};
+use crate::app_state::{
+    DockerLogStream, FeatureTab, ImagePull, ServiceConnectionInfo, ServiceHealth, SqlQueryResult,
+    SqlSchema, Theme,
+};
+use crate::problem_matcher::DiagnosticSeverity;
+use crate::task_source::TaskSourceKind;
+use crate::terminal::TerminalEnvSource;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// All possible actions that can mutate application state.
 ///
@@ -46,8 +53,13 @@ pub enum Action {
     /// Add a worktree with a new branch
     AddWorktreeNewBranch { branch: String },
 
-    /// Remove a worktree (cannot remove main worktree)
-    RemoveWorktree { worktree_path: String },
+    /// Remove a worktree (cannot remove main worktree), optionally dropping
+    /// the database created for it
+    RemoveWorktree {
+        worktree_path: String,
+        #[serde(default)]
+        drop_database: bool,
+    },
 
     /// Fetch all branches for the active project
     FetchBranches,
@@ -400,6 +412,9 @@ pub enum Action {
     /// Refresh the list of Docker services
     RefreshDockerServices,
 
+    /// Re-read service templates (~/.rstn and .rstn/services.yaml)
+    ReloadServiceTemplates,
+
     /// Set the services list (internal, after refresh completes)
     SetDockerServices { services: Vec<DockerServiceData> },
 
@@ -412,6 +427,30 @@ pub enum Action {
     /// Restart a Docker service
     RestartDockerService { service_id: String },
 
+    /// Set a service's readiness (internal, from its readiness probe)
+    SetDockerServiceHealth {
+        service_id: String,
+        health: Option<ServiceHealth>,
+    },
+
+    /// Record a resource usage sample (internal, from the stats monitor;
+    /// `None` clears the service's stats)
+    SetDockerServiceStats {
+        service_id: String,
+        stats: Option<ServiceStatsData>,
+    },
+
+    /// Pull the latest version of a service's image and recreate its
+    /// container on it if the image changed and its data is kept in a
+    /// named volume or bind mount
+    UpdateDockerImage { service_id: String },
+
+    /// Set a service's image pull progress (internal, `None` when done)
+    SetDockerImagePull {
+        service_id: String,
+        pull: Option<ImagePull>,
+    },
+
     /// Select a service to view details/logs
     SelectDockerService { service_id: Option<String> },
 
@@ -421,12 +460,113 @@ pub enum Action {
     /// Set logs (internal, after fetch completes)
     SetDockerLogs { logs: Vec<String> },
 
+    /// Stream a service's logs as they are written, replacing any previous
+    /// follow subscription
+    FollowDockerLogs {
+        service_id: String,
+        stdout: bool,
+        stderr: bool,
+        #[serde(default)]
+        timestamps: bool,
+        /// Unix timestamp (seconds) to stream from
+        #[serde(default)]
+        since: Option<i64>,
+        #[serde(default)]
+        filter: Option<String>,
+    },
+
+    /// Stop the log follow subscription
+    StopFollowingDockerLogs,
+
+    /// Append followed log lines (internal, batched by the follow task)
+    AppendDockerLogLines {
+        service_id: String,
+        lines: Vec<DockerLogLineData>,
+    },
+
+    /// Mark the followed log stream as ended (internal, container stopped)
+    DockerLogStreamEnded { service_id: String },
+
     /// Create a database in a database container
     CreateDatabase { service_id: String, db_name: String },
 
     /// Create a vhost in RabbitMQ
     CreateVhost { service_id: String, vhost_name: String },
 
+    /// Dump a database of a managed service into a new snapshot
+    /// (`database` is required except for Redis)
+    CreateServiceSnapshot {
+        service_id: String,
+        #[serde(default)]
+        database: Option<String>,
+    },
+
+    /// Restore a snapshot into a new database (Redis: replaces its dataset)
+    RestoreServiceSnapshot {
+        service_id: String,
+        snapshot_id: String,
+        #[serde(default)]
+        target_database: Option<String>,
+    },
+
+    /// Delete a snapshot file
+    DeleteServiceSnapshot { service_id: String, snapshot_id: String },
+
+    /// Re-read the snapshots directory
+    RefreshServiceSnapshots,
+
+    /// Set the snapshot list (internal, after a snapshot operation)
+    SetServiceSnapshots { snapshots: Vec<ServiceSnapshotData> },
+
+    /// Open the SQL console of a Postgres or MySQL service and load its
+    /// schema (`database` = None uses the server's default database)
+    OpenSqlConsole {
+        service_id: String,
+        #[serde(default)]
+        database: Option<String>,
+    },
+
+    /// Close the SQL console
+    CloseSqlConsole,
+
+    /// Set the schema loaded for the SQL console (internal)
+    SetSqlSchema { service_id: String, schema: SqlSchema },
+
+    /// Run an ad-hoc query in the SQL console's service
+    /// (defaults: 500 rows, 30 second timeout)
+    RunSqlQuery {
+        service_id: String,
+        #[serde(default)]
+        database: Option<String>,
+        sql: String,
+        #[serde(default)]
+        limit: Option<usize>,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    },
+
+    /// Set the result of a query (internal)
+    SetSqlQueryResult {
+        service_id: String,
+        result: SqlQueryResult,
+    },
+
+    /// Record a failed query or schema load (internal)
+    SetSqlConsoleError { service_id: String, error: String },
+
+    /// Look up how to connect to a service from the host
+    LoadConnectionInfo { service_id: String },
+
+    /// Write a service's connection URL into a worktree's `.env`, keeping
+    /// the file's other lines
+    ExportConnectionInfo {
+        service_id: String,
+        worktree_path: String,
+    },
+
+    /// Set the connection details of a service (internal)
+    SetConnectionInfo { info: Option<ServiceConnectionInfo> },
+
     /// Set the connection string result (internal, after CreateDatabase/CreateVhost)
     SetDockerConnectionString { connection_string: Option<String> },
 
@@ -448,6 +588,29 @@ pub enum Action {
         service_id: String,
     },
 
+    /// Set the active project's compose project (internal, after refresh)
+    SetComposeProject { project: Option<ComposeProjectData> },
+
+    /// Start compose services and their dependencies, in dependency order
+    /// (no services = the whole group)
+    ComposeUp {
+        #[serde(default)]
+        services: Vec<String>,
+    },
+
+    /// Stop compose services and everything depending on them, dependents
+    /// first (no services = the whole group)
+    ComposeStop {
+        #[serde(default)]
+        services: Vec<String>,
+    },
+
+    /// Stop then start compose services (dependents included)
+    ComposeRestart {
+        #[serde(default)]
+        services: Vec<String>,
+    },
+
     /// Set loading state for Docker operations
     SetDockerLoading { is_loading: bool },
 
@@ -457,29 +620,94 @@ pub enum Action {
     // ========================================================================
     // Tasks Actions
     // ========================================================================
-    /// Load justfile commands for the active worktree
+    /// Load tasks (justfile, npm, make, cargo) for the active worktree
     LoadJustfileCommands,
 
-    /// Refresh justfile commands
+    /// Refresh tasks from every task source
     RefreshJustfile,
 
     /// Set commands (internal, after load completes)
     SetJustfileCommands { commands: Vec<JustCommandData> },
 
-    /// Run a just command
-    RunJustCommand { name: String, cwd: String },
+    /// Start a new run of a task (TaskRunStarted reports its run ID)
+    ///
+    /// Several runs may go at once, including of the same task. `name` is the
+    /// task's listed name; its source prefix (`npm:`, `make:`, `cargo:`) picks
+    /// the runner. `args` are positional; `named_args` fill recipe parameters
+    /// by name (make variables for Makefile targets).
+    RunJustCommand {
+        name: String,
+        cwd: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        named_args: HashMap<String, String>,
+    },
+
+    /// A run was started (internal)
+    TaskRunStarted {
+        run_id: String,
+        name: String,
+        cwd: String,
+        command_line: String,
+    },
+
+    /// Cancel a running task (kills its process group)
+    CancelJustCommand { run_id: String },
+
+    /// A run's process exited (internal)
+    JustCommandFinished {
+        run_id: String,
+        exit_code: Option<i32>,
+        cancelled: bool,
+    },
+
+    /// Set a run's status
+    SetTaskStatus { run_id: String, status: TaskStatusData },
+
+    /// Choose the run whose output is shown
+    SelectTaskRun { run_id: Option<String> },
 
-    /// Set task status
-    SetTaskStatus { name: String, status: TaskStatusData },
+    /// Append an output line to a run
+    AppendTaskOutput { run_id: String, line: String },
 
-    /// Set active command
-    SetActiveCommand { name: Option<String> },
+    /// Clear a run's output
+    ClearTaskOutput { run_id: String },
+
+    /// Remove a finished run from the list
+    DismissTaskRun { run_id: String },
+
+    /// Run a task now and again whenever its watched files change
+    ///
+    /// `patterns` are globs relative to `cwd` (`!` excludes); with none, every
+    /// file not ignored by `.gitignore` is watched. With `restart`, a run still
+    /// going is cancelled first; otherwise the rerun waits for it to finish.
+    /// Replaces any existing watch of the same task.
+    StartTaskWatch {
+        name: String,
+        cwd: String,
+        #[serde(default)]
+        patterns: Vec<String>,
+        #[serde(default)]
+        restart: bool,
+        /// Quiet period before re-running (default 300ms)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        debounce_ms: Option<u64>,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        named_args: HashMap<String, String>,
+    },
 
-    /// Append output line
-    AppendTaskOutput { line: String },
+    /// Stop watching a task's files (runs already going are left alone)
+    StopTaskWatch { name: String, cwd: String },
 
-    /// Clear task output
-    ClearTaskOutput,
+    /// Watched files changed and the task is re-run (internal)
+    TaskWatchTriggered {
+        name: String,
+        cwd: String,
+        changed: Vec<String>,
+    },
 
     /// Set tasks loading state
     SetTasksLoading { is_loading: bool },
@@ -487,6 +715,39 @@ pub enum Action {
     /// Set tasks error
     SetTasksError { error: Option<String> },
 
+    /// Load run history and stats for the active worktree (from SQLite)
+    LoadTaskHistory,
+
+    /// Set run history and stats (internal, after load completes)
+    SetTaskHistory {
+        runs: Vec<TaskRunData>,
+        stats: Vec<TaskRunStatsData>,
+    },
+
+    /// Open a past run (loads its captured output)
+    ViewTaskRun { id: String },
+
+    /// Set the opened run (internal, after load completes)
+    SetViewedTaskRun { run: Option<TaskRunData> },
+
+    /// Close the opened run
+    CloseTaskRun,
+
+    /// Run a past run's task again with the same arguments
+    RerunTask { id: String },
+
+    /// Replace the diagnostics matched in a run's output (internal;
+    /// `cwd` is the worktree the run belongs to)
+    SetTaskDiagnostics {
+        run_id: String,
+        name: String,
+        cwd: String,
+        diagnostics: Vec<DiagnosticData>,
+    },
+
+    /// Clear all task diagnostics in the active worktree
+    ClearDiagnostics,
+
     // ========================================================================
     // Env Actions (Project scope)
     // ========================================================================
@@ -510,6 +771,28 @@ pub enum Action {
     /// Set source worktree for env copying
     SetEnvSourceWorktree { worktree_path: Option<String> },
 
+    /// Override a variable injected into terminals (None = don't inject it)
+    SetTerminalEnvOverride { key: String, value: Option<String> },
+
+    /// Remove a terminal env override
+    RemoveTerminalEnvOverride { key: String },
+
+    /// Configure databases created with new worktrees
+    SetWorktreeDbConfig {
+        enabled: bool,
+        service_id: String,
+        /// Database to clone (None = create empty databases)
+        #[serde(default)]
+        template_database: Option<String>,
+        env_var: String,
+    },
+
+    /// Record (or forget) a worktree's database (internal)
+    SetWorktreeDatabase {
+        worktree_path: String,
+        database: Option<WorktreeDatabaseData>,
+    },
+
     // ========================================================================
     // Agent Rules Actions (Project scope)
     // ========================================================================
@@ -568,24 +851,116 @@ pub enum Action {
     // ========================================================================
     // Terminal Actions (worktree scope)
     // ========================================================================
-    /// Spawn a new terminal session
+    /// Start (or restart) the shell for the active terminal session,
+    /// creating a default session if the worktree has none
     SpawnTerminal { cols: u16, rows: u16 },
 
+    /// Create a new named terminal session and start its shell
+    CreateTerminalSession {
+        name: Option<String>,
+        cols: u16,
+        rows: u16,
+    },
+
+    /// Create a terminal session running a command inside a Docker service's
+    /// container (defaults to the service's client, e.g. `psql`) and start it
+    ExecContainerShell {
+        service_id: String,
+        shell: Option<String>,
+    },
+
+    /// Rename a terminal session
+    RenameTerminalSession { session_id: String, name: String },
+
+    /// Move a terminal session to a new position in the tab list
+    MoveTerminalSession { session_id: String, to_index: usize },
+
+    /// Make a terminal session the active one
+    SelectTerminalSession { session_id: String },
+
+    /// Close a terminal session (kills its shell and removes the tab)
+    CloseTerminalSession { session_id: String },
+
     /// Resize an existing terminal session
     ResizeTerminal { session_id: String, cols: u16, rows: u16 },
 
     /// Write data to terminal (user input)
     WriteTerminal { session_id: String, data: String },
 
-    /// Kill a terminal session
+    /// Kill a terminal session's shell (the session tab is kept)
     KillTerminal { session_id: String },
 
-    /// Set terminal session ID (internal, after spawn completes)
+    /// Set active terminal session ID (internal)
     SetTerminalSession { session_id: Option<String> },
 
+    /// Mark a terminal session's shell as running or stopped (internal)
+    SetTerminalSessionRunning { session_id: String, is_running: bool },
+
     /// Set terminal dimensions (internal)
     SetTerminalSize { cols: u16, rows: u16 },
 
+    /// Terminal shell exited on its own (internal, from PTY reader)
+    TerminalExited { session_id: String },
+
+    /// Regex-search a terminal session's scrollback
+    SearchTerminalScrollback { session_id: String, pattern: String },
+
+    /// Set scrollback search results (internal)
+    SetTerminalSearchResults {
+        session_id: String,
+        pattern: String,
+        matches: Vec<TerminalSearchMatchData>,
+    },
+
+    /// Clear scrollback search results
+    ClearTerminalSearch,
+
+    /// Discard a terminal session's scrollback
+    ClearTerminalScrollback { session_id: String },
+
+    /// A command finished in a terminal session (internal, from shell integration)
+    TerminalCommandFinished {
+        session_id: String,
+        command: TerminalCommandData,
+    },
+
+    /// Ask the chat to explain the last failed command of a terminal session
+    /// (active session if None)
+    ExplainTerminalFailure { session_id: Option<String> },
+
+    /// Start recording a terminal session to an asciicast v2 file
+    StartTerminalRecording { session_id: String },
+
+    /// Stop recording a terminal session
+    StopTerminalRecording { session_id: String },
+
+    /// Set the in-progress recording path of a session (internal)
+    SetTerminalRecording {
+        session_id: String,
+        path: Option<String>,
+    },
+
+    /// Replay an asciicast recording through the terminal output stream
+    ReplayTerminalRecording { path: String, speed: f64 },
+
+    /// Stop a running replay
+    StopTerminalReplay { replay_id: String },
+
+    /// Set the active replay (internal, after the recording is loaded)
+    SetTerminalReplay { replay: TerminalReplayData },
+
+    /// A replay finished or was stopped (internal)
+    TerminalReplayFinished { replay_id: String },
+
+    /// Recompute the variables injected into the active worktree's terminals
+    RefreshTerminalEnv,
+
+    /// Set the variables injected into a worktree's terminals (internal)
+    SetTerminalEnv {
+        worktree_path: String,
+        vars: Vec<TerminalEnvVarData>,
+    },
+
     // ========================================================================
     // Settings Actions
     // ========================================================================
@@ -833,6 +1208,47 @@ pub struct WorktreeData {
     pub is_main: bool,
 }
 
+/// Scrollback search match for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalSearchMatchData {
+    pub line: u64,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Terminal command record for actions (from shell integration)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalCommandData {
+    pub id: u64,
+    pub command: String,
+    pub cwd: Option<String>,
+    pub started_at: String,
+    pub duration_ms: Option<u64>,
+    pub exit_code: Option<i32>,
+    pub output_start: u64,
+    pub output_end: Option<u64>,
+}
+
+/// Injected terminal environment variable for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalEnvVarData {
+    pub key: String,
+    pub value: String,
+    pub source: TerminalEnvSource,
+    pub origin: String,
+}
+
+/// Terminal replay data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalReplayData {
+    pub id: String,
+    pub path: String,
+    pub speed: f64,
+    pub width: u16,
+    pub height: u16,
+}
+
 /// Branch data for UI (from `git branch`)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BranchData {
@@ -909,14 +1325,139 @@ pub struct DockerServiceData {
     pub service_type: String,
     pub project_group: Option<String>,
     pub is_rstn_managed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_service: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<String>,
 }
 
-/// Just command data for actions
+/// Container log line data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DockerLogLineData {
+    pub stream: DockerLogStream,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    pub text: String,
+}
+
+/// Resource usage sample for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceStatsData {
+    pub cpu_percent: f64,
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+    pub block_read_bytes: u64,
+    pub block_write_bytes: u64,
+}
+
+/// Service snapshot data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceSnapshotData {
+    pub id: String,
+    pub service_id: String,
+    pub database: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+/// Compose project data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComposeProjectData {
+    pub name: String,
+    pub file: String,
+    pub services: Vec<ComposeServiceData>,
+}
+
+/// Compose service data for actions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComposeServiceData {
+    pub name: String,
+    pub image: Option<String>,
+    #[serde(default)]
+    pub ports: Vec<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    pub container_name: String,
+}
+
+/// Just command data for actions
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct JustCommandData {
     pub name: String,
+    #[serde(default)]
+    pub source: TaskSourceKind,
     pub description: Option<String>,
     pub recipe: String,
+    #[serde(default)]
+    pub parameters: Vec<JustParameterData>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Just recipe parameter data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JustParameterData {
+    pub name: String,
+    pub default: Option<String>,
+    /// "singular", "plus" or "star"
+    pub kind: String,
+    pub export: bool,
+}
+
+/// Recorded task run data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskRunData {
+    pub id: String,
+    pub name: String,
+    pub command_line: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub named_args: HashMap<String, String>,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub status: TaskStatusData,
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub output_truncated: bool,
+}
+
+/// Task run stats data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskRunStatsData {
+    pub name: String,
+    pub runs: usize,
+    pub failures: usize,
+    pub flips: usize,
+    pub flaky: bool,
+    pub avg_duration_ms: u64,
+    pub max_duration_ms: u64,
+    pub last_duration_ms: u64,
+}
+
+/// Diagnostic matched in task output for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DiagnosticData {
+    pub file: String,
+    pub line: u32,
+    #[serde(default)]
+    pub column: Option<u32>,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    pub source: String,
 }
 
 /// Task status for actions
@@ -927,6 +1468,7 @@ pub enum TaskStatusData {
     Running,
     Success,
     Error,
+    Cancelled,
 }
 
 /// Port conflict data for actions
@@ -946,6 +1488,14 @@ pub struct ConflictingContainerData {
     pub is_rstn_managed: bool,
 }
 
+/// Worktree database data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorktreeDatabaseData {
+    pub service_id: String,
+    pub name: String,
+    pub connection_string: String,
+}
+
 /// Env copy result data for actions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EnvCopyResultData {
@@ -1247,6 +1797,8 @@ mod tests {
                 service_type: "Database".to_string(),
                 project_group: Some("rstn".to_string()),
                 is_rstn_managed: true,
+                compose_service: None,
+                health: None,
             }],
         };
         let json = serde_json::to_string_pretty(&action).unwrap();
@@ -6,8 +6,9 @@
 //! - Testing (state round-trip tests)
 //! - Debugging (time-travel, bug reproduction)
 
+use crate::task_source::TaskSourceKind;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use uuid::Uuid;
 
 /// Main application state - single source of truth
@@ -318,6 +319,9 @@ pub struct WorktreeState {
     /// File explorer state
     #[serde(default)]
     pub explorer: FileExplorerState,
+    /// Problems matched in task output (compiler errors, lint, test failures)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<crate::problem_matcher::Diagnostic>,
     // Note: Docker state moved to AppState.docker (global scope)
 }
 
@@ -341,6 +345,7 @@ impl WorktreeState {
                 current_path: path,
                 ..Default::default()
             },
+            diagnostics: Vec::new(),
         }
     }
 }
@@ -611,6 +616,10 @@ pub struct FileExplorerState {
     /// Currently active tab path (the tab being viewed)
     #[serde(default)]
     pub active_tab_path: Option<String>,
+    /// Error/warning counts from task diagnostics (absolute path -> counts,
+    /// directories include everything below them)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub diagnostic_badges: HashMap<String, crate::problem_matcher::DiagnosticBadge>,
 }
 
 impl Default for FileExplorerState {
@@ -630,6 +639,7 @@ impl Default for FileExplorerState {
             error: None,
             tabs: Vec::new(),
             active_tab_path: None,
+            diagnostic_badges: HashMap::new(),
         }
     }
 }
@@ -732,6 +742,53 @@ pub struct EnvConfig {
     /// Result of the last copy operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_copy_result: Option<EnvCopyResult>,
+    /// Per-project overrides for variables injected into terminals
+    /// (`None` suppresses a variable that would otherwise be injected)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub terminal_env_overrides: BTreeMap<String, Option<String>>,
+    /// Isolated databases created with new worktrees
+    #[serde(default)]
+    pub worktree_db: WorktreeDbConfig,
+}
+
+/// Per-worktree database settings
+///
+/// When enabled, adding a worktree creates a database named after its
+/// branch and writes the connection string into the worktree's `.env`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorktreeDbConfig {
+    /// Create a database when a worktree is added
+    pub enabled: bool,
+    /// Service to create databases in (rstn-postgres or rstn-mysql)
+    pub service_id: String,
+    /// Database new ones are cloned from (empty databases when None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_database: Option<String>,
+    /// Variable set to the connection string in the worktree's `.env`
+    pub env_var: String,
+    /// Databases created so far, by worktree path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub databases: BTreeMap<String, WorktreeDatabase>,
+}
+
+impl Default for WorktreeDbConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            service_id: "rstn-postgres".to_string(),
+            template_database: None,
+            env_var: "DATABASE_URL".to_string(),
+            databases: BTreeMap::new(),
+        }
+    }
+}
+
+/// A database created for a worktree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorktreeDatabase {
+    pub service_id: String,
+    pub name: String,
+    pub connection_string: String,
 }
 
 impl Default for EnvConfig {
@@ -746,6 +803,8 @@ impl Default for EnvConfig {
             auto_copy_enabled: true,
             source_worktree: None,
             last_copy_result: None,
+            terminal_env_overrides: BTreeMap::new(),
+            worktree_db: WorktreeDbConfig::default(),
         }
     }
 }
@@ -1214,6 +1273,178 @@ pub struct DockersState {
     /// Result of the last CreateDatabase or CreateVhost operation
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_connection_string: Option<String>,
+    /// Compose project in the active project's root (its services are
+    /// listed in `services` under this project group)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose: Option<ComposeProjectInfo>,
+    /// Snapshots under `~/.rstn/snapshots`, grouped by service, newest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<ServiceSnapshotInfo>,
+    /// Service with a snapshot or restore in progress
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_in_progress: Option<String>,
+    /// Active log follow subscription (cleared when another service is selected)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_follow: Option<DockerLogFollow>,
+    /// Lines streamed by the log follow subscription, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_lines: Vec<DockerLogLine>,
+    /// Open SQL console (Postgres and MySQL services)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sql_console: Option<SqlConsoleState>,
+    /// Connection details of the last service looked up or exported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_info: Option<ServiceConnectionInfo>,
+}
+
+/// Log follow subscription for a service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DockerLogFollow {
+    pub service_id: String,
+    pub stdout: bool,
+    pub stderr: bool,
+    pub timestamps: bool,
+    /// Unix timestamp (seconds) to stream from; `None` = recent tail
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+    /// Case-insensitive text filter applied to each line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// False once the container's log stream has ended
+    pub is_streaming: bool,
+}
+
+/// Output stream a container log line was written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DockerLogStream {
+    Stdout,
+    Stderr,
+}
+
+/// A single streamed container log line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DockerLogLine {
+    pub stream: DockerLogStream,
+    /// RFC 3339 timestamp (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    pub text: String,
+}
+
+/// SQL console of a database service: schema browser plus the last query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SqlConsoleState {
+    pub service_id: String,
+    /// Database queries run against (`None` = the server's default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+    pub schema: SqlSchema,
+    pub is_loading_schema: bool,
+    pub is_running: bool,
+    /// Result of the last successful query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<SqlQueryResult>,
+    /// Error of the last query or schema load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Databases, schemas and tables of a database server
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SqlSchema {
+    pub databases: Vec<String>,
+    /// Schemas of the selected database (Postgres; MySQL has none below
+    /// the database)
+    pub schemas: Vec<String>,
+    pub tables: Vec<SqlTableInfo>,
+}
+
+/// A table and its columns
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SqlTableInfo {
+    pub schema: String,
+    pub name: String,
+    pub columns: Vec<SqlTableColumn>,
+}
+
+/// A table column as declared
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SqlTableColumn {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// A column of a query result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SqlColumn {
+    pub name: String,
+    /// Type reported by the server (None when it could not be described)
+    pub data_type: Option<String>,
+}
+
+/// Typed result of an ad-hoc query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SqlQueryResult {
+    pub columns: Vec<SqlColumn>,
+    /// Values as JSON: numbers, booleans and JSON columns typed, NULL as null,
+    /// everything else (including exact numerics) as strings
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// More rows matched than the row limit
+    pub truncated: bool,
+    pub elapsed_ms: u64,
+}
+
+/// How an app on the host connects to a service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceConnectionInfo {
+    pub service_id: String,
+    pub url: String,
+    pub host: String,
+    /// Host port the container is mapped to
+    pub port: u16,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    /// Variable the URL is conventionally read from (e.g. `DATABASE_URL`)
+    pub env_var: String,
+}
+
+/// A database snapshot of a managed service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceSnapshotInfo {
+    /// Snapshot file name
+    pub id: String,
+    pub service_id: String,
+    /// Database the snapshot was taken of ("redis" for Redis)
+    pub database: String,
+    /// ISO 8601 timestamp
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+/// A docker-compose project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComposeProjectInfo {
+    /// Project name (also the services' project group)
+    pub name: String,
+    /// Compose file path
+    pub file: String,
+    pub services: Vec<ComposeServiceInfo>,
+}
+
+/// A service of a compose project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComposeServiceInfo {
+    pub name: String,
+    /// Image (None for build-only services)
+    pub image: Option<String>,
+    pub ports: Vec<String>,
+    pub depends_on: Vec<String>,
+    /// Container compose creates for the service
+    pub container_name: String,
 }
 
 /// Pending port conflict that requires user resolution
@@ -1262,6 +1493,67 @@ pub struct DockerServiceInfo {
     pub project_group: Option<String>,
     /// Whether this container is managed by rstn (rstn-* prefix)
     pub is_rstn_managed: bool,
+    /// Compose service name (for services of a compose project)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_service: Option<String>,
+    /// Readiness of a running service (`None` until probed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<ServiceHealth>,
+    /// Live resource usage of a running service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<ServiceStats>,
+    /// Image pull in progress for the service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull: Option<ImagePull>,
+}
+
+/// Progress of an image pull, aggregated over its layers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImagePull {
+    pub image: String,
+    /// Fraction done, from 0 to 1
+    pub progress: f64,
+    pub layers_done: u32,
+    pub layers_total: u32,
+    /// What the pull is doing (e.g. "Downloading", "Extracting")
+    pub status: String,
+}
+
+/// Resource usage of a running service, with a short rolling history
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ServiceStats {
+    /// Percentage of one CPU (can exceed 100 on multi-core hosts)
+    pub cpu_percent: f64,
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+    pub block_read_bytes: u64,
+    pub block_write_bytes: u64,
+    /// Recent `cpu_percent` samples, oldest first
+    pub cpu_history: Vec<f64>,
+    /// Recent `memory_usage` samples, oldest first
+    pub memory_history: Vec<u64>,
+}
+
+/// Readiness of a running service, from its readiness probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceHealth {
+    /// Running but not accepting connections yet
+    Starting,
+    Healthy,
+    Unhealthy,
+}
+
+impl ServiceHealth {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceHealth::Starting => "starting",
+            ServiceHealth::Healthy => "healthy",
+            ServiceHealth::Unhealthy => "unhealthy",
+        }
+    }
 }
 
 /// Service status
@@ -1297,14 +1589,31 @@ pub enum ServiceType {
 /// Tasks tab state
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct TasksState {
-    /// Justfile commands
+    /// Tasks from every task source (justfile, npm, make, cargo)
     pub commands: Vec<JustCommandInfo>,
-    /// Status of each task (by name)
+    /// Status of each task's latest run (by name)
     pub task_statuses: HashMap<String, TaskStatus>,
-    /// Currently active/running command
-    pub active_command: Option<String>,
-    /// Output from last command
-    pub output: Vec<String>,
+    /// Exit code of each task's last run (by name; absent if killed by a signal)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub exit_codes: HashMap<String, i32>,
+    /// Recent finished runs in this worktree, newest first (without output)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<TaskRunInfo>,
+    /// Reliability and duration stats per task over its recent runs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history_stats: Vec<TaskRunStatsInfo>,
+    /// Past run opened for viewing (with output)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub viewed_run: Option<TaskRunInfo>,
+    /// Runs of this session, oldest first (all running ones plus recent finished ones)
+    #[serde(default)]
+    pub runs: Vec<TaskRun>,
+    /// Run whose output is shown
+    #[serde(default)]
+    pub selected_run: Option<String>,
+    /// Tasks re-run when their watched files change (one watch per task)
+    #[serde(default)]
+    pub watches: Vec<TaskWatch>,
     /// Loading state
     pub is_loading: bool,
     /// Error message
@@ -1371,12 +1680,146 @@ pub struct ConstitutionWorkflow {
     pub error: Option<String>,
 }
 
-/// Justfile command info
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Task info (a justfile recipe or a task from another task source)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct JustCommandInfo {
+    /// Task name (prefixed with its source outside justfiles, e.g. `npm:build`)
     pub name: String,
+    /// Where the task is defined
+    #[serde(default)]
+    pub source: TaskSourceKind,
     pub description: Option<String>,
     pub recipe: String,
+    /// Recipe parameters in declaration order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<JustParameterInfo>,
+    /// Recipes run before/after this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+    /// Group from the `[group]` attribute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Hidden from listings (`[private]` or leading underscore)
+    #[serde(default)]
+    pub private: bool,
+    /// Aliases pointing at this recipe
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Requires confirmation before running (`[confirm]`)
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Justfile recipe parameter
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JustParameterInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    pub kind: JustParameterKind,
+    /// Exported to the recipe as an environment variable (`$name`)
+    #[serde(default)]
+    pub export: bool,
+}
+
+/// Arity of a recipe parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum JustParameterKind {
+    /// Exactly one argument (or the default)
+    #[default]
+    Singular,
+    /// One or more arguments (`+name`)
+    Plus,
+    /// Zero or more arguments (`*name`)
+    Star,
+}
+
+/// A task in watch mode: re-run whenever matching files change
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskWatch {
+    /// Task name as listed in `TasksState.commands`
+    pub name: String,
+    /// Globs relative to the worktree (`!` excludes; empty = all files not ignored by .gitignore)
+    pub patterns: Vec<String>,
+    /// Cancel a still-running run instead of waiting for it to finish
+    pub restart: bool,
+    /// Quiet period after the last change before re-running
+    pub debounce_ms: u64,
+    pub args: Vec<String>,
+    pub named_args: HashMap<String, String>,
+    /// Number of change-triggered runs so far
+    pub triggers: u32,
+    /// ISO 8601
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_triggered_at: Option<String>,
+    /// Files whose change caused the last run
+    #[serde(default)]
+    pub last_changed: Vec<String>,
+}
+
+/// A task run in this session, with its own output and cancel handle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskRun {
+    /// Run ID (key for output, status and cancellation)
+    pub id: String,
+    /// Task name as listed in `TasksState.commands`
+    pub name: String,
+    /// Directory the task runs in
+    pub cwd: String,
+    /// Resolved command line
+    pub command_line: String,
+    pub status: TaskStatus,
+    /// Exit code once finished (None if killed by a signal)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub output: Vec<String>,
+    /// ISO 8601
+    pub started_at: String,
+}
+
+/// A finished task run recorded in the task_runs table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskRunInfo {
+    pub id: String,
+    /// Task name as listed in `TasksState.commands`
+    pub name: String,
+    /// Resolved command line that was executed
+    pub command_line: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub named_args: HashMap<String, String>,
+    /// ISO 8601
+    pub started_at: String,
+    /// ISO 8601
+    pub finished_at: String,
+    pub duration_ms: u64,
+    /// Exit code (None if killed by a signal)
+    pub exit_code: Option<i32>,
+    pub status: TaskStatus,
+    /// Captured output (only loaded for the viewed run)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Earlier output was dropped to stay under the size cap
+    #[serde(default)]
+    pub output_truncated: bool,
+}
+
+/// Reliability and duration stats for one task (cancelled runs excluded)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskRunStatsInfo {
+    pub name: String,
+    /// Number of recent runs considered
+    pub runs: usize,
+    pub failures: usize,
+    /// Times the outcome changed between consecutive runs
+    pub flips: usize,
+    /// Outcome flipped at least twice recently
+    pub flaky: bool,
+    pub avg_duration_ms: u64,
+    pub max_duration_ms: u64,
+    pub last_duration_ms: u64,
 }
 
 /// Task execution status
@@ -1388,6 +1831,8 @@ pub enum TaskStatus {
     Running,
     Success,
     Error,
+    /// Stopped through CancelJustCommand
+    Cancelled,
 }
 
 // ============================================================================
@@ -1873,6 +2318,7 @@ mod tests {
                 name: "test".to_string(),
                 description: Some("Run tests".to_string()),
                 recipe: "cargo test".to_string(),
+                ..Default::default()
             });
         }
         state.projects.push(project);
@@ -1887,6 +2333,10 @@ mod tests {
             service_type: ServiceType::Database,
             project_group: Some("rstn".to_string()),
             is_rstn_managed: true,
+            compose_service: None,
+            health: None,
+            stats: None,
+            pull: None,
         });
 
         let json = serde_json::to_string_pretty(&state).unwrap();
@@ -0,0 +1,446 @@
+//! docker-compose project support.
+//!
+//! The compose file in a project root is parsed for its services and their
+//! `depends_on` graph. Services are started and stopped through the
+//! `docker compose` CLI one at a time, dependencies first on the way up and
+//! dependents first on the way down.
+
+use crate::service_templates::{parse_yaml, scalar_string};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Compose file names, in the order `docker compose` looks for them
+pub const COMPOSE_FILES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// Container label holding the compose project name
+pub const PROJECT_LABEL: &str = "com.docker.compose.project";
+
+/// Container label holding the compose service name
+pub const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// A compose file and its services
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeProject {
+    /// Project name (`name:` in the file, else the directory name)
+    pub name: String,
+    pub file: PathBuf,
+    /// Services, sorted by name
+    pub services: Vec<ComposeService>,
+}
+
+/// A service defined in a compose file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeService {
+    pub name: String,
+    /// Image (None for services that are only built)
+    pub image: Option<String>,
+    /// Port specs as written (`"8080:80"`, `5432`)
+    pub ports: Vec<String>,
+    pub depends_on: Vec<String>,
+    /// Explicit `container_name`
+    pub container_name: Option<String>,
+}
+
+impl ComposeService {
+    /// Container compose creates for this service (its first replica)
+    pub fn container_name(&self, project: &str) -> String {
+        self.container_name
+            .clone()
+            .unwrap_or_else(|| format!("{}-{}-1", project, self.name))
+    }
+
+    /// Host side of the first published port
+    pub fn host_port(&self) -> Option<u32> {
+        let spec = self.ports.first()?.split('/').next()?;
+        let parts: Vec<&str> = spec.split(':').collect();
+        let host = if parts.len() >= 2 { parts[parts.len() - 2] } else { parts[0] };
+        // Ranges ("8000-8010:8000-8010") report their first port
+        host.split('-').next()?.parse().ok()
+    }
+}
+
+/// First compose file found in `root`
+pub fn find_compose_file(root: &Path) -> Option<PathBuf> {
+    COMPOSE_FILES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Parse the compose file in `root`, if there is one
+pub fn load_compose_project(root: &Path) -> Result<Option<ComposeProject>, String> {
+    let Some(file) = find_compose_file(root) else {
+        return Ok(None);
+    };
+    let text = std::fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let dir_name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    parse_compose(&text, file.clone(), &dir_name)
+        .map(Some)
+        .map_err(|e| format!("{}: {}", file.display(), e))
+}
+
+/// Parse compose file contents. `dir_name` names the project when the file
+/// has no top-level `name:`.
+pub fn parse_compose(text: &str, file: PathBuf, dir_name: &str) -> Result<ComposeProject, String> {
+    let doc = parse_yaml(text)?;
+    let name = doc
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or(dir_name);
+    let name = normalize_project_name(name);
+    if name.is_empty() {
+        return Err("project name is empty".to_string());
+    }
+
+    let services = match doc.get("services") {
+        Some(Value::Object(services)) => services,
+        Some(Value::Null) | None => return Err("no services defined".to_string()),
+        Some(_) => return Err("`services` must be a mapping".to_string()),
+    };
+    let services = services
+        .iter()
+        .map(|(service, spec)| parse_service(service, spec).map_err(|e| format!("service {}: {}", service, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let project = ComposeProject { name, file, services };
+    // Reject unknown dependencies and cycles up front
+    project.start_order(&[])?;
+    Ok(project)
+}
+
+fn parse_service(name: &str, spec: &Value) -> Result<ComposeService, String> {
+    let spec = match spec {
+        Value::Object(spec) => spec,
+        Value::Null => return Ok(ComposeService {
+            name: name.to_string(),
+            image: None,
+            ports: Vec::new(),
+            depends_on: Vec::new(),
+            container_name: None,
+        }),
+        _ => return Err("expected a mapping".to_string()),
+    };
+
+    let ports = match spec.get("ports") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(ports)) => ports
+            .iter()
+            .filter_map(|p| match p {
+                // Long syntax: {target: 80, published: 8080}
+                Value::Object(long) => {
+                    let target = long.get("target").and_then(scalar_string)?;
+                    Some(match long.get("published").and_then(scalar_string) {
+                        Some(published) => format!("{}:{}", published, target),
+                        None => target,
+                    })
+                }
+                other => scalar_string(other),
+            })
+            .collect(),
+        Some(_) => return Err("`ports` must be a list".to_string()),
+    };
+
+    let depends_on = match spec.get("depends_on") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(deps)) => deps
+            .iter()
+            .map(|d| d.as_str().map(str::to_string).ok_or("`depends_on` entries must be strings"))
+            .collect::<Result<_, _>>()?,
+        // Long syntax: {db: {condition: service_healthy}}
+        Some(Value::Object(deps)) => deps.keys().cloned().collect(),
+        Some(_) => return Err("`depends_on` must be a list or a mapping".to_string()),
+    };
+
+    Ok(ComposeService {
+        name: name.to_string(),
+        image: spec.get("image").and_then(Value::as_str).map(str::to_string),
+        ports,
+        depends_on,
+        container_name: spec
+            .get("container_name")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// Compose's project name rules: lowercase letters, digits, `-` and `_`,
+/// starting with a letter or digit
+pub fn normalize_project_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect::<String>()
+        .trim_start_matches(['-', '_'])
+        .to_string()
+}
+
+impl ComposeProject {
+    pub fn service(&self, name: &str) -> Option<&ComposeService> {
+        self.services.iter().find(|s| s.name == name)
+    }
+
+    /// `targets` plus everything they depend on, dependencies first.
+    /// No targets means every service.
+    pub fn start_order(&self, targets: &[String]) -> Result<Vec<String>, String> {
+        let mut order = Vec::new();
+        let mut done = HashSet::new();
+        let mut visiting = Vec::new();
+        for target in self.targets(targets)? {
+            self.visit(&target, &mut visiting, &mut done, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    /// `targets` plus everything depending on them, dependents first.
+    /// No targets means every service.
+    pub fn stop_order(&self, targets: &[String]) -> Result<Vec<String>, String> {
+        let mut affected: HashSet<String> = self.targets(targets)?.into_iter().collect();
+        loop {
+            let before = affected.len();
+            for service in &self.services {
+                if service.depends_on.iter().any(|d| affected.contains(d)) {
+                    affected.insert(service.name.clone());
+                }
+            }
+            if affected.len() == before {
+                break;
+            }
+        }
+        let mut order: Vec<String> = self
+            .start_order(&[])?
+            .into_iter()
+            .filter(|s| affected.contains(s))
+            .collect();
+        order.reverse();
+        Ok(order)
+    }
+
+    fn targets(&self, targets: &[String]) -> Result<Vec<String>, String> {
+        if targets.is_empty() {
+            return Ok(self.services.iter().map(|s| s.name.clone()).collect());
+        }
+        for target in targets {
+            if self.service(target).is_none() {
+                return Err(format!("Unknown compose service: {}", target));
+            }
+        }
+        Ok(targets.to_vec())
+    }
+
+    fn visit(
+        &self,
+        name: &str,
+        visiting: &mut Vec<String>,
+        done: &mut HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), String> {
+        if done.contains(name) {
+            return Ok(());
+        }
+        if let Some(start) = visiting.iter().position(|v| v == name) {
+            let mut cycle = visiting[start..].to_vec();
+            cycle.push(name.to_string());
+            return Err(format!("depends_on cycle: {}", cycle.join(" -> ")));
+        }
+        let service = self
+            .service(name)
+            .ok_or_else(|| format!("{} depends on unknown service {}", visiting.last().map(String::as_str).unwrap_or("?"), name))?;
+        visiting.push(name.to_string());
+        for dep in &service.depends_on {
+            self.visit(dep, visiting, done, order)?;
+        }
+        visiting.pop();
+        done.insert(name.to_string());
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    /// Run `docker compose <args>` for this project
+    pub async fn run(&self, args: &[&str]) -> Result<(), String> {
+        let mut cmd = tokio::process::Command::new("docker");
+        cmd.arg("compose")
+            .arg("-f")
+            .arg(&self.file)
+            .arg("-p")
+            .arg(&self.name)
+            .args(args);
+        if let Some(dir) = self.file.parent() {
+            cmd.current_dir(dir);
+        }
+        let output = cmd
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run docker compose: {}", e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(format!("docker compose {} failed: {}", args.join(" "), stderr.trim()))
+        }
+    }
+
+    /// Start `targets` and their dependencies in order
+    pub async fn up(&self, targets: &[String]) -> Result<(), String> {
+        for service in self.start_order(targets)? {
+            self.run(&["up", "-d", "--no-deps", &service]).await?;
+        }
+        Ok(())
+    }
+
+    /// Stop `targets` and their dependents in order
+    pub async fn stop(&self, targets: &[String]) -> Result<(), String> {
+        for service in self.stop_order(targets)? {
+            self.run(&["stop", &service]).await?;
+        }
+        Ok(())
+    }
+
+    /// Stop `targets` (and dependents), then bring the same services back up
+    pub async fn restart(&self, targets: &[String]) -> Result<(), String> {
+        let stopped = self.stop_order(targets)?;
+        self.stop(targets).await?;
+        self.up(&stopped).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPOSE: &str = r#"
+name: My_Shop
+services:
+  web:
+    build: .
+    ports:
+      - "8080:80"
+    depends_on:
+      api:
+        condition: service_started
+  api:
+    image: shop/api
+    depends_on: [db, cache]
+    ports:
+      - target: 3000
+        published: 3001
+  db:
+    image: postgres:16
+    container_name: shop-db
+    ports: ["127.0.0.1:5433:5432"]
+  cache:
+    image: redis:7
+  worker:
+    image: shop/worker
+    depends_on: [db]
+"#;
+
+    fn project() -> ComposeProject {
+        parse_compose(COMPOSE, PathBuf::from("/shop/compose.yaml"), "shop").unwrap()
+    }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_compose() {
+        let project = project();
+        assert_eq!(project.name, "my_shop");
+        assert_eq!(project.services.len(), 5);
+
+        let web = project.service("web").unwrap();
+        assert_eq!(web.image, None);
+        assert_eq!(web.depends_on, vec!["api"]);
+        assert_eq!(web.host_port(), Some(8080));
+        assert_eq!(web.container_name("my_shop"), "my_shop-web-1");
+
+        let api = project.service("api").unwrap();
+        assert_eq!(api.ports, vec!["3001:3000"]);
+        assert_eq!(api.host_port(), Some(3001));
+
+        let db = project.service("db").unwrap();
+        assert_eq!(db.container_name("my_shop"), "shop-db");
+        assert_eq!(db.host_port(), Some(5433));
+        assert_eq!(project.service("cache").unwrap().host_port(), None);
+    }
+
+    #[test]
+    fn test_start_and_stop_order() {
+        let project = project();
+        assert_eq!(project.start_order(&names(&["web"])).unwrap(), names(&["db", "cache", "api", "web"]));
+        assert_eq!(project.start_order(&names(&["worker"])).unwrap(), names(&["db", "worker"]));
+
+        let all = project.start_order(&[]).unwrap();
+        assert_eq!(all.len(), 5);
+        let pos = |s: &str| all.iter().position(|x| x == s).unwrap();
+        assert!(pos("db") < pos("api") && pos("api") < pos("web") && pos("db") < pos("worker"));
+
+        // Stopping the database takes down everything depending on it first
+        let stop = project.stop_order(&names(&["db"])).unwrap();
+        assert_eq!(stop.last().unwrap(), "db");
+        assert_eq!(stop.len(), 4);
+        assert!(stop.iter().position(|s| s == "web") < stop.iter().position(|s| s == "api"));
+        assert_eq!(project.stop_order(&names(&["web"])).unwrap(), names(&["web"]));
+
+        assert!(project.start_order(&names(&["nope"])).is_err());
+    }
+
+    #[test]
+    fn test_invalid_compose() {
+        let file = PathBuf::from("/x/compose.yaml");
+        let cycle = "services:\n  a:\n    depends_on: [b]\n  b:\n    depends_on: [a]\n";
+        assert!(parse_compose(cycle, file.clone(), "x").unwrap_err().contains("cycle"));
+        let unknown = "services:\n  a:\n    depends_on: [ghost]\n";
+        assert!(parse_compose(unknown, file.clone(), "x").unwrap_err().contains("ghost"));
+        assert!(parse_compose("version: '3'\n", file, "x").unwrap_err().contains("no services"));
+    }
+
+    #[test]
+    fn test_anchors_and_merge_keys() {
+        let text = r#"
+x-common: &defaults
+  image: shop/app
+  depends_on: [db]
+services:
+  db:
+    image: postgres
+  api:
+    <<: *defaults
+    ports: ["3000:3000"]
+  worker:
+    <<: *defaults
+    image: shop/worker
+"#;
+        let project = parse_compose(text, PathBuf::from("/shop/compose.yaml"), "shop").unwrap();
+        let api = project.service("api").unwrap();
+        assert_eq!(api.image.as_deref(), Some("shop/app"));
+        assert_eq!(api.depends_on, vec!["db"]);
+        assert_eq!(api.host_port(), Some(3000));
+        // Keys in the mapping win over merged ones
+        let worker = project.service("worker").unwrap();
+        assert_eq!(worker.image.as_deref(), Some("shop/worker"));
+        assert_eq!(worker.depends_on, vec!["db"]);
+    }
+
+    #[test]
+    fn test_load_compose_project() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_compose_project(dir.path()).unwrap(), None);
+
+        std::fs::write(dir.path().join("docker-compose.yml"), "services:\n  db:\n    image: postgres\n").unwrap();
+        let project = load_compose_project(dir.path()).unwrap().unwrap();
+        assert_eq!(project.name, normalize_project_name(&dir.path().file_name().unwrap().to_string_lossy()));
+        assert_eq!(project.services[0].name, "db");
+        assert_eq!(normalize_project_name("-My App.v2"), "myappv2");
+    }
+}
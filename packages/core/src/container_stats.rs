@@ -0,0 +1,105 @@
+//! Resource statistics of running containers.
+//!
+//! Samples come from Docker's stats stream (about one per second) and are
+//! reduced to the figures `docker stats` shows: CPU %, memory usage/limit,
+//! network I/O and block I/O.
+
+use bollard::container::{MemoryStatsStats, Stats};
+use std::time::Duration;
+
+/// Minimum time between samples pushed into state, per container
+pub const STATS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// One reading of a container's resource usage
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatsSample {
+    /// Percentage of one CPU (can exceed 100 on multi-core hosts)
+    pub cpu_percent: f64,
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+    pub block_read_bytes: u64,
+    pub block_write_bytes: u64,
+}
+
+/// CPU usage since the previous sample, as `docker stats` computes it
+pub fn cpu_percent(cpu_delta: u64, system_delta: u64, online_cpus: u64) -> f64 {
+    if cpu_delta == 0 || system_delta == 0 {
+        return 0.0;
+    }
+    cpu_delta as f64 / system_delta as f64 * online_cpus.max(1) as f64 * 100.0
+}
+
+/// Memory in use excluding the reclaimable page cache, like `docker stats`
+pub fn memory_usage(usage: u64, inactive_file: u64) -> u64 {
+    usage.saturating_sub(inactive_file)
+}
+
+/// Reduce a raw stats reading to a sample
+pub fn sample(stats: &Stats) -> StatsSample {
+    let cpu = &stats.cpu_stats;
+    let precpu = &stats.precpu_stats;
+    let online_cpus = cpu
+        .online_cpus
+        .or_else(|| cpu.cpu_usage.percpu_usage.as_ref().map(|p| p.len() as u64))
+        .unwrap_or(1);
+    let cpu_delta = cpu.cpu_usage.total_usage.saturating_sub(precpu.cpu_usage.total_usage);
+    let system_delta = cpu
+        .system_cpu_usage
+        .unwrap_or(0)
+        .saturating_sub(precpu.system_cpu_usage.unwrap_or(0));
+
+    let inactive_file = match stats.memory_stats.stats {
+        Some(MemoryStatsStats::V1(v1)) => v1.total_inactive_file,
+        Some(MemoryStatsStats::V2(v2)) => v2.inactive_file,
+        None => 0,
+    };
+
+    let (net_rx_bytes, net_tx_bytes) = stats
+        .networks
+        .iter()
+        .flat_map(|networks| networks.values())
+        .fold((0, 0), |(rx, tx), n| (rx + n.rx_bytes, tx + n.tx_bytes));
+
+    let mut block_read_bytes = 0;
+    let mut block_write_bytes = 0;
+    for entry in stats.blkio_stats.io_service_bytes_recursive.iter().flatten() {
+        if entry.op.eq_ignore_ascii_case("read") {
+            block_read_bytes += entry.value;
+        } else if entry.op.eq_ignore_ascii_case("write") {
+            block_write_bytes += entry.value;
+        }
+    }
+
+    StatsSample {
+        cpu_percent: cpu_percent(cpu_delta, system_delta, online_cpus),
+        memory_usage: memory_usage(stats.memory_stats.usage.unwrap_or(0), inactive_file),
+        memory_limit: stats.memory_stats.limit.unwrap_or(0),
+        net_rx_bytes,
+        net_tx_bytes,
+        block_read_bytes,
+        block_write_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_percent() {
+        // Half of the host's CPU time on a 4-core host = two full cores
+        assert_eq!(cpu_percent(500, 1000, 4), 200.0);
+        assert_eq!(cpu_percent(100, 1000, 1), 10.0);
+        // First sample has no previous reading to compare against
+        assert_eq!(cpu_percent(0, 1000, 4), 0.0);
+        assert_eq!(cpu_percent(100, 0, 4), 0.0);
+    }
+
+    #[test]
+    fn test_memory_usage_excludes_page_cache() {
+        assert_eq!(memory_usage(300 << 20, 100 << 20), 200 << 20);
+        assert_eq!(memory_usage(10, 20), 0);
+    }
+}
@@ -3,6 +3,7 @@
 //! Automatically gathers, ranks, and formats the most relevant information
 //! from the project state to send to the LLM.
 
+use crate::problem_matcher::{Diagnostic, DiagnosticSeverity};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
@@ -308,6 +309,89 @@ impl ContextGatherer for DockerGatherer {
     }
 }
 
+// ============================================================================
+// Command Failure Gatherer
+// ============================================================================
+
+/// Gatherer for failed terminal commands (from shell integration).
+pub struct CommandFailureGatherer {
+    /// Formatted failures (command, exit code, output tail).
+    pub failures: Vec<String>,
+}
+
+impl ContextGatherer for CommandFailureGatherer {
+    fn name(&self) -> &'static str {
+        "command_failures"
+    }
+
+    fn gather(&self, _project_path: &Path) -> GatheredContext {
+        if self.failures.is_empty() {
+            return GatheredContext::default();
+        }
+
+        let tokens = self.failures.iter().map(|s| s.len()).sum::<usize>() / 4;
+
+        GatheredContext {
+            priority: 8, // Commands the user just ran: highest error priority
+            tokens,
+            content: ContextContent::Errors(self.failures.clone()),
+        }
+    }
+}
+
+// ============================================================================
+// Diagnostic Gatherer
+// ============================================================================
+
+/// Gatherer for problems matched in task output (compiler, lint, tests).
+pub struct DiagnosticGatherer {
+    /// Diagnostics of the worktree.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Maximum diagnostics reported (errors first).
+    pub max_items: usize,
+}
+
+impl Default for DiagnosticGatherer {
+    fn default() -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            max_items: 50,
+        }
+    }
+}
+
+impl ContextGatherer for DiagnosticGatherer {
+    fn name(&self) -> &'static str {
+        "diagnostics"
+    }
+
+    fn gather(&self, _project_path: &Path) -> GatheredContext {
+        let mut diagnostics: Vec<&Diagnostic> = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity != DiagnosticSeverity::Info)
+            .collect();
+        if diagnostics.is_empty() {
+            return GatheredContext::default();
+        }
+        // Stable: errors first, otherwise in output order
+        diagnostics.sort_by_key(|d| d.severity != DiagnosticSeverity::Error);
+
+        let errors: Vec<String> = diagnostics
+            .into_iter()
+            .take(self.max_items)
+            .map(Diagnostic::to_context_line)
+            .collect();
+        let tokens = errors.iter().map(|s| s.len()).sum::<usize>() / 4;
+
+        GatheredContext {
+            priority: 8, // Exact locations of what's broken
+            tokens,
+            content: ContextContent::Errors(errors),
+        }
+    }
+}
+
 // ============================================================================
 // Directory Tree Gatherer
 // ============================================================================
@@ -439,9 +523,14 @@ impl ContextGatherer for TerminalGatherer {
         let output = match &self.last_output {
             Some(o) if !o.is_empty() => {
                 if o.len() > self.max_size {
+                    // Terminal output is arbitrary UTF-8; don't cut inside a character
+                    let mut start = o.len() - self.max_size;
+                    while !o.is_char_boundary(start) {
+                        start += 1;
+                    }
                     format!(
                         "...{}\n(showing last {} chars)",
-                        &o[o.len() - self.max_size..],
+                        &o[start..],
                         self.max_size
                     )
                 } else {
@@ -563,6 +652,8 @@ pub fn build_context(
     active_files: Vec<String>,
     task_output: Option<String>,
     docker_errors: Vec<String>,
+    command_failures: Vec<String>,
+    diagnostics: Vec<Diagnostic>,
     token_budget: usize,
 ) -> AIContext {
     let mut engine = ContextEngine::new(token_budget);
@@ -593,6 +684,21 @@ pub fn build_context(
         }));
     }
 
+    // Add failed terminal commands (shell integration)
+    if !command_failures.is_empty() {
+        engine.add_gatherer(Box::new(CommandFailureGatherer {
+            failures: command_failures,
+        }));
+    }
+
+    // Add problems matched in task output
+    if !diagnostics.is_empty() {
+        engine.add_gatherer(Box::new(DiagnosticGatherer {
+            diagnostics,
+            ..Default::default()
+        }));
+    }
+
     // Add directory gatherer (low priority, will be cut if over budget)
     engine.add_gatherer(Box::new(DirectoryGatherer::default()));
 
@@ -785,11 +891,49 @@ mod tests {
             vec![file_path.to_string_lossy().to_string()],
             Some("test passed".to_string()),
             vec!["docker error".to_string()],
+            vec!["Command `cargo test` exited with code 101".to_string()],
+            Vec::new(),
             10000,
         );
 
         assert!(!context.open_files.is_empty());
         assert!(context.terminal_last_output.is_some());
-        assert!(!context.active_errors.is_empty());
+        assert_eq!(context.active_errors.len(), 2);
+    }
+
+    #[test]
+    fn test_diagnostic_gatherer() {
+        let diagnostic = |file: &str, severity| Diagnostic {
+            file: file.to_string(),
+            line: 3,
+            column: Some(7),
+            severity,
+            message: "boom".to_string(),
+            source: "rustc".to_string(),
+            task: "build".to_string(),
+            run_id: "r1".to_string(),
+        };
+        let gatherer = DiagnosticGatherer {
+            diagnostics: vec![
+                diagnostic("src/warn.rs", DiagnosticSeverity::Warning),
+                diagnostic("src/note.rs", DiagnosticSeverity::Info),
+                diagnostic("src/err.rs", DiagnosticSeverity::Error),
+            ],
+            max_items: 10,
+        };
+
+        let result = gatherer.gather(Path::new("."));
+        assert_eq!(result.priority, 8);
+        if let ContextContent::Errors(errors) = result.content {
+            assert_eq!(
+                errors,
+                vec!["src/err.rs:3:7: error: boom", "src/warn.rs:3:7: warning: boom"]
+            );
+        } else {
+            panic!("Expected Errors content");
+        }
+
+        let empty = DiagnosticGatherer::default().gather(Path::new("."));
+        assert!(matches!(empty.content, ContextContent::Empty));
     }
 }
@@ -1,12 +1,24 @@
 //! SQLite Database Management
 //!
-//! Handles user-scoped persistence for structured data like comments and logs.
+//! Handles user-scoped persistence for structured data like comments, logs
+//! and task run history.
 //! Database is stored at ~/.rstn/state.db with project_id column for data isolation.
 
-use rusqlite::{params, Connection, Result};
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Mutex;
 use uuid::Uuid;
 
+/// Maximum bytes of output kept per task run (the tail is kept)
+pub const TASK_RUN_OUTPUT_LIMIT: usize = 256 * 1024;
+
+/// Number of most recent finished runs per task used for stats
+pub const TASK_RUN_STATS_WINDOW: usize = 20;
+
+/// Number of most recent runs kept per task and worktree (older ones are
+/// deleted as new ones are recorded)
+pub const TASK_RUN_RETENTION: usize = 100;
+
 /// Database manager - single global instance for all projects
 pub struct DbManager {
     conn: Mutex<Connection>,
@@ -31,9 +43,13 @@ impl DbManager {
         // Enable WAL mode for better concurrency
         conn.execute("PRAGMA journal_mode=WAL;", [])?;
 
+        Self::with_connection(conn)
+    }
+
+    /// Wrap an open connection and run migrations on it
+    fn with_connection(conn: Connection) -> Result<Self> {
         let manager = Self { conn: Mutex::new(conn) };
         manager.run_migrations()?;
-
         Ok(manager)
     }
 
@@ -112,6 +128,33 @@ impl DbManager {
             [],
         )?;
 
+        // Table: Task Runs (one row per finished task, per project and worktree)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS task_runs (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                worktree_path TEXT NOT NULL,
+                task_name TEXT NOT NULL,
+                command_line TEXT NOT NULL,
+                args_json TEXT NOT NULL,
+                named_args_json TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                exit_code INTEGER,
+                status TEXT NOT NULL,
+                output TEXT NOT NULL,
+                output_truncated INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )?;
+
+        // Index for task runs by worktree, newest first
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_task_runs_worktree ON task_runs(project_id, worktree_path, started_at)",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -240,6 +283,226 @@ impl DbManager {
         }
         Ok(result)
     }
+
+    // ========================================================================
+    // Task Runs (all queries require project_id)
+    // ========================================================================
+
+    /// Record a finished task run. Output beyond the limit is cut from the front,
+    /// and runs of the task beyond [`TASK_RUN_RETENTION`] are deleted.
+    pub fn add_task_run(&self, run: &TaskRunRecord) -> Result<()> {
+        let (output, cut) = cap_output(&run.output, TASK_RUN_OUTPUT_LIMIT);
+        let args_json = serde_json::to_string(&run.args).unwrap_or_else(|_| "[]".to_string());
+        let named_args_json =
+            serde_json::to_string(&run.named_args).unwrap_or_else(|_| "{}".to_string());
+        let duration_ms = (run.finished_at - run.started_at).num_milliseconds().max(0);
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "INSERT INTO task_runs (id, project_id, worktree_path, task_name, command_line, args_json,
+                named_args_json, started_at, finished_at, duration_ms, exit_code, status, output, output_truncated)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                run.id,
+                run.project_id,
+                run.worktree_path,
+                run.task_name,
+                run.command_line,
+                args_json,
+                named_args_json,
+                run.started_at.to_rfc3339(),
+                run.finished_at.to_rfc3339(),
+                duration_ms,
+                run.exit_code,
+                run.status,
+                output,
+                run.output_truncated || cut,
+            ],
+        )?;
+        tx.execute(
+            "DELETE FROM task_runs WHERE project_id = ?1 AND worktree_path = ?2 AND task_name = ?3
+             AND id NOT IN (
+                SELECT id FROM task_runs WHERE project_id = ?1 AND worktree_path = ?2 AND task_name = ?3
+                ORDER BY started_at DESC LIMIT ?4
+             )",
+            params![run.project_id, run.worktree_path, run.task_name, TASK_RUN_RETENTION],
+        )?;
+
+        tx.commit()
+    }
+
+    /// Most recent runs in a worktree, newest first (without output)
+    pub fn get_task_runs(
+        &self,
+        project_id: &str,
+        worktree_path: &str,
+        limit: usize,
+    ) -> Result<Vec<TaskRunRow>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, task_name, command_line, args_json, named_args_json, started_at, finished_at,
+                    duration_ms, exit_code, status, NULL, output_truncated
+             FROM task_runs WHERE project_id = ?1 AND worktree_path = ?2
+             ORDER BY started_at DESC LIMIT ?3",
+        )?;
+
+        let rows = stmt.query_map(params![project_id, worktree_path, limit], task_run_from_row)?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    /// A single run including its captured output
+    pub fn get_task_run(&self, project_id: &str, id: &str) -> Result<Option<TaskRunRow>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT id, task_name, command_line, args_json, named_args_json, started_at, finished_at,
+                    duration_ms, exit_code, status, output, output_truncated
+             FROM task_runs WHERE project_id = ?1 AND id = ?2",
+            params![project_id, id],
+            task_run_from_row,
+        )
+        .optional()
+    }
+
+    /// Per-task reliability and duration stats over each task's recent runs.
+    ///
+    /// Cancelled runs are ignored. Sorted slowest first (by average duration).
+    pub fn get_task_run_stats(
+        &self,
+        project_id: &str,
+        worktree_path: &str,
+    ) -> Result<Vec<TaskRunStatsRow>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT task_name, status, duration_ms FROM task_runs
+             WHERE project_id = ?1 AND worktree_path = ?2 AND status != 'cancelled'
+             ORDER BY started_at DESC",
+        )?;
+
+        let rows = stmt.query_map(params![project_id, worktree_path], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+
+        // Newest first, capped per task
+        let mut runs: HashMap<String, Vec<(bool, i64)>> = HashMap::new();
+        for row in rows {
+            let (task_name, status, duration_ms) = row?;
+            let task_runs = runs.entry(task_name).or_default();
+            if task_runs.len() < TASK_RUN_STATS_WINDOW {
+                task_runs.push((status == "success", duration_ms));
+            }
+        }
+
+        let mut stats: Vec<TaskRunStatsRow> = runs
+            .into_iter()
+            .map(|(task_name, runs)| TaskRunStatsRow::from_runs(task_name, &runs))
+            .collect();
+        stats.sort_by(|a, b| {
+            b.avg_duration_ms
+                .cmp(&a.avg_duration_ms)
+                .then_with(|| a.task_name.cmp(&b.task_name))
+        });
+        Ok(stats)
+    }
+}
+
+fn task_run_from_row(row: &rusqlite::Row) -> Result<TaskRunRow> {
+    let args_json: String = row.get(3)?;
+    let named_args_json: String = row.get(4)?;
+    Ok(TaskRunRow {
+        id: row.get(0)?,
+        task_name: row.get(1)?,
+        command_line: row.get(2)?,
+        args: serde_json::from_str(&args_json).unwrap_or_default(),
+        named_args: serde_json::from_str(&named_args_json).unwrap_or_default(),
+        started_at: row.get(5)?,
+        finished_at: row.get(6)?,
+        duration_ms: row.get::<_, i64>(7)?.max(0) as u64,
+        exit_code: row.get(8)?,
+        status: row.get(9)?,
+        output: row.get(10)?,
+        output_truncated: row.get(11)?,
+    })
+}
+
+/// Keep at most `limit` bytes from the end of `output`, cut at a line break.
+///
+/// Returns the kept text and whether anything was dropped.
+pub fn cap_output(output: &str, limit: usize) -> (&str, bool) {
+    if output.len() <= limit {
+        return (output, false);
+    }
+    let mut start = output.len() - limit;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = &output[start..];
+    let tail = match tail.find('\n') {
+        Some(newline) if newline + 1 < tail.len() => &tail[newline + 1..],
+        _ => tail,
+    };
+    (tail, true)
+}
+
+/// Bounded buffer of the most recent output lines of a running task
+#[derive(Debug, Default)]
+pub struct TaskOutputTail {
+    lines: VecDeque<String>,
+    bytes: usize,
+    truncated: bool,
+}
+
+impl TaskOutputTail {
+    pub fn push(&mut self, line: &str) {
+        self.bytes += line.len() + 1;
+        self.lines.push_back(line.to_string());
+        while self.bytes > TASK_RUN_OUTPUT_LIMIT {
+            match self.lines.pop_front() {
+                Some(dropped) => {
+                    self.bytes -= dropped.len() + 1;
+                    self.truncated = true;
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Collected output and whether earlier lines were dropped
+    pub fn finish(self) -> (String, bool) {
+        (Vec::from(self.lines).join("\n"), self.truncated)
+    }
+}
+
+/// A finished task run to record
+#[derive(Debug, Clone)]
+pub struct TaskRunRecord {
+    /// Run ID (shared with the live run in state)
+    pub id: String,
+    pub project_id: String,
+    pub worktree_path: String,
+    pub task_name: String,
+    /// Resolved command line (e.g. "npm run test -- --watch")
+    pub command_line: String,
+    pub args: Vec<String>,
+    pub named_args: HashMap<String, String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+    /// Exit code (None if killed by a signal)
+    pub exit_code: Option<i32>,
+    /// "success", "error" or "cancelled"
+    pub status: String,
+    pub output: String,
+    /// Output was already cut before recording
+    pub output_truncated: bool,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -262,4 +525,161 @@ pub struct LogRow {
     pub timestamp: String,
 }
 
-// Activity Log integration will be added in Phase B1.3
+#[derive(Debug, serde::Serialize)]
+pub struct TaskRunRow {
+    pub id: String,
+    pub task_name: String,
+    pub command_line: String,
+    pub args: Vec<String>,
+    pub named_args: HashMap<String, String>,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub status: String,
+    /// Captured output (only loaded for a single run)
+    pub output: Option<String>,
+    pub output_truncated: bool,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct TaskRunStatsRow {
+    pub task_name: String,
+    /// Finished runs considered (at most TASK_RUN_STATS_WINDOW)
+    pub runs: usize,
+    pub failures: usize,
+    /// Times the outcome flipped between consecutive runs
+    pub flips: usize,
+    /// Outcome flipped at least twice within the window
+    pub flaky: bool,
+    pub avg_duration_ms: u64,
+    pub max_duration_ms: u64,
+    pub last_duration_ms: u64,
+}
+
+impl TaskRunStatsRow {
+    /// Stats from `(succeeded, duration_ms)` pairs, newest first
+    fn from_runs(task_name: String, runs: &[(bool, i64)]) -> Self {
+        let durations: Vec<u64> = runs.iter().map(|(_, d)| (*d).max(0) as u64).collect();
+        let failures = runs.iter().filter(|(ok, _)| !ok).count();
+        let flips = runs.windows(2).filter(|pair| pair[0].0 != pair[1].0).count();
+        Self {
+            task_name,
+            runs: runs.len(),
+            failures,
+            flips,
+            flaky: flips >= 2,
+            avg_duration_ms: durations.iter().sum::<u64>() / durations.len().max(1) as u64,
+            max_duration_ms: durations.iter().copied().max().unwrap_or(0),
+            last_duration_ms: durations.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> DbManager {
+        DbManager::with_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn record(task: &str, status: &str, offset_secs: i64, duration_ms: i64) -> TaskRunRecord {
+        let started_at = chrono::Utc::now() + chrono::Duration::seconds(offset_secs);
+        TaskRunRecord {
+            id: Uuid::new_v4().to_string(),
+            project_id: "p".to_string(),
+            worktree_path: "/wt".to_string(),
+            task_name: task.to_string(),
+            command_line: format!("just {}", task),
+            args: vec!["a".to_string()],
+            named_args: HashMap::new(),
+            started_at,
+            finished_at: started_at + chrono::Duration::milliseconds(duration_ms),
+            exit_code: Some(if status == "success" { 0 } else { 1 }),
+            status: status.to_string(),
+            output: "line 1\nline 2".to_string(),
+            output_truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_task_runs_roundtrip() {
+        let db = manager();
+        let build = record("build", "success", 0, 1500);
+        let first = build.id.clone();
+        db.add_task_run(&build).unwrap();
+        db.add_task_run(&record("test", "error", 1, 200)).unwrap();
+        let mut other = record("build", "success", 2, 10);
+        other.worktree_path = "/other".to_string();
+        db.add_task_run(&other).unwrap();
+
+        let runs = db.get_task_runs("p", "/wt", 10).unwrap();
+        let names: Vec<&str> = runs.iter().map(|r| r.task_name.as_str()).collect();
+        assert_eq!(names, vec!["test", "build"]);
+        assert!(runs[0].output.is_none());
+
+        let run = db.get_task_run("p", &first).unwrap().unwrap();
+        assert_eq!(run.duration_ms, 1500);
+        assert_eq!(run.args, vec!["a"]);
+        assert_eq!(run.output.as_deref(), Some("line 1\nline 2"));
+        assert!(db.get_task_run("other-project", &first).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_task_runs_beyond_retention_are_deleted() {
+        let db = manager();
+        let oldest = record("build", "success", 0, 10);
+        db.add_task_run(&oldest).unwrap();
+        for i in 1..=TASK_RUN_RETENTION as i64 {
+            db.add_task_run(&record("build", "success", i, 10)).unwrap();
+        }
+        db.add_task_run(&record("test", "success", 0, 10)).unwrap();
+
+        let runs = db.get_task_runs("p", "/wt", TASK_RUN_RETENTION * 2).unwrap();
+        assert_eq!(runs.iter().filter(|r| r.task_name == "build").count(), TASK_RUN_RETENTION);
+        assert_eq!(runs.iter().filter(|r| r.task_name == "test").count(), 1);
+        assert!(db.get_task_run("p", &oldest.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_task_run_stats() {
+        let db = manager();
+        for (i, status) in ["success", "error", "success", "error"].iter().enumerate() {
+            db.add_task_run(&record("test", status, i as i64, 100)).unwrap();
+        }
+        db.add_task_run(&record("build", "success", 10, 5000)).unwrap();
+        db.add_task_run(&record("build", "error", 11, 3000)).unwrap();
+        db.add_task_run(&record("build", "cancelled", 12, 10)).unwrap();
+
+        let stats = db.get_task_run_stats("p", "/wt").unwrap();
+        assert_eq!(stats[0].task_name, "build");
+        assert_eq!(stats[0].runs, 2);
+        assert_eq!(stats[0].avg_duration_ms, 4000);
+        assert_eq!(stats[0].last_duration_ms, 3000);
+        assert!(!stats[0].flaky);
+
+        assert_eq!(stats[1].task_name, "test");
+        assert_eq!(stats[1].failures, 2);
+        assert_eq!(stats[1].flips, 3);
+        assert!(stats[1].flaky);
+    }
+
+    #[test]
+    fn test_cap_output_keeps_tail() {
+        assert_eq!(cap_output("short", 10), ("short", false));
+        assert_eq!(cap_output("first\nsecond\nthird", 12), ("third", true));
+    }
+
+    #[test]
+    fn test_task_output_tail() {
+        let mut tail = TaskOutputTail::default();
+        let line = "x".repeat(TASK_RUN_OUTPUT_LIMIT / 2);
+        tail.push("first");
+        tail.push(&line);
+        tail.push(&line);
+        let (output, truncated) = tail.finish();
+        assert!(truncated);
+        assert!(!output.contains("first"));
+    }
+}
@@ -1,6 +1,7 @@
 //! Environment file management.
 //!
-//! Handles copying dotfiles between worktrees for environment synchronization.
+//! Handles copying dotfiles between worktrees for environment synchronization,
+//! and reading dotenv files so their variables can be injected into terminals.
 
 use std::fs;
 use std::path::Path;
@@ -61,6 +62,86 @@ pub fn list_env_files(dir: &str, patterns: &[String]) -> Vec<String> {
     files
 }
 
+/// Parse dotenv content into `(key, value)` pairs in file order
+///
+/// Supports `#` comments, an optional `export ` prefix, and single or double
+/// quoted values. Lines that are not `KEY=VALUE` assignments (e.g. direnv
+/// directives in `.envrc`) are ignored.
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    let mut vars = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let valid_key = !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            continue;
+        }
+
+        vars.push((key.to_string(), parse_dotenv_value(value.trim())));
+    }
+
+    vars
+}
+
+/// Unquote a dotenv value, stripping trailing comments from unquoted values
+fn parse_dotenv_value(raw: &str) -> String {
+    if let Some(rest) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return value,
+                '\\' => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some(other) => value.push(other),
+                    None => break,
+                },
+                _ => value.push(c),
+            }
+        }
+    }
+    if let Some(rest) = raw.strip_prefix('\'') {
+        if let Some(end) = rest.find('\'') {
+            return rest[..end].to_string();
+        }
+    }
+    match raw.find(" #") {
+        Some(idx) => raw[..idx].trim_end().to_string(),
+        None => raw.to_string(),
+    }
+}
+
+/// Read variables from the tracked dotenv files in a directory
+///
+/// Only regular files are read (tracked directories such as `.claude/` are
+/// skipped). Returns `(pattern, vars)` in pattern order.
+pub fn read_env_files(dir: &str, patterns: &[String]) -> Vec<(String, Vec<(String, String)>)> {
+    let base = Path::new(dir);
+
+    list_env_files(dir, patterns)
+        .into_iter()
+        .filter_map(|pattern| {
+            let path = base.join(&pattern);
+            if !path.is_file() {
+                return None;
+            }
+            let content = fs::read_to_string(&path).ok()?;
+            Some((pattern, parse_dotenv(&content)))
+        })
+        .collect()
+}
+
 /// Copy env files from source to destination worktree
 ///
 /// # Arguments
@@ -155,6 +236,54 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Set `key` in a dotenv file, replacing an existing assignment in place or
+/// appending one. Creates the file if it doesn't exist.
+pub fn upsert_dotenv(path: &Path, key: &str, value: &str) -> Result<(), String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    let needs_quotes = value.is_empty() || value.chars().any(|c| c.is_whitespace() || c == '#' || c == '"');
+    let value = if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    };
+    let assignment = format!("{}={}", key, value);
+
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let unexported = trimmed.strip_prefix("export ").map(str::trim_start);
+            let is_key = unexported
+                .unwrap_or(trimmed)
+                .split_once('=')
+                .is_some_and(|(k, _)| k.trim() == key);
+            if is_key && !replaced {
+                replaced = true;
+                // Keep an `export ` prefix
+                match unexported {
+                    Some(_) => format!("export {}", assignment),
+                    None => assignment.clone(),
+                }
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        lines.push(assignment);
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    fs::write(path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,4 +434,64 @@ mod tests {
         assert!(!partial.is_success());
         assert!(partial.is_partial());
     }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv(
+            "# comment\n\
+             DATABASE_URL=postgres://localhost/app\n\
+             export API_KEY = 'abc 123'\n\
+             GREETING=\"hello\\nworld\"\n\
+             PORT=3000 # trailing comment\n\
+             use flake\n\
+             1BAD=skip\n\
+             EMPTY=\n",
+        );
+
+        assert_eq!(
+            vars,
+            vec![
+                ("DATABASE_URL".to_string(), "postgres://localhost/app".to_string()),
+                ("API_KEY".to_string(), "abc 123".to_string()),
+                ("GREETING".to_string(), "hello\nworld".to_string()),
+                ("PORT".to_string(), "3000".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_env_files_skips_directories() {
+        let temp = TempDir::new().unwrap();
+        File::create(temp.path().join(".env"))
+            .unwrap()
+            .write_all(b"KEY=value")
+            .unwrap();
+        fs::create_dir(temp.path().join(".claude")).unwrap();
+
+        let files = read_env_files(temp.path().to_str().unwrap(), &default_patterns());
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, ".env");
+        assert_eq!(files[0].1, vec![("KEY".to_string(), "value".to_string())]);
+    }
+
+    #[test]
+    fn test_upsert_dotenv() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(".env");
+
+        upsert_dotenv(&path, "DATABASE_URL", "postgresql://localhost/app").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "DATABASE_URL=postgresql://localhost/app\n");
+
+        fs::write(&path, "# comment\nexport DATABASE_URL=old\nPORT=3000\n").unwrap();
+        upsert_dotenv(&path, "DATABASE_URL", "postgresql://localhost/feature").unwrap();
+        upsert_dotenv(&path, "GREETING", "hello world").unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "# comment\nexport DATABASE_URL=postgresql://localhost/feature\nPORT=3000\nGREETING=\"hello world\"\n"
+        );
+        let vars = parse_dotenv(&content);
+        assert_eq!(vars[2], ("GREETING".to_string(), "hello world".to_string()));
+    }
 }
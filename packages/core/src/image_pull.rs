@@ -0,0 +1,180 @@
+//! Progress of image pulls.
+//!
+//! Docker reports a pull as per-layer events on the `create_image` stream
+//! ("Pulling fs layer", "Downloading", "Extracting", "Pull complete", ...).
+//! [`PullProgress`] folds them into one fraction: each layer counts
+//! equally, its download as the first half and its extraction as the
+//! second.
+
+use bollard::models::CreateImageInfo;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Minimum time between progress updates pushed into state
+pub const PULL_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Where a layer is in the pull
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LayerPhase {
+    Waiting,
+    /// Fraction of the download done
+    Downloading(f64),
+    Downloaded,
+    /// Fraction of the extraction done
+    Extracting(f64),
+    Done,
+}
+
+impl LayerPhase {
+    fn fraction(self) -> f64 {
+        match self {
+            Self::Waiting => 0.0,
+            Self::Downloading(f) => f * 0.5,
+            Self::Downloaded => 0.5,
+            Self::Extracting(f) => 0.5 + f * 0.5,
+            Self::Done => 1.0,
+        }
+    }
+}
+
+/// Aggregated progress of one image pull
+#[derive(Debug, Clone, Default)]
+pub struct PullProgress {
+    layers: BTreeMap<String, LayerPhase>,
+    /// Last status line that isn't about a single layer
+    status: Option<String>,
+}
+
+/// `current / total` of an event's progress detail, when known
+fn detail_fraction(info: &CreateImageInfo) -> Option<f64> {
+    let detail = info.progress_detail.as_ref()?;
+    let (current, total) = (detail.current?, detail.total?);
+    (total > 0).then(|| (current as f64 / total as f64).clamp(0.0, 1.0))
+}
+
+impl PullProgress {
+    /// Fold in one event of the `create_image` stream
+    ///
+    /// Fails with the daemon's message when the event reports an error
+    /// (e.g. unknown tag, registry unreachable).
+    pub fn update(&mut self, info: &CreateImageInfo) -> Result<(), String> {
+        if let Some(error) = &info.error {
+            return Err(error.clone());
+        }
+        let Some(status) = info.status.as_deref() else {
+            return Ok(());
+        };
+
+        let phase = match status {
+            "Pulling fs layer" | "Waiting" => Some(LayerPhase::Waiting),
+            "Downloading" => Some(LayerPhase::Downloading(detail_fraction(info).unwrap_or(0.0))),
+            "Verifying Checksum" | "Download complete" => Some(LayerPhase::Downloaded),
+            "Extracting" => Some(LayerPhase::Extracting(detail_fraction(info).unwrap_or(0.0))),
+            "Pull complete" | "Already exists" => Some(LayerPhase::Done),
+            _ => None,
+        };
+        match (phase, &info.id) {
+            (Some(phase), Some(id)) => {
+                self.layers.insert(id.clone(), phase);
+            }
+            _ => self.status = Some(status.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Fraction of the pull done, from 0 to 1
+    pub fn fraction(&self) -> f64 {
+        if self.layers.is_empty() {
+            return 0.0;
+        }
+        self.layers.values().map(|p| p.fraction()).sum::<f64>() / self.layers.len() as f64
+    }
+
+    pub fn layers_total(&self) -> u32 {
+        self.layers.len() as u32
+    }
+
+    pub fn layers_done(&self) -> u32 {
+        self.layers.values().filter(|p| **p == LayerPhase::Done).count() as u32
+    }
+
+    /// Short description of what the pull is doing
+    pub fn status(&self) -> String {
+        let downloading = self
+            .layers
+            .values()
+            .any(|p| matches!(p, LayerPhase::Waiting | LayerPhase::Downloading(_)));
+        let extracting = self
+            .layers
+            .values()
+            .any(|p| matches!(p, LayerPhase::Downloaded | LayerPhase::Extracting(_)));
+        if downloading {
+            "Downloading".to_string()
+        } else if extracting {
+            "Extracting".to_string()
+        } else {
+            self.status.clone().unwrap_or_else(|| "Pulling".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::ProgressDetail;
+
+    fn event(id: &str, status: &str, progress: Option<(i64, i64)>) -> CreateImageInfo {
+        CreateImageInfo {
+            id: Some(id.to_string()),
+            status: Some(status.to_string()),
+            progress_detail: progress.map(|(current, total)| ProgressDetail {
+                current: Some(current),
+                total: Some(total),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_layers_aggregate_into_one_fraction() {
+        let mut pull = PullProgress::default();
+        pull.update(&event("3-management", "Pulling from library/rabbitmq", None)).unwrap();
+        assert_eq!(pull.layers_total(), 0);
+        assert_eq!(pull.fraction(), 0.0);
+
+        pull.update(&event("a", "Pulling fs layer", None)).unwrap();
+        pull.update(&event("b", "Already exists", None)).unwrap();
+        assert_eq!(pull.layers_total(), 2);
+        assert_eq!(pull.layers_done(), 1);
+        assert_eq!(pull.fraction(), 0.5);
+        assert_eq!(pull.status(), "Downloading");
+
+        // Half of a's download is a quarter of a, an eighth of the image
+        pull.update(&event("a", "Downloading", Some((50, 100)))).unwrap();
+        assert_eq!(pull.fraction(), 0.625);
+
+        pull.update(&event("a", "Download complete", None)).unwrap();
+        assert_eq!(pull.status(), "Extracting");
+        pull.update(&event("a", "Extracting", Some((100, 100)))).unwrap();
+        assert_eq!(pull.fraction(), 1.0);
+        pull.update(&event("a", "Pull complete", None)).unwrap();
+        assert_eq!(pull.layers_done(), 2);
+
+        pull.update(&CreateImageInfo {
+            status: Some("Status: Downloaded newer image for rabbitmq:3-management".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(pull.status(), "Status: Downloaded newer image for rabbitmq:3-management");
+    }
+
+    #[test]
+    fn test_error_event_fails_the_pull() {
+        let mut pull = PullProgress::default();
+        let error = pull.update(&CreateImageInfo {
+            error: Some("manifest for postgres:99 not found".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(error, Err("manifest for postgres:99 not found".to_string()));
+    }
+}
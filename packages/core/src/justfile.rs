@@ -1,101 +1,881 @@
 //! Justfile parser for extracting commands and descriptions.
+//!
+//! Prefers `just --dump --dump-format json` when `just` is installed, and
+//! falls back to a native parser covering recipe parameters, dependencies,
+//! attributes, aliases, settings, assignments and `import`/`mod` statements.
 
 use napi_derive::napi;
+use serde_json::Value;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// File names `just` looks for, in lookup order
+const JUSTFILE_NAMES: &[&str] = &["justfile", "Justfile", ".justfile", "JUSTFILE"];
+
+/// Maximum nesting of `import`/`mod` statements (guards against cycles)
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// A recipe parameter
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct JustParameter {
+    /// Parameter name
+    pub name: String,
+    /// Default value (string literals unquoted, other expressions verbatim)
+    pub default: Option<String>,
+    /// "singular", "plus" (`+args`, one or more) or "star" (`*args`, zero or more)
+    pub kind: String,
+    /// Exported to the recipe as an environment variable (`$name`)
+    pub export: bool,
+}
+
 /// A command parsed from a justfile
 #[napi(object)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct JustCommand {
-    /// Command name (e.g., "test", "build")
+    /// Command name (e.g., "test", "build"; "module::recipe" inside modules)
     pub name: String,
-    /// Description from comment above the command
+    /// Description from comment above the command (or `[doc]` attribute)
     pub description: Option<String>,
     /// The recipe/shell commands
     pub recipe: String,
+    /// Recipe parameters in declaration order
+    pub parameters: Vec<JustParameter>,
+    /// Recipes this one depends on (prior and subsequent)
+    pub dependencies: Vec<String>,
+    /// Group from the `[group]` attribute
+    pub group: Option<String>,
+    /// Hidden from listings (`[private]` or a leading underscore)
+    pub private: bool,
+    /// Aliases pointing at this recipe
+    pub aliases: Vec<String>,
+    /// Requires confirmation before running (`[confirm]`)
+    pub confirm: bool,
+}
+
+/// Find the justfile in a directory (any of the names `just` accepts)
+pub fn find_justfile(dir: &str) -> Option<PathBuf> {
+    JUSTFILE_NAMES
+        .iter()
+        .map(|name| Path::new(dir).join(name))
+        .find(|path| path.is_file())
 }
 
 /// Parse a justfile and extract all commands
 pub fn parse_justfile(path: &str) -> Result<Vec<JustCommand>, String> {
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read justfile: {}", e))?;
+    if let Some(commands) = dump_justfile(path) {
+        return Ok(commands);
+    }
+    parse_justfile_native(path)
+}
+
+/// Ask `just` itself for the parsed justfile (None if unavailable or failing)
+fn dump_justfile(path: &str) -> Option<Vec<JustCommand>> {
+    let output = Command::new("just")
+        .args(["--justfile", path, "--dump", "--dump-format", "json"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_dump_json(&String::from_utf8_lossy(&output.stdout)).ok()
+}
 
+/// Convert the output of `just --dump --dump-format json` into commands
+///
+/// Recipes come back in name order (the dump does not preserve file order).
+pub fn parse_dump_json(json: &str) -> Result<Vec<JustCommand>, String> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid justfile dump: {}", e))?;
     let mut commands = Vec::new();
-    let mut pending_description: Option<String> = None;
-    let mut current_name: Option<String> = None;
-    let mut current_description: Option<String> = None;
-    let mut current_recipe = String::new();
-    let mut in_recipe = false;
-
-    for line in content.lines() {
-        // Check for comment (description for next command)
-        // A comment at the start of a line (not indented) ends the current recipe
-        if line.starts_with('#') {
-            in_recipe = false;
-            let comment = line.trim_start_matches('#').trim();
-            pending_description = Some(comment.to_string());
-            continue;
-        }
-
-        // Check for command definition (name followed by colon)
-        if !line.starts_with(' ') && !line.starts_with('\t') && line.contains(':') {
-            // Save previous command if exists
-            if let Some(name) = current_name.take() {
-                commands.push(JustCommand {
-                    name,
-                    description: current_description.take(),
-                    recipe: current_recipe.trim().to_string(),
+    commands_from_dump(&value, "", &mut commands);
+    Ok(commands)
+}
+
+fn commands_from_dump(justfile: &Value, prefix: &str, commands: &mut Vec<JustCommand>) {
+    let start = commands.len();
+
+    if let Some(recipes) = justfile.get("recipes").and_then(Value::as_object) {
+        for (name, recipe) in recipes {
+            let mut command = JustCommand {
+                name: format!("{}{}", prefix, name),
+                description: recipe.get("doc").and_then(Value::as_str).map(str::to_string),
+                private: recipe.get("private").and_then(Value::as_bool).unwrap_or(false),
+                ..Default::default()
+            };
+
+            for attribute in recipe
+                .get("attributes")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                match attribute {
+                    Value::String(name) => apply_attribute(&mut command, name, None),
+                    Value::Object(map) => {
+                        for (name, arg) in map {
+                            apply_attribute(&mut command, name, arg.as_str().map(str::to_string));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            command.parameters = recipe
+                .get("parameters")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|param| JustParameter {
+                    name: param.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+                    default: param
+                        .get("default")
+                        .filter(|d| !d.is_null())
+                        .map(render_expression),
+                    kind: param
+                        .get("kind")
+                        .and_then(Value::as_str)
+                        .unwrap_or("singular")
+                        .to_string(),
+                    export: param.get("export").and_then(Value::as_bool).unwrap_or(false),
+                })
+                .collect();
+
+            command.dependencies = recipe
+                .get("dependencies")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|dep| dep.get("recipe").and_then(Value::as_str))
+                .map(|dep| format!("{}{}", prefix, dep))
+                .collect();
+
+            let lines: Vec<String> = recipe
+                .get("body")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|line| {
+                    line.as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|fragment| match fragment {
+                            Value::String(text) => text.clone(),
+                            Value::Array(expr) => format!(
+                                "{{{{{}}}}}",
+                                expr.first().map(render_expression).unwrap_or_default()
+                            ),
+                            _ => String::new(),
+                        })
+                        .collect()
+                })
+                .collect();
+            command.recipe = lines.join("\n").trim().to_string();
+
+            commands.push(command);
+        }
+    }
+
+    if let Some(aliases) = justfile.get("aliases").and_then(Value::as_object) {
+        for (alias, info) in aliases {
+            let target = info.get("target").and_then(Value::as_str).unwrap_or_default();
+            let target = format!("{}{}", prefix, target);
+            if let Some(command) = commands[start..].iter_mut().find(|c| c.name == target) {
+                command.aliases.push(format!("{}{}", prefix, alias));
+            }
+        }
+    }
+
+    if let Some(modules) = justfile.get("modules").and_then(Value::as_object) {
+        for (name, module) in modules {
+            commands_from_dump(module, &format!("{}{}::", prefix, name), commands);
+        }
+    }
+}
+
+/// Render a dumped expression back into justfile-like source
+fn render_expression(expr: &Value) -> String {
+    match expr {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => {
+            let op = items.first().and_then(Value::as_str).unwrap_or_default();
+            let args: Vec<String> = items.iter().skip(1).map(render_expression).collect();
+            match op {
+                "variable" => args.join(""),
+                "evaluate" => format!("`{}`", args.join("")),
+                "concatenate" => args.join(" + "),
+                "join" => args.join(" / "),
+                "call" => match args.split_first() {
+                    Some((name, rest)) => format!("{}({})", name, rest.join(", ")),
+                    None => String::new(),
+                },
+                _ => expr.to_string(),
+            }
+        }
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Parse a justfile without invoking `just`
+pub fn parse_justfile_native(path: &str) -> Result<Vec<JustCommand>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read justfile: {}", e))?;
+    let dir = Path::new(path).parent().unwrap_or(Path::new("."));
+
+    let mut parser = NativeParser::default();
+    parser.parse_source(&content, dir, "", 0);
+    Ok(parser.finish())
+}
+
+#[derive(Default)]
+struct NativeParser {
+    commands: Vec<JustCommand>,
+    /// (alias, target) with module prefixes applied
+    aliases: Vec<(String, String)>,
+}
+
+impl NativeParser {
+    fn finish(mut self) -> Vec<JustCommand> {
+        for (alias, target) in self.aliases {
+            if let Some(command) = self.commands.iter_mut().find(|c| c.name == target) {
+                command.aliases.push(alias);
+            }
+        }
+        self.commands
+    }
+
+    fn parse_source(&mut self, content: &str, dir: &Path, prefix: &str, depth: usize) {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut pending_doc: Option<String> = None;
+        // (name, argument) of attributes collected for the next recipe
+        let mut attributes: Vec<(String, Option<String>)> = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.trim();
+            i += 1;
+
+            if trimmed.is_empty() {
+                pending_doc = None;
+                continue;
+            }
+            // Indented lines outside a recipe body (e.g. assignment continuations)
+            if line.starts_with(' ') || line.starts_with('\t') {
+                continue;
+            }
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                if !comment.starts_with('!') {
+                    pending_doc = Some(comment.trim().to_string());
+                }
+                continue;
+            }
+            if trimmed.starts_with('[') {
+                attributes.extend(parse_attributes(trimmed));
+                continue;
+            }
+
+            let doc = pending_doc.take();
+            let attrs = std::mem::take(&mut attributes);
+            let mut cursor = Cursor::new(trimmed);
+            let keyword = cursor.ident();
+            cursor.skip_ws();
+
+            match keyword.as_str() {
+                "alias" if cursor.peek_ident_then(":=") => {
+                    let alias = cursor.ident();
+                    cursor.skip_ws();
+                    cursor.eat(":=");
+                    cursor.skip_ws();
+                    let target = cursor.path();
+                    self.aliases
+                        .push((format!("{}{}", prefix, alias), format!("{}{}", prefix, target)));
+                    continue;
+                }
+                "set" if cursor.peek_is_ident() => continue,
+                "export" | "unexport" if cursor.peek_is_ident() => continue,
+                "import" if matches!(cursor.peek(), Some('?' | '\'' | '"')) => {
+                    let optional = cursor.eat("?");
+                    cursor.skip_ws();
+                    if let Some(file) = cursor.string_literal() {
+                        let file_path = dir.join(file);
+                        if depth < MAX_INCLUDE_DEPTH {
+                            match fs::read_to_string(&file_path) {
+                                Ok(content) => {
+                                    let file_dir = file_path.parent().unwrap_or(dir).to_path_buf();
+                                    self.parse_source(&content, &file_dir, prefix, depth + 1);
+                                }
+                                Err(_) if optional => {}
+                                Err(e) => tracing::debug!(
+                                    "Skipping justfile import {}: {}",
+                                    file_path.display(),
+                                    e
+                                ),
+                            }
+                        }
+                    }
+                    continue;
+                }
+                "mod" if cursor.peek() == Some('?') || cursor.peek_is_ident() => {
+                    cursor.eat("?");
+                    cursor.skip_ws();
+                    let name = cursor.ident();
+                    cursor.skip_ws();
+                    let explicit = cursor.string_literal();
+                    if let Some(file_path) = find_module_file(dir, &name, explicit.as_deref()) {
+                        if depth < MAX_INCLUDE_DEPTH {
+                            if let Ok(content) = fs::read_to_string(&file_path) {
+                                let file_dir = file_path.parent().unwrap_or(dir).to_path_buf();
+                                let module_prefix = format!("{}{}::", prefix, name);
+                                self.parse_source(&content, &file_dir, &module_prefix, depth + 1);
+                            }
+                        }
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            // Variable assignment (`name := value`)
+            if cursor.rest().starts_with(":=") {
+                continue;
+            }
+
+            let Some(mut command) = parse_recipe_header(trimmed, prefix) else {
+                continue;
+            };
+
+            // Collect the indented body (blank lines inside the body are kept)
+            let body_start = i;
+            let mut body_end = i;
+            while i < lines.len() {
+                let body_line = lines[i];
+                if body_line.trim().is_empty() {
+                    i += 1;
+                } else if body_line.starts_with(' ') || body_line.starts_with('\t') {
+                    i += 1;
+                    body_end = i;
+                } else {
+                    break;
+                }
+            }
+            i = body_end.max(body_start);
+            command.recipe = dedent(&lines[body_start..body_end]);
+
+            command.description = doc;
+            for (name, arg) in attrs {
+                apply_attribute(&mut command, &name, arg);
+            }
+            self.commands.push(command);
+        }
+    }
+}
+
+/// Locate a module's source file the way `just` does
+fn find_module_file(dir: &Path, name: &str, explicit: Option<&str>) -> Option<PathBuf> {
+    if let Some(explicit) = explicit {
+        let path = dir.join(explicit);
+        if path.is_dir() {
+            return ["mod.just", "justfile", "Justfile", ".justfile"]
+                .iter()
+                .map(|file| path.join(file))
+                .find(|p| p.is_file());
+        }
+        return path.is_file().then_some(path);
+    }
+
+    let candidates = [
+        dir.join(format!("{}.just", name)),
+        dir.join(name).join("mod.just"),
+        dir.join(name).join("justfile"),
+        dir.join(name).join("Justfile"),
+        dir.join(name).join(".justfile"),
+    ];
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// Apply a recipe attribute by name (unknown attributes are ignored)
+fn apply_attribute(command: &mut JustCommand, name: &str, arg: Option<String>) {
+    match name {
+        "private" => command.private = true,
+        "group" => command.group = arg,
+        "confirm" => command.confirm = true,
+        "doc" => command.description = arg,
+        _ => {}
+    }
+}
+
+/// Parse an attribute line like `[private, group('ci')]` or `[group: 'ci']`
+fn parse_attributes(line: &str) -> Vec<(String, Option<String>)> {
+    let inner = line
+        .trim_start_matches('[')
+        .rsplit_once(']')
+        .map(|(inner, _)| inner)
+        .unwrap_or_default();
+
+    split_top_level(inner, ',')
+        .into_iter()
+        .map(|item| {
+            let item = item.trim();
+            let name_end = item
+                .find(|c: char| c == '(' || c == ':' || c.is_whitespace())
+                .unwrap_or(item.len());
+            let arg = Cursor::new(&item[name_end..]).first_string_literal();
+            (item[..name_end].to_string(), arg)
+        })
+        .collect()
+}
+
+/// Parse `@name param='default' +rest: dep (dep2 arg) && post`
+fn parse_recipe_header(line: &str, prefix: &str) -> Option<JustCommand> {
+    let mut cursor = Cursor::new(line);
+    cursor.eat("@");
+    let name = cursor.ident();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut parameters = Vec::new();
+    loop {
+        cursor.skip_ws();
+        match cursor.peek()? {
+            ':' => break,
+            _ => {
+                let kind = if cursor.eat("+") {
+                    "plus"
+                } else if cursor.eat("*") {
+                    "star"
+                } else {
+                    "singular"
+                };
+                let export = cursor.eat("$");
+                let param = cursor.ident();
+                if param.is_empty() {
+                    return None;
+                }
+                cursor.skip_ws();
+                let default = if cursor.eat("=") {
+                    cursor.skip_ws();
+                    Some(cursor.value()?)
+                } else {
+                    None
+                };
+                parameters.push(JustParameter {
+                    name: param,
+                    default,
+                    kind: kind.to_string(),
+                    export,
                 });
-                current_recipe = String::new();
-            }
-
-            // Parse new command name
-            let parts: Vec<&str> = line.splitn(2, ':').collect();
-            if !parts.is_empty() {
-                let name = parts[0].trim();
-                // Skip if it looks like a variable assignment
-                if !name.contains('=') && !name.is_empty() {
-                    current_name = Some(name.to_string());
-                    // The pending description belongs to THIS command
-                    current_description = pending_description.take();
-                    in_recipe = true;
+            }
+        }
+    }
+
+    cursor.eat(":");
+    if cursor.peek() == Some('=') {
+        return None;
+    }
+
+    let mut dependencies = Vec::new();
+    loop {
+        cursor.skip_ws();
+        match cursor.peek() {
+            None | Some('#') => break,
+            Some('&') => {
+                cursor.eat("&&");
+            }
+            Some('(') => {
+                cursor.eat("(");
+                cursor.skip_ws();
+                let dep = cursor.path();
+                if dep.is_empty() {
+                    break;
+                }
+                dependencies.push(format!("{}{}", prefix, dep));
+                // Skip the dependency's arguments
+                let mut depth = 1;
+                while depth > 0 {
+                    match cursor.peek() {
+                        None => break,
+                        Some('\'' | '"' | '`') => {
+                            cursor.string_literal();
+                        }
+                        Some(c) => {
+                            if c == '(' {
+                                depth += 1;
+                            } else if c == ')' {
+                                depth -= 1;
+                            }
+                            cursor.bump();
+                        }
+                    }
+                }
+            }
+            Some(_) => {
+                let dep = cursor.path();
+                if dep.is_empty() {
+                    break;
                 }
+                dependencies.push(format!("{}{}", prefix, dep));
             }
-            continue;
         }
+    }
+
+    Some(JustCommand {
+        private: name.starts_with('_'),
+        name: format!("{}{}", prefix, name),
+        parameters,
+        dependencies,
+        ..Default::default()
+    })
+}
+
+/// Strip the first body line's indentation from every line
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .find(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| {
+            let strip = indent.min(l.len() - l.trim_start().len());
+            l[strip..].trim_end()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Split on `sep` outside quotes and parentheses
+fn split_top_level(text: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                c if c == sep && depth == 0 => {
+                    parts.push(&text[start..i]);
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            },
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Minimal scanner over a single justfile line
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
 
-        // Collect recipe lines (indented with spaces or tabs)
-        if in_recipe && (line.starts_with(' ') || line.starts_with('\t')) {
-            current_recipe.push_str(line.trim());
-            current_recipe.push('\n');
-        } else if in_recipe && line.trim().is_empty() {
-            // Empty line might still be part of recipe
-            current_recipe.push('\n');
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
         } else {
-            // Non-indented non-empty line ends recipe
-            if !line.trim().is_empty() {
-                in_recipe = false;
+            false
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn peek_is_ident(&self) -> bool {
+        self.peek().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+    }
+
+    /// Whether the rest is `ident :=` (used to tell keywords from recipe names)
+    fn peek_ident_then(&self, token: &str) -> bool {
+        let mut probe = Cursor::new(self.rest());
+        !probe.ident().is_empty() && {
+            probe.skip_ws();
+            probe.rest().starts_with(token)
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            self.bump();
+        }
+        self.text[start..self.pos].to_string()
+    }
+
+    /// An identifier optionally qualified with `module::`
+    fn path(&mut self) -> String {
+        let mut path = self.ident();
+        while !path.is_empty() && self.rest().starts_with("::") {
+            self.eat("::");
+            path.push_str("::");
+            path.push_str(&self.ident());
+        }
+        path
+    }
+
+    /// A quoted string or backtick; returns the unquoted contents
+    fn string_literal(&mut self) -> Option<String> {
+        for delimiter in ["\"\"\"", "'''", "```", "\"", "'", "`"] {
+            if self.eat(delimiter) {
+                let end = self.rest().find(delimiter)?;
+                let raw = &self.rest()[..end];
+                self.pos += end + delimiter.len();
+                let value = if delimiter.starts_with('"') {
+                    raw.replace("\\n", "\n")
+                        .replace("\\t", "\t")
+                        .replace("\\\"", "\"")
+                        .replace("\\\\", "\\")
+                } else {
+                    raw.to_string()
+                };
+                return Some(if delimiter.starts_with('`') {
+                    format!("`{}`", value)
+                } else {
+                    value
+                });
             }
         }
+        None
+    }
 
-        // Reset pending description if not followed by command
-        if !line.starts_with('#') && !line.trim().is_empty() && current_name.is_none() {
-            pending_description = None;
+    /// The first string literal anywhere in the rest of the text
+    fn first_string_literal(&mut self) -> Option<String> {
+        while let Some(c) = self.peek() {
+            if matches!(c, '\'' | '"') {
+                return self.string_literal();
+            }
+            self.bump();
         }
+        None
     }
 
-    // Save last command
-    if let Some(name) = current_name {
-        commands.push(JustCommand {
-            name,
-            description: current_description,
-            recipe: current_recipe.trim().to_string(),
-        });
+    /// A parameter default: string literal, `(expression)` or bare word
+    fn value(&mut self) -> Option<String> {
+        match self.peek()? {
+            '\'' | '"' | '`' => self.string_literal(),
+            '(' => {
+                let start = self.pos;
+                let mut depth = 0;
+                loop {
+                    match self.peek()? {
+                        '\'' | '"' | '`' => {
+                            self.string_literal()?;
+                        }
+                        c => {
+                            if c == '(' {
+                                depth += 1;
+                            } else if c == ')' {
+                                depth -= 1;
+                            }
+                            self.bump();
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                    }
+                }
+                Some(self.text[start..self.pos].to_string())
+            }
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| !c.is_whitespace() && c != ':')
+                {
+                    self.bump();
+                }
+                Some(self.text[start..self.pos].to_string())
+            }
+        }
     }
+}
 
-    Ok(commands)
+/// Render a recipe's signature (e.g. `migrate name +flags='-v'`) for error messages
+pub fn recipe_signature(command: &JustCommand) -> String {
+    let mut signature = command.name.clone();
+    for param in &command.parameters {
+        signature.push(' ');
+        match param.kind.as_str() {
+            "plus" => signature.push('+'),
+            "star" => signature.push('*'),
+            _ => {}
+        }
+        if param.export {
+            signature.push('$');
+        }
+        signature.push_str(&param.name);
+        if let Some(default) = &param.default {
+            signature.push_str(&format!("='{}'", default));
+        }
+    }
+    signature
+}
+
+/// Bind positional and named arguments to a recipe's parameters
+///
+/// Returns the positional arguments to pass to `just` after the recipe name.
+/// Named values fill parameters by name (values for variadic parameters are
+/// split on whitespace). A defaulted parameter can only be skipped if no
+/// later parameter is given, since `just` takes arguments positionally.
+pub fn bind_recipe_args(
+    command: &JustCommand,
+    args: &[String],
+    named: &HashMap<String, String>,
+) -> Result<Vec<String>, String> {
+    let usage = || format!("usage: just {}", recipe_signature(command));
+
+    if let Some(unknown) = named
+        .keys()
+        .find(|key| !command.parameters.iter().any(|p| &p.name == *key))
+    {
+        return Err(format!(
+            "Recipe '{}' has no parameter '{}' ({})",
+            command.name,
+            unknown,
+            usage()
+        ));
+    }
+
+    // Values per parameter; None means "not given"
+    let mut values: Vec<Option<Vec<String>>> = vec![None; command.parameters.len()];
+    let mut positional = args.iter();
+
+    for (index, param) in command.parameters.iter().enumerate() {
+        let variadic = param.kind != "singular";
+        let given: Vec<String> = if variadic {
+            positional.by_ref().cloned().collect()
+        } else {
+            positional.next().cloned().into_iter().collect()
+        };
+
+        if let Some(value) = named.get(&param.name) {
+            if !given.is_empty() {
+                return Err(format!(
+                    "Parameter '{}' of recipe '{}' was given both positionally and by name",
+                    param.name, command.name
+                ));
+            }
+            values[index] = Some(if variadic {
+                value.split_whitespace().map(str::to_string).collect()
+            } else {
+                vec![value.clone()]
+            });
+        } else if !given.is_empty() {
+            values[index] = Some(given);
+        }
+    }
+
+    let extra: Vec<&String> = positional.collect();
+    if !extra.is_empty() {
+        return Err(format!(
+            "Recipe '{}' takes at most {} argument(s) but got {} ({})",
+            command.name,
+            command.parameters.len(),
+            args.len(),
+            usage()
+        ));
+    }
+
+    let last_given = values.iter().rposition(Option::is_some);
+    let mut bound = Vec::new();
+    for (index, (param, value)) in command.parameters.iter().zip(values).enumerate() {
+        match value {
+            Some(value) => {
+                if param.kind == "plus" && value.is_empty() && param.default.is_none() {
+                    return Err(format!(
+                        "Parameter '{}' of recipe '{}' needs at least one value ({})",
+                        param.name,
+                        command.name,
+                        usage()
+                    ));
+                }
+                bound.extend(value);
+            }
+            None if param.default.is_none() && param.kind != "star" => {
+                return Err(format!(
+                    "Recipe '{}' requires argument '{}' ({})",
+                    command.name,
+                    param.name,
+                    usage()
+                ));
+            }
+            None if last_given.is_some_and(|last| last > index) => {
+                return Err(format!(
+                    "Parameter '{}' of recipe '{}' must be given because a later parameter is set ({})",
+                    param.name,
+                    command.name,
+                    usage()
+                ));
+            }
+            None => {}
+        }
+    }
+
+    Ok(bound)
+}
+
+/// Validate arguments for a recipe in a directory's justfile and build the
+/// `just` argument list (`[recipe, args...]`).
+///
+/// Recipes can be referred to by name or alias.
+pub fn prepare_recipe_args(
+    dir: &str,
+    recipe: &str,
+    args: &[String],
+    named: &HashMap<String, String>,
+) -> Result<Vec<String>, String> {
+    let justfile =
+        find_justfile(dir).ok_or_else(|| format!("No justfile found in {}", dir))?;
+    let commands = parse_justfile(&justfile.to_string_lossy())?;
+    let command = commands
+        .iter()
+        .find(|c| c.name == recipe || c.aliases.iter().any(|a| a == recipe))
+        .ok_or_else(|| format!("Unknown recipe '{}'", recipe))?;
+
+    let mut argv = vec![recipe.to_string()];
+    argv.extend(bind_recipe_args(command, args, named)?);
+    Ok(argv)
 }
 
 /// Run a just command in a directory
@@ -124,6 +904,7 @@ pub fn run_just_command(command: &str, cwd: &str) -> Result<String, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn test_parse_justfile() {
@@ -150,4 +931,277 @@ test:
 
         fs::remove_file(temp_path).ok();
     }
+
+    #[test]
+    fn test_native_parser_grammar() {
+        let temp = TempDir::new().unwrap();
+        let content = r#"set dotenv-load
+set shell := ["bash", "-c"]
+
+version := "1.0"
+export RUST_LOG := 'info'
+
+alias b := build
+
+# Build the project
+[group('dev')]
+build target='debug' +features: clean (fmt "check")
+    cargo build --profile {{target}} {{features}}
+
+    echo done
+
+[private, confirm("Really?")]
+deploy $env *flags: build && notify
+    ./deploy.sh $env {{flags}}
+
+_helper:
+    @echo hidden
+
+[doc('Format sources')]
+fmt mode=(arch() + "-x"):
+    cargo fmt
+clean:
+    rm -rf target
+notify:
+"#;
+        let path = temp.path().join("justfile");
+        fs::write(&path, content).unwrap();
+
+        let commands = parse_justfile_native(path.to_str().unwrap()).unwrap();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "deploy", "_helper", "fmt", "clean", "notify"]);
+
+        let build = &commands[0];
+        assert_eq!(build.description.as_deref(), Some("Build the project"));
+        assert_eq!(build.group.as_deref(), Some("dev"));
+        assert_eq!(build.aliases, vec!["b"]);
+        assert_eq!(build.dependencies, vec!["clean", "fmt"]);
+        assert_eq!(
+            build.parameters,
+            vec![
+                JustParameter {
+                    name: "target".to_string(),
+                    default: Some("debug".to_string()),
+                    kind: "singular".to_string(),
+                    export: false,
+                },
+                JustParameter {
+                    name: "features".to_string(),
+                    default: None,
+                    kind: "plus".to_string(),
+                    export: false,
+                },
+            ]
+        );
+        assert_eq!(
+            build.recipe,
+            "cargo build --profile {{target}} {{features}}\n\necho done"
+        );
+
+        let deploy = &commands[1];
+        assert!(deploy.private);
+        assert!(deploy.confirm);
+        assert!(deploy.description.is_none());
+        assert_eq!(deploy.dependencies, vec!["build", "notify"]);
+        assert!(deploy.parameters[0].export);
+        assert_eq!(deploy.parameters[1].kind, "star");
+
+        assert!(commands[2].private);
+        assert_eq!(commands[3].description.as_deref(), Some("Format sources"));
+        assert_eq!(
+            commands[3].parameters[0].default.as_deref(),
+            Some("(arch() + \"-x\")")
+        );
+        assert_eq!(commands[4].recipe, "rm -rf target");
+        assert_eq!(commands[5].recipe, "");
+    }
+
+    #[test]
+    fn test_native_parser_import_and_mod() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("justfile"),
+            "import 'common.just'\nimport? 'missing.just'\nmod db\n\nmain: lint\n    echo main\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("common.just"), "# Lint\nlint:\n    cargo clippy\n").unwrap();
+        fs::create_dir(temp.path().join("db")).unwrap();
+        fs::write(
+            temp.path().join("db").join("mod.just"),
+            "alias m := migrate\nmigrate: reset\n    sqlx migrate run\nreset:\n",
+        )
+        .unwrap();
+
+        let path = temp.path().join("justfile");
+        let commands = parse_justfile_native(path.to_str().unwrap()).unwrap();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["lint", "db::migrate", "db::reset", "main"]);
+        assert_eq!(commands[0].description.as_deref(), Some("Lint"));
+        assert_eq!(commands[1].dependencies, vec!["db::reset"]);
+        assert_eq!(commands[1].aliases, vec!["db::m"]);
+    }
+
+    #[test]
+    fn test_parse_dump_json() {
+        let json = r#"{
+          "aliases": {"b": {"name": "b", "target": "build", "attributes": [], "private": false}},
+          "recipes": {
+            "build": {
+              "name": "build",
+              "doc": "Build it",
+              "attributes": [{"group": "dev"}, "confirm"],
+              "private": false,
+              "parameters": [
+                {"name": "target", "kind": "singular", "export": false, "default": "debug"},
+                {"name": "rest", "kind": "star", "export": true, "default": null}
+              ],
+              "dependencies": [{"recipe": "clean", "arguments": []}],
+              "body": [["cargo build ", [["variable", "target"]]], ["echo ok"]]
+            },
+            "clean": {
+              "name": "clean", "doc": null, "attributes": ["private"], "private": false,
+              "parameters": [], "dependencies": [], "body": []
+            }
+          },
+          "modules": {
+            "db": {"aliases": {}, "recipes": {"migrate": {"name": "migrate", "parameters": [], "dependencies": [], "body": [], "attributes": [], "private": false}}}
+          }
+        }"#;
+
+        let commands = parse_dump_json(json).unwrap();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "clean", "db::migrate"]);
+
+        let build = &commands[0];
+        assert_eq!(build.description.as_deref(), Some("Build it"));
+        assert_eq!(build.group.as_deref(), Some("dev"));
+        assert!(build.confirm);
+        assert_eq!(build.aliases, vec!["b"]);
+        assert_eq!(build.dependencies, vec!["clean"]);
+        assert_eq!(build.parameters[0].default.as_deref(), Some("debug"));
+        assert!(build.parameters[1].export);
+        assert_eq!(build.recipe, "cargo build {{target}}\necho ok");
+        assert!(commands[1].private);
+    }
+
+    fn recipe(signature: &[(&str, Option<&str>, &str)]) -> JustCommand {
+        JustCommand {
+            name: "migrate".to_string(),
+            parameters: signature
+                .iter()
+                .map(|(name, default, kind)| JustParameter {
+                    name: name.to_string(),
+                    default: default.map(str::to_string),
+                    kind: kind.to_string(),
+                    export: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_bind_recipe_args() {
+        let none = HashMap::new();
+        let command = recipe(&[
+            ("name", None, "singular"),
+            ("env", Some("dev"), "singular"),
+            ("flags", None, "star"),
+        ]);
+
+        assert_eq!(
+            bind_recipe_args(&command, &strings(&["init"]), &none).unwrap(),
+            strings(&["init"])
+        );
+        assert_eq!(
+            bind_recipe_args(&command, &strings(&["init", "prod", "-v", "-x"]), &none).unwrap(),
+            strings(&["init", "prod", "-v", "-x"])
+        );
+
+        let err = bind_recipe_args(&command, &[], &none).unwrap_err();
+        assert!(err.contains("requires argument 'name'"));
+        assert!(err.contains("usage: just migrate name env='dev' *flags"));
+
+        let mut named = HashMap::new();
+        named.insert("name".to_string(), "init".to_string());
+        named.insert("env".to_string(), "prod".to_string());
+        assert_eq!(
+            bind_recipe_args(&command, &[], &named).unwrap(),
+            strings(&["init", "prod"])
+        );
+
+        // Positional and named for the same parameter
+        assert!(bind_recipe_args(&command, &strings(&["init"]), &named).is_err());
+
+        // Skipping a defaulted parameter before a given one is ambiguous
+        let mut named = HashMap::new();
+        named.insert("name".to_string(), "init".to_string());
+        named.insert("flags".to_string(), "-v -x".to_string());
+        assert!(bind_recipe_args(&command, &[], &named)
+            .unwrap_err()
+            .contains("must be given"));
+
+        let mut unknown = HashMap::new();
+        unknown.insert("nope".to_string(), "1".to_string());
+        assert!(bind_recipe_args(&command, &strings(&["init"]), &unknown)
+            .unwrap_err()
+            .contains("no parameter 'nope'"));
+    }
+
+    #[test]
+    fn test_bind_recipe_args_arity() {
+        let none = HashMap::new();
+        let fixed = recipe(&[("name", None, "singular")]);
+        assert!(bind_recipe_args(&fixed, &strings(&["a", "b"]), &none)
+            .unwrap_err()
+            .contains("at most 1"));
+
+        let plus = recipe(&[("files", None, "plus")]);
+        assert!(bind_recipe_args(&plus, &[], &none).is_err());
+        let mut named = HashMap::new();
+        named.insert("files".to_string(), "  ".to_string());
+        assert!(bind_recipe_args(&plus, &[], &named)
+            .unwrap_err()
+            .contains("at least one"));
+        assert_eq!(
+            bind_recipe_args(&plus, &strings(&["a", "b"]), &none).unwrap(),
+            strings(&["a", "b"])
+        );
+    }
+
+    #[test]
+    fn test_prepare_recipe_args() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("justfile"),
+            "alias t := test
+test filter='':
+    cargo test {{filter}}
+",
+        )
+        .unwrap();
+        let dir = temp.path().to_str().unwrap();
+        let none = HashMap::new();
+
+        assert_eq!(
+            prepare_recipe_args(dir, "t", &strings(&["parser"]), &none).unwrap(),
+            strings(&["t", "parser"])
+        );
+        assert!(prepare_recipe_args(dir, "missing", &[], &none)
+            .unwrap_err()
+            .contains("Unknown recipe"));
+    }
+
+    #[test]
+    fn test_find_justfile() {
+        let temp = TempDir::new().unwrap();
+        assert!(find_justfile(temp.path().to_str().unwrap()).is_none());
+        fs::write(temp.path().join(".justfile"), "a:\n").unwrap();
+        let found = find_justfile(temp.path().to_str().unwrap()).unwrap();
+        assert_eq!(found.file_name().unwrap(), ".justfile");
+    }
 }
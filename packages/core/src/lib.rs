@@ -10,7 +10,10 @@ pub mod agent_rules;
 pub mod app_state;
 pub mod archive;
 pub mod claude_cli;
+pub mod compose;
+pub mod connection_info;
 pub mod constitution;
+pub mod container_stats;
 pub mod context;
 pub mod db;
 pub mod explorer;
@@ -20,13 +23,22 @@ pub mod context_sync;
 pub mod docker;
 pub mod env;
 pub mod file_reader;
+pub mod image_pull;
 pub mod justfile;
 pub mod mcp_config;
 pub mod mcp_server;
 pub mod migration;
 pub mod persistence;
+pub mod problem_matcher;
+pub mod readiness;
 pub mod reducer;
+pub mod service_templates;
+pub mod snapshots;
+pub mod sql_console;
 pub mod state;
+pub mod task_runner;
+pub mod task_source;
+pub mod task_watch;
 pub mod terminal;
 pub mod worktree;
 
@@ -38,6 +50,9 @@ use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode,
 use reducer::reduce;
 use state::DockerService;
 use std::sync::{Arc, OnceLock};
+use task_runner::{TaskEvent, TaskRunner};
+use task_watch::TaskWatcher;
+use terminal::TerminalManager;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::sync::{OnceCell, RwLock};
 
@@ -47,6 +62,49 @@ static DOCKER_MANAGER: OnceCell<Arc<DockerManager>> = OnceCell::const_new();
 // Global MCP server manager instance (sync init, doesn't need tokio::OnceCell)
 static MCP_SERVER_MANAGER: OnceLock<Arc<McpServerManager>> = OnceLock::new();
 
+// Global terminal manager instance (PTY sessions for all worktrees)
+static TERMINAL_MANAGER: OnceLock<Arc<TerminalManager>> = OnceLock::new();
+
+// Global task runner (streamed, cancellable task runs keyed by run ID)
+static TASK_RUNNER: OnceLock<Arc<TaskRunner>> = OnceLock::new();
+
+// Global task watcher (watch-mode file watches keyed by worktree and task)
+static TASK_WATCHER: OnceLock<Arc<TaskWatcher>> = OnceLock::new();
+
+// Stats monitors of running containers, keyed by container name
+static STATS_MONITORS: OnceLock<StatsMonitors> = OnceLock::new();
+
+type StatsMonitors = std::sync::Mutex<std::collections::HashMap<String, tokio_util::sync::CancellationToken>>;
+
+// Project the service templates were last loaded for (`Some(None)`: no
+// project open); `None` until the first load
+static TEMPLATES_PROJECT: std::sync::Mutex<Option<Option<String>>> = std::sync::Mutex::new(None);
+
+// Cancellation for the running Docker log follow task (at most one)
+static LOG_FOLLOW: std::sync::Mutex<Option<tokio_util::sync::CancellationToken>> =
+    std::sync::Mutex::new(None);
+
+/// How often followed log lines are flushed into state
+const LOG_FOLLOW_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Number of terminal scrollback lines fed to the context engine
+const TERMINAL_CONTEXT_LINES: usize = 200;
+
+/// Number of failed terminal commands reported as context errors
+const TERMINAL_CONTEXT_FAILURES: usize = 3;
+
+/// Output lines included per failed terminal command
+const TERMINAL_FAILURE_OUTPUT_LINES: usize = 40;
+
+/// Output lines sent to chat when explaining a failed terminal command
+const TERMINAL_EXPLAIN_OUTPUT_LINES: usize = 200;
+
+/// Delay before a replay starts streaming output
+const REPLAY_START_DELAY: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Number of past task runs listed in the Tasks tab
+const TASK_HISTORY_LIMIT: usize = 50;
+
 // Global application state
 static APP_STATE: OnceCell<Arc<RwLock<AppState>>> = OnceCell::const_new();
 
@@ -89,6 +147,745 @@ fn get_mcp_server_manager() -> &'static Arc<McpServerManager> {
     MCP_SERVER_MANAGER.get_or_init(|| Arc::new(McpServerManager::new()))
 }
 
+fn get_task_runner() -> &'static Arc<TaskRunner> {
+    TASK_RUNNER.get_or_init(|| Arc::new(TaskRunner::new()))
+}
+
+fn get_task_watcher() -> &'static Arc<TaskWatcher> {
+    TASK_WATCHER.get_or_init(|| Arc::new(TaskWatcher::new()))
+}
+
+/// Watcher key for a task in a worktree
+fn task_watch_key(cwd: &str, name: &str) -> String {
+    format!("{}\0{}", cwd, name)
+}
+
+fn get_terminal_manager() -> &'static Arc<TerminalManager> {
+    TERMINAL_MANAGER.get_or_init(|| {
+        let manager = Arc::new(TerminalManager::new());
+        // Called from the PTY reader thread when a shell exits on its own
+        manager.set_exit_callback(Arc::new(|session_id| {
+            tokio::spawn(handle_terminal_exit(session_id));
+        }));
+        // Called from the PTY reader thread when shell integration reports a finished command
+        manager.set_command_callback(Arc::new(|session_id, record| {
+            tokio::spawn(handle_terminal_command(session_id, record));
+        }));
+        manager
+    })
+}
+
+/// Mirror a running task's output and exit status into its run in state.
+///
+/// Lines that arrive together are applied in one state update. Problem
+/// matchers run over the output as it arrives (paths relative to `cwd`). When
+/// `run` is given, the finished run is recorded in the task_runs table.
+async fn stream_task_events(
+    run_id: String,
+    name: String,
+    cwd: String,
+    mut events: tokio::sync::mpsc::UnboundedReceiver<TaskEvent>,
+    mut run: Option<db::TaskRunRecord>,
+) {
+    let mut output = db::TaskOutputTail::default();
+    let mut matcher = problem_matcher::DiagnosticCollector::new(&name, &run_id, &cwd);
+    while let Some(event) = events.recv().await {
+        let mut batch = vec![event];
+        while let Ok(event) = events.try_recv() {
+            batch.push(event);
+        }
+
+        let mut finished = None;
+        {
+            let mut state = get_app_state().write().await;
+            let mut matched = false;
+            for event in batch {
+                match event {
+                    TaskEvent::Output { line, .. } => {
+                        output.push(&line);
+                        matched |= matcher.feed(&line);
+                        reduce(&mut state, Action::AppendTaskOutput {
+                            run_id: run_id.clone(),
+                            line,
+                        });
+                    }
+                    TaskEvent::Exited(exit) => {
+                        reduce(&mut state, Action::JustCommandFinished {
+                            run_id: run_id.clone(),
+                            exit_code: exit.code,
+                            cancelled: exit.cancelled,
+                        });
+                        finished = Some(exit);
+                    }
+                }
+            }
+            if matched {
+                reduce(&mut state, Action::SetTaskDiagnostics {
+                    run_id: run_id.clone(),
+                    name: name.clone(),
+                    cwd: cwd.clone(),
+                    diagnostics: matcher
+                        .diagnostics()
+                        .iter()
+                        .map(|d| actions::DiagnosticData {
+                            file: d.file.clone(),
+                            line: d.line,
+                            column: d.column,
+                            severity: d.severity,
+                            message: d.message.clone(),
+                            source: d.source.clone(),
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        if let (Some(exit), Some(mut record)) = (finished, run.take()) {
+            let (text, truncated) = std::mem::take(&mut output).finish();
+            record.finished_at = chrono::Utc::now();
+            record.exit_code = exit.code;
+            record.status = if exit.cancelled {
+                "cancelled"
+            } else if exit.success() {
+                "success"
+            } else {
+                "error"
+            }
+            .to_string();
+            record.output = text;
+            record.output_truncated = truncated;
+            if let Some(db) = get_db_manager() {
+                match db.add_task_run(&record) {
+                    Ok(_) => load_task_history(Some(&record.worktree_path)).await,
+                    Err(e) => eprintln!("Failed to record task run: {}", e),
+                }
+            }
+        }
+        notify_state_update().await;
+    }
+}
+
+/// Spawn a run of a task and stream it into state. Returns the run ID if the
+/// process started; failures are kept visible as a run of their own.
+async fn start_task_run(
+    name: &str,
+    cwd: &str,
+    args: &[String],
+    named_args: &std::collections::HashMap<String, String>,
+) -> Option<String> {
+    let runner = get_task_runner();
+    let run_id = uuid::Uuid::new_v4().to_string();
+    // History belongs to the project owning the worktree, which a watch
+    // may run in while another project is active
+    let project_id = {
+        let state = get_app_state().read().await;
+        state
+            .projects
+            .iter()
+            .find(|p| p.worktrees.iter().any(|w| w.path == cwd))
+            .or_else(|| state.active_project())
+            .map(|p| persistence::get_project_id(&p.path))
+    };
+    let spawned = task_source::prepare_task(cwd, name, args, named_args).and_then(
+        |(program, argv)| {
+            let events = runner.spawn(&run_id, &program, &argv, cwd)?;
+            Ok((format!("{} {}", program, argv.join(" ")), events))
+        },
+    );
+    let mut state = get_app_state().write().await;
+    match spawned {
+        Ok((command_line, events)) => {
+            reduce(&mut state, Action::TaskRunStarted {
+                run_id: run_id.clone(),
+                name: name.to_string(),
+                cwd: cwd.to_string(),
+                command_line: command_line.clone(),
+            });
+            let now = chrono::Utc::now();
+            let run = project_id.map(|project_id| db::TaskRunRecord {
+                id: run_id.clone(),
+                project_id,
+                worktree_path: cwd.to_string(),
+                task_name: name.to_string(),
+                command_line,
+                args: args.to_vec(),
+                named_args: named_args.clone(),
+                started_at: now,
+                finished_at: now,
+                exit_code: None,
+                status: String::new(),
+                output: String::new(),
+                output_truncated: false,
+            });
+            tokio::spawn(stream_task_events(
+                run_id.clone(),
+                name.to_string(),
+                cwd.to_string(),
+                events,
+                run,
+            ));
+            Some(run_id)
+        }
+        Err(e) => {
+            // Keep the failure visible as a run of its own
+            reduce(&mut state, Action::TaskRunStarted {
+                run_id: run_id.clone(),
+                name: name.to_string(),
+                cwd: cwd.to_string(),
+                command_line: name.to_string(),
+            });
+            reduce(&mut state, Action::AppendTaskOutput {
+                run_id: run_id.clone(),
+                line: e,
+            });
+            reduce(&mut state, Action::SetTaskStatus {
+                run_id,
+                status: actions::TaskStatusData::Error,
+            });
+            None
+        }
+    }
+}
+
+/// Wait for a service's readiness probe, tracking its health in state
+async fn wait_for_service_ready(service_id: &str, timeout: std::time::Duration) -> Result<(), String> {
+    let dm = get_docker_manager().await.map_err(|e| e.to_string())?;
+    set_service_health(service_id, Some(app_state::ServiceHealth::Starting)).await;
+    let result = dm.wait_until_ready(service_id, timeout).await;
+    // Only the probe or Docker says a service is unhealthy; one still not
+    // ready at the timeout stays Starting, and a stopped container has no
+    // health to report
+    let health = match &result {
+        Ok(()) => Some(app_state::ServiceHealth::Healthy),
+        Err(_) => dm.check_health(service_id).await.ok(),
+    };
+    set_service_health(service_id, health).await;
+    result
+}
+
+async fn set_service_health(service_id: &str, health: Option<app_state::ServiceHealth>) {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetDockerServiceHealth {
+            service_id: service_id.to_string(),
+            health,
+        });
+    }
+    notify_state_update().await;
+}
+
+/// Start a service (on `port`, if given) and watch its readiness
+///
+/// The image is pulled first so its progress shows on the service; pull
+/// failures are reported as `DOCKER_PULL_ERROR`, start failures as
+/// `DOCKER_START_ERROR` with `context`.
+async fn start_docker_service_internal(service_id: &str, port: Option<u16>, context: String) {
+    if pull_service_image(service_id, false).await.is_err() {
+        return;
+    }
+    let started = match port {
+        Some(port) => docker_start_service_with_port(service_id.to_string(), port).await,
+        None => docker_start_service(service_id.to_string()).await,
+    };
+    match started {
+        Ok(()) => {
+            refresh_docker_services_internal().await;
+            watch_service_readiness(service_id);
+        }
+        Err(e) => {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetError {
+                code: "DOCKER_START_ERROR".to_string(),
+                message: e.to_string(),
+                context: Some(context),
+            });
+        }
+    }
+}
+
+/// Pull a service's image, tracking the pull's progress on the service
+///
+/// Without `force`, an image that is already present isn't pulled again.
+/// Returns whether the local image changed; failures are reported as
+/// `DOCKER_PULL_ERROR`.
+async fn pull_service_image(service_id: &str, force: bool) -> Result<bool, String> {
+    use futures_util::StreamExt;
+
+    let result: Result<bool, String> = async {
+        let dm = get_docker_manager().await.map_err(|e| e.to_string())?;
+        let image = dm.service_image(service_id).await?;
+        let before = dm.image_id(&image).await;
+        if before.is_some() && !force {
+            return Ok(false);
+        }
+
+        let mut pull = image_pull::PullProgress::default();
+        let mut last_sent: Option<tokio::time::Instant> = None;
+        let stream = dm.pull_image(&image);
+        tokio::pin!(stream);
+        while let Some(info) = stream.next().await {
+            pull.update(&info?)
+                .map_err(|e| format!("Failed to pull image {}: {}", image, e))?;
+            if last_sent.is_some_and(|t| t.elapsed() < image_pull::PULL_PROGRESS_INTERVAL) {
+                continue;
+            }
+            last_sent = Some(tokio::time::Instant::now());
+            set_image_pull(service_id, Some(app_state::ImagePull {
+                image: image.clone(),
+                progress: pull.fraction(),
+                layers_done: pull.layers_done(),
+                layers_total: pull.layers_total(),
+                status: pull.status(),
+            }))
+            .await;
+        }
+        Ok(dm.image_id(&image).await != before)
+    }
+    .await;
+
+    set_image_pull(service_id, None).await;
+    if let Err(e) = &result {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetError {
+            code: "DOCKER_PULL_ERROR".to_string(),
+            message: e.clone(),
+            context: Some(format!("Pull image for {}", service_id)),
+        });
+    }
+    result
+}
+
+/// Recreate an rstn-managed container on its freshly pulled image,
+/// starting it again if it was running
+///
+/// Only done when the service's data is in a named volume or bind mount;
+/// otherwise the user is told to recreate it themselves. Returns the
+/// notification to show, or None when a failure was reported as
+/// `DOCKER_START_ERROR`.
+async fn recreate_on_new_image(
+    service_id: &str,
+    was_running: bool,
+    port: Option<u16>,
+) -> Option<(String, actions::NotificationTypeData)> {
+    let context = format!("UpdateDockerImage: {}", service_id);
+    let removed = match get_docker_manager().await {
+        Ok(dm) => match dm.data_survives_recreate(service_id).await {
+            Ok(true) => dm.remove_service(service_id).await.map(|()| true),
+            Ok(false) => Ok(false),
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e.to_string()),
+    };
+    match removed {
+        Ok(true) => {
+            if was_running {
+                start_docker_service_internal(service_id, port, context).await;
+            } else {
+                refresh_docker_services_internal().await;
+            }
+            Some((
+                format!("Updated the image of {}", service_id),
+                actions::NotificationTypeData::Success,
+            ))
+        }
+        Ok(false) => Some((
+            format!(
+                "Pulled a new image for {}, but its data is not in a named volume or bind mount; \
+                 remove and start it again to use the new image",
+                service_id
+            ),
+            actions::NotificationTypeData::Warning,
+        )),
+        Err(e) => {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetError {
+                code: "DOCKER_START_ERROR".to_string(),
+                message: e,
+                context: Some(context),
+            });
+            None
+        }
+    }
+}
+
+async fn set_image_pull(service_id: &str, pull: Option<app_state::ImagePull>) {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetDockerImagePull {
+            service_id: service_id.to_string(),
+            pull,
+        });
+    }
+    notify_state_update().await;
+}
+
+/// Probe a just-started service in the background until it is ready
+fn watch_service_readiness(service_id: &str) {
+    let service_id = service_id.to_string();
+    tokio::spawn(async move {
+        if let Err(e) = wait_for_service_ready(&service_id, readiness::READY_TIMEOUT).await {
+            tracing::warn!("{}", e);
+        }
+    });
+}
+
+fn stats_monitors() -> &'static StatsMonitors {
+    STATS_MONITORS.get_or_init(Default::default)
+}
+
+/// Monitor resource usage of running services, stopping monitors of
+/// services that are no longer running
+async fn sync_stats_monitors() {
+    let running: Vec<String> = {
+        let state = get_app_state().read().await;
+        state
+            .docker
+            .services
+            .iter()
+            .filter(|s| s.status == app_state::ServiceStatus::Running)
+            .map(|s| s.id.clone())
+            .collect()
+    };
+
+    let mut monitors = stats_monitors().lock().unwrap();
+    monitors.retain(|service_id, cancel| {
+        let keep = running.contains(service_id);
+        if !keep {
+            cancel.cancel();
+        }
+        keep
+    });
+    for service_id in running {
+        if !monitors.contains_key(&service_id) {
+            let cancel = tokio_util::sync::CancellationToken::new();
+            monitors.insert(service_id.clone(), cancel.clone());
+            tokio::spawn(monitor_service_stats(service_id, cancel));
+        }
+    }
+}
+
+/// Push a container's resource usage into state every `STATS_INTERVAL`
+/// until cancelled or the container stops
+async fn monitor_service_stats(service_id: String, cancel: tokio_util::sync::CancellationToken) {
+    use futures_util::StreamExt;
+
+    let Ok(dm) = get_docker_manager().await else {
+        return;
+    };
+    let stream = dm.stream_stats(&service_id);
+    tokio::pin!(stream);
+    let mut last_sent: Option<tokio::time::Instant> = None;
+
+    loop {
+        let sample = tokio::select! {
+            _ = cancel.cancelled() => return,
+            sample = stream.next() => sample,
+        };
+        let Some(Ok(sample)) = sample else {
+            break;
+        };
+        if last_sent.is_some_and(|t| t.elapsed() < container_stats::STATS_INTERVAL) {
+            continue;
+        }
+        last_sent = Some(tokio::time::Instant::now());
+        {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetDockerServiceStats {
+                service_id: service_id.clone(),
+                stats: Some(actions::ServiceStatsData {
+                    cpu_percent: sample.cpu_percent,
+                    memory_usage: sample.memory_usage,
+                    memory_limit: sample.memory_limit,
+                    net_rx_bytes: sample.net_rx_bytes,
+                    net_tx_bytes: sample.net_tx_bytes,
+                    block_read_bytes: sample.block_read_bytes,
+                    block_write_bytes: sample.block_write_bytes,
+                }),
+            });
+        }
+        notify_state_update().await;
+    }
+
+    // Stream ended with the container: let the next sync start a new monitor.
+    // A replacement monitor may already be registered under this service.
+    cancel.cancel();
+    let mut monitors = stats_monitors().lock().unwrap();
+    if monitors.get(&service_id).is_some_and(|token| token.is_cancelled()) {
+        monitors.remove(&service_id);
+    }
+}
+
+/// Cancel the running Docker log follow task, if any
+fn stop_log_follow() {
+    if let Some(cancel) = LOG_FOLLOW.lock().unwrap().take() {
+        cancel.cancel();
+    }
+}
+
+/// Stream a container's logs into state in batches until cancelled or the
+/// container's log stream ends.
+async fn follow_docker_logs(
+    service_id: String,
+    options: docker::LogFollowOptions,
+    filter: Option<String>,
+    cancel: tokio_util::sync::CancellationToken,
+) {
+    use futures_util::StreamExt;
+
+    let dm = match get_docker_manager().await {
+        Ok(dm) => dm,
+        Err(e) => {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetError {
+                code: "DOCKER_LOGS_ERROR".to_string(),
+                message: e.to_string(),
+                context: Some(format!("FollowDockerLogs: {}", service_id)),
+            });
+            reduce(&mut state, Action::DockerLogStreamEnded { service_id });
+            drop(state);
+            notify_state_update().await;
+            return;
+        }
+    };
+
+    let filter = filter.map(|f| f.to_lowercase()).unwrap_or_default();
+    let stream = dm.follow_logs(&service_id, &options);
+    tokio::pin!(stream);
+    let mut flush = tokio::time::interval(LOG_FOLLOW_FLUSH_INTERVAL);
+    let mut pending = Vec::new();
+    let mut error = None;
+
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            chunk = stream.next() => match chunk {
+                Some(Ok(lines)) => pending.extend(lines.into_iter().filter(|l| l.matches(&filter))),
+                Some(Err(e)) => {
+                    error = Some(e);
+                    break;
+                }
+                None => break,
+            },
+            _ = flush.tick() => {
+                if !pending.is_empty() {
+                    append_docker_log_lines(&service_id, std::mem::take(&mut pending)).await;
+                }
+            }
+        }
+    }
+
+    if cancel.is_cancelled() {
+        return;
+    }
+    append_docker_log_lines(&service_id, pending).await;
+    {
+        let mut state = get_app_state().write().await;
+        if let Some(message) = error {
+            reduce(&mut state, Action::SetError {
+                code: "DOCKER_LOGS_ERROR".to_string(),
+                message,
+                context: Some(format!("FollowDockerLogs: {}", service_id)),
+            });
+        }
+        reduce(&mut state, Action::DockerLogStreamEnded { service_id });
+    }
+    notify_state_update().await;
+}
+
+async fn append_docker_log_lines(service_id: &str, lines: Vec<docker::LogLine>) {
+    if lines.is_empty() {
+        return;
+    }
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::AppendDockerLogLines {
+            service_id: service_id.to_string(),
+            lines: lines
+                .into_iter()
+                .map(|line| actions::DockerLogLineData {
+                    stream: line.stream,
+                    timestamp: line.timestamp,
+                    text: line.text,
+                })
+                .collect(),
+        });
+    }
+    notify_state_update().await;
+}
+
+/// Re-run a watched task for each batch of changed files until its watch
+/// is stopped or replaced, or its worktree is gone.
+///
+/// Without `restart`, a run still going is waited for; changes arriving
+/// meanwhile are folded into the next run.
+async fn drive_task_watch(
+    name: String,
+    cwd: String,
+    mut changes: tokio::sync::mpsc::UnboundedReceiver<Vec<String>>,
+) {
+    let runner = get_task_runner();
+    while let Some(mut changed) = changes.recv().await {
+        let (watch, running) = {
+            let state = get_app_state().read().await;
+            let Some(tasks) = state
+                .projects
+                .iter()
+                .flat_map(|p| p.worktrees.iter())
+                .find(|w| w.path == cwd)
+                .map(|w| &w.tasks)
+            else {
+                get_task_watcher().stop(&task_watch_key(&cwd, &name));
+                break;
+            };
+            let Some(watch) = tasks.watches.iter().find(|w| w.name == name) else {
+                break;
+            };
+            let running: Vec<String> = tasks
+                .runs
+                .iter()
+                .filter(|r| r.name == name && r.status == app_state::TaskStatus::Running)
+                .map(|r| r.id.clone())
+                .collect();
+            (watch.clone(), running)
+        };
+
+        if watch.restart {
+            for run_id in &running {
+                runner.cancel(run_id);
+            }
+        }
+        while running.iter().any(|run_id| runner.is_running(run_id)) {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        while let Ok(more) = changes.try_recv() {
+            changed.extend(more);
+        }
+        changed.sort();
+        changed.dedup();
+
+        {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::TaskWatchTriggered {
+                name: name.clone(),
+                cwd: cwd.clone(),
+                changed,
+            });
+        }
+        start_task_run(&name, &cwd, &watch.args, &watch.named_args).await;
+        notify_state_update().await;
+    }
+}
+
+/// Project ID and path of the active worktree
+async fn active_worktree_key() -> Option<(String, String)> {
+    let state = get_app_state().read().await;
+    let project = state.active_project()?;
+    let worktree = project.active_worktree()?;
+    Some((persistence::get_project_id(&project.path), worktree.path.clone()))
+}
+
+/// Load run history and stats for the active worktree.
+///
+/// With `only_worktree`, nothing is loaded unless that worktree is active.
+async fn load_task_history(only_worktree: Option<&str>) {
+    let Some(db) = get_db_manager() else {
+        return;
+    };
+    let Some((project_id, worktree_path)) = active_worktree_key().await else {
+        return;
+    };
+    if only_worktree.is_some_and(|path| path != worktree_path) {
+        return;
+    }
+
+    let runs = db.get_task_runs(&project_id, &worktree_path, TASK_HISTORY_LIMIT);
+    let stats = db.get_task_run_stats(&project_id, &worktree_path);
+    let mut state = get_app_state().write().await;
+    match (runs, stats) {
+        (Ok(runs), Ok(stats)) => {
+            reduce(&mut state, Action::SetTaskHistory {
+                runs: runs.into_iter().map(task_run_data).collect(),
+                stats: stats
+                    .into_iter()
+                    .map(|s| actions::TaskRunStatsData {
+                        name: s.task_name,
+                        runs: s.runs,
+                        failures: s.failures,
+                        flips: s.flips,
+                        flaky: s.flaky,
+                        avg_duration_ms: s.avg_duration_ms,
+                        max_duration_ms: s.max_duration_ms,
+                        last_duration_ms: s.last_duration_ms,
+                    })
+                    .collect(),
+            });
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            reduce(&mut state, Action::SetTasksError {
+                error: Some(format!("Failed to load task history: {}", e)),
+            });
+        }
+    }
+}
+
+fn task_run_data(row: db::TaskRunRow) -> actions::TaskRunData {
+    actions::TaskRunData {
+        id: row.id,
+        name: row.task_name,
+        command_line: row.command_line,
+        args: row.args,
+        named_args: row.named_args,
+        started_at: row.started_at,
+        finished_at: row.finished_at,
+        duration_ms: row.duration_ms,
+        exit_code: row.exit_code,
+        status: match row.status.as_str() {
+            "success" => actions::TaskStatusData::Success,
+            "cancelled" => actions::TaskStatusData::Cancelled,
+            _ => actions::TaskStatusData::Error,
+        },
+        output: row.output,
+        output_truncated: row.output_truncated,
+    }
+}
+
+/// Mirror a finished terminal command into state
+async fn handle_terminal_command(session_id: String, record: terminal::CommandRecord) {
+    let Some(state_cell) = APP_STATE.get() else {
+        return;
+    };
+    {
+        let mut state = state_cell.write().await;
+        reduce(&mut state, Action::TerminalCommandFinished {
+            session_id,
+            command: actions::TerminalCommandData {
+                id: record.id,
+                command: record.command,
+                cwd: record.cwd,
+                started_at: record.started_at,
+                duration_ms: record.duration_ms,
+                exit_code: record.exit_code,
+                output_start: record.output_start,
+                output_end: record.output_end,
+            },
+        });
+    }
+    notify_state_update().await;
+}
+
+/// Drop an exited session and clear it from state
+async fn handle_terminal_exit(session_id: String) {
+    let _ = get_terminal_manager().kill(&session_id).await;
+    let Some(state_cell) = APP_STATE.get() else {
+        return;
+    };
+    {
+        let mut state = state_cell.write().await;
+        reduce(&mut state, Action::TerminalExited { session_id });
+    }
+    notify_state_update().await;
+}
+
 /// Read context files and format them for Claude prompt injection
 fn build_context_files_section(paths: &[String], project_root: &str) -> String {
     if paths.is_empty() {
@@ -177,48 +974,169 @@ pub async fn docker_stop_service(service_id: String) -> napi::Result<()> {
 #[napi]
 pub async fn docker_restart_service(service_id: String) -> napi::Result<()> {
     let dm = get_docker_manager().await?;
-    dm.restart_service(&service_id)
+    dm.restart_service(&service_id)
+        .await
+        .map_err(napi::Error::from_reason)
+}
+
+/// Wait until a Docker service accepts connections (default timeout: 120s)
+#[napi]
+pub async fn docker_wait_until_ready(service_id: String, timeout_ms: Option<u32>) -> napi::Result<()> {
+    let timeout = timeout_ms
+        .map(|ms| std::time::Duration::from_millis(ms as u64))
+        .unwrap_or(readiness::READY_TIMEOUT);
+    wait_for_service_ready(&service_id, timeout)
+        .await
+        .map_err(napi::Error::from_reason)
+}
+
+/// Get container logs
+#[napi]
+pub async fn docker_get_logs(service_id: String, tail: Option<u32>) -> napi::Result<Vec<String>> {
+    let dm = get_docker_manager().await?;
+    let tail = tail.unwrap_or(100) as usize;
+    dm.get_logs(&service_id, tail)
+        .await
+        .map_err(napi::Error::from_reason)
+}
+
+/// Remove a Docker service
+#[napi]
+pub async fn docker_remove_service(service_id: String) -> napi::Result<()> {
+    let dm = get_docker_manager().await?;
+    dm.remove_service(&service_id)
+        .await
+        .map_err(napi::Error::from_reason)
+}
+
+/// Create a database in a database container
+/// Returns the connection string for the new database
+#[napi]
+pub async fn docker_create_database(service_id: String, db_name: String) -> napi::Result<String> {
+    let dm = get_docker_manager().await?;
+    dm.create_database(&service_id, &db_name)
+        .await
+        .map_err(napi::Error::from_reason)
+}
+
+/// Create a vhost in RabbitMQ
+/// Returns the connection string for the new vhost
+#[napi]
+pub async fn docker_create_vhost(service_id: String, vhost_name: String) -> napi::Result<String> {
+    let dm = get_docker_manager().await?;
+    dm.create_vhost(&service_id, &vhost_name)
         .await
         .map_err(napi::Error::from_reason)
 }
 
-/// Get container logs
+/// Snapshot a database in a database container
+/// Returns the new snapshot's ID
 #[napi]
-pub async fn docker_get_logs(service_id: String, tail: Option<u32>) -> napi::Result<Vec<String>> {
+pub async fn docker_create_snapshot(service_id: String, database: Option<String>) -> napi::Result<String> {
     let dm = get_docker_manager().await?;
-    let tail = tail.unwrap_or(100) as usize;
-    dm.get_logs(&service_id, tail)
+    dm.create_snapshot(&service_id, database.as_deref())
         .await
+        .map(|snapshot| snapshot.id)
         .map_err(napi::Error::from_reason)
 }
 
-/// Remove a Docker service
+/// Restore a snapshot into a new database
+/// Returns the connection string for the restored database
 #[napi]
-pub async fn docker_remove_service(service_id: String) -> napi::Result<()> {
+pub async fn docker_restore_snapshot(
+    service_id: String,
+    snapshot_id: String,
+    target_database: Option<String>,
+) -> napi::Result<String> {
     let dm = get_docker_manager().await?;
-    dm.remove_service(&service_id)
+    dm.restore_snapshot(&service_id, &snapshot_id, target_database.as_deref())
         .await
         .map_err(napi::Error::from_reason)
 }
 
-/// Create a database in a database container
-/// Returns the connection string for the new database
+/// List the databases, schemas and tables (with columns) of a Postgres or
+/// MySQL service
 #[napi]
-pub async fn docker_create_database(service_id: String, db_name: String) -> napi::Result<String> {
-    let dm = get_docker_manager().await?;
-    dm.create_database(&service_id, &db_name)
+pub async fn docker_describe_database(
+    service_id: String,
+    database: Option<String>,
+) -> napi::Result<serde_json::Value> {
+    let schema = describe_database(&service_id, database.as_deref())
         .await
-        .map_err(napi::Error::from_reason)
+        .map_err(napi::Error::from_reason)?;
+    serde_json::to_value(schema).map_err(|e| napi::Error::from_reason(e.to_string()))
 }
 
-/// Create a vhost in RabbitMQ
-/// Returns the connection string for the new vhost
+/// Run an ad-hoc query in a Postgres or MySQL service
+/// Returns typed rows (default limit: 500 rows, timeout: 30s)
 #[napi]
-pub async fn docker_create_vhost(service_id: String, vhost_name: String) -> napi::Result<String> {
+pub async fn docker_run_sql(
+    service_id: String,
+    database: Option<String>,
+    sql: String,
+    limit: Option<u32>,
+    timeout_ms: Option<u32>,
+) -> napi::Result<serde_json::Value> {
+    let result = run_sql_query(
+        &service_id,
+        database.as_deref(),
+        &sql,
+        limit.map(|l| l as usize),
+        timeout_ms.map(|ms| std::time::Duration::from_millis(ms as u64)),
+    )
+    .await
+    .map_err(napi::Error::from_reason)?;
+    serde_json::to_value(result).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Schema of a database service (shared by the SQL console and MCP tools)
+async fn describe_database(service_id: &str, database: Option<&str>) -> Result<app_state::SqlSchema, String> {
+    let dm = get_docker_manager().await.map_err(|e| e.to_string())?;
+    dm.describe_database(service_id, database).await
+}
+
+/// Run a query in a database service with the console's defaults for
+/// missing limits (shared by the SQL console and MCP tools)
+async fn run_sql_query(
+    service_id: &str,
+    database: Option<&str>,
+    sql: &str,
+    limit: Option<usize>,
+    timeout: Option<std::time::Duration>,
+) -> Result<app_state::SqlQueryResult, String> {
+    let dm = get_docker_manager().await.map_err(|e| e.to_string())?;
+    dm.run_sql(
+        service_id,
+        database,
+        sql,
+        sql_console::row_limit(limit),
+        timeout.unwrap_or(sql_console::DEFAULT_QUERY_TIMEOUT),
+    )
+    .await
+}
+
+/// How to connect to a service from the host: URL, host, mapped port,
+/// credentials and the env var apps read it from
+#[napi]
+pub async fn docker_connection_info(service_id: String) -> napi::Result<serde_json::Value> {
     let dm = get_docker_manager().await?;
-    dm.create_vhost(&service_id, &vhost_name)
+    let info = dm
+        .connection_info(&service_id)
         .await
-        .map_err(napi::Error::from_reason)
+        .map_err(napi::Error::from_reason)?;
+    serde_json::to_value(info).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Upsert a service's connection URL into `<worktree_path>/.env`
+async fn export_connection_info(
+    service_id: &str,
+    worktree_path: &str,
+) -> Result<app_state::ServiceConnectionInfo, String> {
+    let dm = get_docker_manager().await.map_err(|e| e.to_string())?;
+    let info = dm.connection_info(service_id).await?;
+    let env_file = std::path::Path::new(worktree_path).join(".env");
+    env::upsert_dotenv(&env_file, &info.env_var, &info.url)?;
+    Ok(info)
 }
 
 /// Start a Docker service with a specific port override
@@ -433,6 +1351,122 @@ pub fn env_default_patterns() -> Vec<String> {
     env::default_patterns()
 }
 
+// ============================================================================
+// Terminal functions
+// ============================================================================
+
+/// Register a listener for PTY output.
+///
+/// The callback receives raw output bytes for every terminal session.
+/// Terminal output is streamed separately from state updates to keep
+/// the state JSON small.
+#[napi]
+pub fn terminal_set_output_listener(
+    #[napi(ts_arg_type = "(err: Error | null, sessionId: string, data: Buffer) => void")]
+    callback: napi::JsFunction,
+) -> napi::Result<()> {
+    let tsfn: ThreadsafeFunction<(String, Vec<u8>)> = callback.create_threadsafe_function(
+        0,
+        |ctx: ThreadSafeCallContext<(String, Vec<u8>)>| {
+            let (session_id, data) = ctx.value;
+            Ok(vec![
+                ctx.env.create_string(&session_id)?.into_unknown(),
+                ctx.env.create_buffer_with_data(data)?.into_raw().into_unknown(),
+            ])
+        },
+    )?;
+
+    get_terminal_manager().set_output_callback(Arc::new(move |session_id, data| {
+        tsfn.call(Ok((session_id, data)), ThreadsafeFunctionCallMode::NonBlocking);
+    }));
+
+    Ok(())
+}
+
+/// Scrollback page for napi export
+#[napi(object)]
+pub struct NapiScrollbackPage {
+    /// Line number of the first returned line
+    pub start: i64,
+    pub lines: Vec<String>,
+    /// Oldest line number still available
+    pub first_line: i64,
+    pub total_lines: i64,
+}
+
+/// Scrollback search match for napi export
+#[napi(object)]
+pub struct NapiScrollbackMatch {
+    pub line: i64,
+    /// Line text with escape sequences removed
+    pub text: String,
+    /// Byte range of the match within `text`
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Get a page of a terminal session's scrollback.
+///
+/// Without `start`, returns the last `count` lines. Raw lines keep escape
+/// sequences so the renderer can replay them; pass `plain` to strip them.
+#[napi]
+pub fn terminal_get_scrollback(
+    session_id: String,
+    start: Option<i64>,
+    count: u32,
+    plain: Option<bool>,
+) -> napi::Result<NapiScrollbackPage> {
+    let page = get_terminal_manager()
+        .scrollback_page(
+            &session_id,
+            start.map(|s| s.max(0) as u64),
+            count as usize,
+            plain.unwrap_or(false),
+        )
+        .map_err(napi::Error::from_reason)?;
+
+    Ok(NapiScrollbackPage {
+        start: page.start as i64,
+        lines: page.lines,
+        first_line: page.first_line as i64,
+        total_lines: page.total_lines as i64,
+    })
+}
+
+/// Get the last `count` lines of a terminal session as plain text
+#[napi]
+pub fn terminal_get_last_lines(session_id: String, count: u32) -> napi::Result<Vec<String>> {
+    get_terminal_manager()
+        .scrollback_last_lines(&session_id, count as usize)
+        .map_err(napi::Error::from_reason)
+}
+
+/// Regex-search a terminal session's scrollback (most recent `limit` matches)
+#[napi]
+pub fn terminal_search_scrollback(
+    session_id: String,
+    pattern: String,
+    limit: Option<u32>,
+) -> napi::Result<Vec<NapiScrollbackMatch>> {
+    let matches = get_terminal_manager()
+        .search_scrollback(
+            &session_id,
+            &pattern,
+            limit.map_or(terminal::DEFAULT_SEARCH_LIMIT, |l| l as usize),
+        )
+        .map_err(napi::Error::from_reason)?;
+
+    Ok(matches
+        .into_iter()
+        .map(|m| NapiScrollbackMatch {
+            line: m.line as i64,
+            text: m.text,
+            start: m.start as u32,
+            end: m.end as u32,
+        })
+        .collect())
+}
+
 // ============================================================================
 // MCP functions
 // ============================================================================
@@ -533,6 +1567,9 @@ pub struct NapiFileContext {
 /// Build AI context for a project path
 ///
 /// Gathers context from git, files, and other sources within a token budget.
+/// When `task_output` is absent, recent output of `terminal_session_id` is used;
+/// its failed commands and the worktree's task diagnostics are reported as
+/// active errors.
 #[napi]
 pub fn context_build(
     project_path: String,
@@ -540,15 +1577,21 @@ pub fn context_build(
     task_output: Option<String>,
     docker_errors: Vec<String>,
     token_budget: Option<u32>,
+    terminal_session_id: Option<String>,
 ) -> NapiAIContext {
     let budget = token_budget.unwrap_or(20000) as usize;
     let path = std::path::Path::new(&project_path);
+    let task_output = task_output.or_else(|| terminal_context_output(terminal_session_id.as_deref()));
+    let command_failures = terminal_context_failures(terminal_session_id.as_deref());
+    let diagnostics = worktree_diagnostics(&project_path);
 
     let context = context_engine::build_context(
         path,
         active_files,
         task_output,
         docker_errors,
+        command_failures,
+        diagnostics,
         budget,
     );
 
@@ -566,6 +1609,58 @@ pub fn context_build(
     }
 }
 
+/// Recent plain-text output of a terminal session for the context engine
+fn terminal_context_output(session_id: Option<&str>) -> Option<String> {
+    let lines = get_terminal_manager()
+        .scrollback_last_lines(session_id?, TERMINAL_CONTEXT_LINES)
+        .ok()?;
+    let output = lines.join("\n");
+    (!output.trim().is_empty()).then_some(output)
+}
+
+/// Recent failed commands of a terminal session, formatted as context errors
+fn terminal_context_failures(session_id: Option<&str>) -> Vec<String> {
+    let Some(session_id) = session_id else {
+        return Vec::new();
+    };
+    get_terminal_manager()
+        .failed_commands(session_id, TERMINAL_CONTEXT_FAILURES, TERMINAL_FAILURE_OUTPUT_LINES)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(record, output)| format_command_failure(&record, &output))
+        .collect()
+}
+
+/// Task diagnostics of the worktree at `worktree_path` (empty if unknown)
+fn worktree_diagnostics(worktree_path: &str) -> Vec<problem_matcher::Diagnostic> {
+    let Some(state) = APP_STATE.get().and_then(|state| state.try_read().ok()) else {
+        return Vec::new();
+    };
+    state
+        .projects
+        .iter()
+        .flat_map(|p| p.worktrees.iter())
+        .find(|w| w.path == worktree_path)
+        .map(|w| w.diagnostics.clone())
+        .unwrap_or_default()
+}
+
+/// Describe a failed terminal command and its output
+fn format_command_failure(record: &terminal::CommandRecord, output: &[String]) -> String {
+    let mut text = format!(
+        "Command `{}` exited with code {}",
+        record.command,
+        record.exit_code.unwrap_or_default()
+    );
+    if let Some(ref cwd) = record.cwd {
+        text.push_str(&format!(" (in {})", cwd));
+    }
+    if !output.is_empty() {
+        text.push_str(&format!(":\n{}", output.join("\n")));
+    }
+    text
+}
+
 /// Build AI context and format as a system prompt string
 #[napi]
 pub fn context_build_system_prompt(
@@ -574,15 +1669,21 @@ pub fn context_build_system_prompt(
     task_output: Option<String>,
     docker_errors: Vec<String>,
     token_budget: Option<u32>,
+    terminal_session_id: Option<String>,
 ) -> String {
     let budget = token_budget.unwrap_or(20000) as usize;
     let path = std::path::Path::new(&project_path);
+    let task_output = task_output.or_else(|| terminal_context_output(terminal_session_id.as_deref()));
+    let command_failures = terminal_context_failures(terminal_session_id.as_deref());
+    let diagnostics = worktree_diagnostics(&project_path);
 
     let context = context_engine::build_context(
         path,
         active_files,
         task_output,
         docker_errors,
+        command_failures,
+        diagnostics,
         budget,
     );
 
@@ -687,11 +1788,219 @@ pub async fn state_dispatch(action_json: String) -> napi::Result<()> {
     Ok(())
 }
 
+/// Active project path, if any
+async fn active_project_path() -> Option<String> {
+    let state = get_app_state().read().await;
+    state.active_project().map(|p| p.path.clone())
+}
+
+/// Load service templates unless they are already loaded for the active
+/// project (so services.yaml is read on project open/switch, not on every
+/// refresh)
+async fn ensure_service_templates() {
+    let project_path = active_project_path().await;
+    if TEMPLATES_PROJECT.lock().unwrap().as_ref() != Some(&project_path) {
+        reload_service_templates().await;
+    }
+}
+
+/// Load service templates (built-in, ~/.rstn and the active project's
+/// .rstn/services.yaml) into the Docker manager
+async fn reload_service_templates() {
+    let Ok(dm) = get_docker_manager().await else {
+        return;
+    };
+    let project_path = active_project_path().await;
+    let (templates, errors) =
+        service_templates::load_templates(project_path.as_deref().map(std::path::Path::new));
+    dm.set_templates(templates);
+    *TEMPLATES_PROJECT.lock().unwrap() = Some(project_path);
+    if !errors.is_empty() {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetError {
+            code: "SERVICE_TEMPLATE_ERROR".to_string(),
+            message: errors.join("\n"),
+            context: Some("LoadServiceTemplates".to_string()),
+        });
+    }
+}
+
+/// Create the database for a new worktree (if enabled for the project) and
+/// write its connection string into the worktree's `.env`
+async fn create_worktree_database(worktree_path: &str, branch: &str) {
+    let (config, project_name) = {
+        let state = get_app_state().read().await;
+        match state.active_project() {
+            Some(project) => (project.env_config.worktree_db.clone(), project.name.clone()),
+            None => return,
+        }
+    };
+    if !config.enabled {
+        return;
+    }
+
+    // Names other worktrees' databases already have
+    let taken: Vec<String> = config
+        .databases
+        .iter()
+        .filter(|(path, _)| *path != worktree_path)
+        .map(|(_, database)| database.name.clone())
+        .collect();
+    let name = worktree::database_name(&project_name, branch, &taken);
+    let created = match get_docker_manager().await {
+        Ok(dm) => match dm.wait_until_ready(&config.service_id, readiness::READY_TIMEOUT).await {
+            Ok(()) => match &config.template_database {
+                Some(template) => dm.clone_database(&config.service_id, template, &name).await,
+                None => dm.create_database(&config.service_id, &name).await,
+            },
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e.to_string()),
+    };
+    let connection_string = match created {
+        Ok(connection_string) => connection_string,
+        Err(e) => {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetError {
+                code: "WORKTREE_DB_ERROR".to_string(),
+                message: e,
+                context: Some(format!("Create database {} for {}", name, worktree_path)),
+            });
+            return;
+        }
+    };
+
+    let env_file = std::path::Path::new(worktree_path).join(".env");
+    let written = env::upsert_dotenv(&env_file, &config.env_var, &connection_string);
+
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::SetWorktreeDatabase {
+        worktree_path: worktree_path.to_string(),
+        database: Some(actions::WorktreeDatabaseData {
+            service_id: config.service_id,
+            name,
+            connection_string,
+        }),
+    });
+    if let Err(e) = written {
+        reduce(&mut state, Action::SetError {
+            code: "WORKTREE_DB_ERROR".to_string(),
+            message: e,
+            context: Some(format!("Write {}", env_file.display())),
+        });
+    }
+}
+
+/// Forget a removed worktree's database, dropping it first if asked to
+async fn remove_worktree_database(worktree_path: &str, drop_database: bool) {
+    let database = {
+        let state = get_app_state().read().await;
+        state
+            .active_project()
+            .and_then(|p| p.env_config.worktree_db.databases.get(worktree_path).cloned())
+    };
+    let Some(database) = database else {
+        return;
+    };
+
+    if drop_database {
+        let dropped = match get_docker_manager().await {
+            Ok(dm) => dm.drop_database(&database.service_id, &database.name).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = dropped {
+            // Keep the record so the database isn't forgotten
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetError {
+                code: "WORKTREE_DB_ERROR".to_string(),
+                message: e,
+                context: Some(format!("Drop database {} for {}", database.name, worktree_path)),
+            });
+            return;
+        }
+    }
+
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::SetWorktreeDatabase {
+        worktree_path: worktree_path.to_string(),
+        database: None,
+    });
+}
+
+/// Re-read the snapshots directory into state
+async fn refresh_service_snapshots() {
+    let snapshots = snapshots::list_all_snapshots(&snapshots::snapshots_root())
+        .into_iter()
+        .map(|s| actions::ServiceSnapshotData {
+            id: s.id,
+            service_id: s.service_id,
+            database: s.database,
+            created_at: s.created_at.to_rfc3339(),
+            size_bytes: s.size_bytes,
+        })
+        .collect();
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::SetServiceSnapshots { snapshots });
+}
+
+/// Parse the compose file in the active project's root, if any
+async fn active_compose_project() -> Result<Option<compose::ComposeProject>, String> {
+    let project_path = {
+        let state = get_app_state().read().await;
+        state.active_project().map(|p| p.path.clone())
+    };
+    match project_path {
+        Some(path) => compose::load_compose_project(std::path::Path::new(&path)),
+        None => Ok(None),
+    }
+}
+
+/// Run `docker compose` up/stop/restart for services of the active project
+async fn run_compose_action(command: &str, services: &[String]) {
+    let result = match active_compose_project().await {
+        Ok(Some(project)) => match command {
+            "up" => project.up(services).await,
+            "stop" => project.stop(services).await,
+            _ => project.restart(services).await,
+        },
+        Ok(None) => Err("No compose file in the project root".to_string()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = &result {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetError {
+            code: "DOCKER_COMPOSE_ERROR".to_string(),
+            message: e.clone(),
+            context: Some(format!("Compose {}: {}", command, services.join(", "))),
+        });
+    }
+    refresh_docker_services_internal().await;
+
+    if result.is_ok() && command != "stop" {
+        let started: Vec<String> = {
+            let state = get_app_state().read().await;
+            let group = state.docker.compose.as_ref().map(|c| c.name.as_str());
+            state
+                .docker
+                .services
+                .iter()
+                .filter(|s| s.compose_service.is_some() && s.project_group.as_deref() == group)
+                .filter(|s| s.status == app_state::ServiceStatus::Running)
+                .map(|s| s.id.clone())
+                .collect()
+        };
+        for service_id in &started {
+            watch_service_readiness(service_id);
+        }
+    }
+}
+
 /// Refresh Docker services and update state
 async fn refresh_docker_services_internal() {
+    ensure_service_templates().await;
     match docker_list_services().await {
         Ok(services) => {
-            let service_data: Vec<actions::DockerServiceData> = services
+            let mut service_data: Vec<actions::DockerServiceData> = services
                 .into_iter()
                 .map(|s| actions::DockerServiceData {
                     id: s.id,
@@ -702,10 +2011,68 @@ async fn refresh_docker_services_internal() {
                     service_type: s.service_type,
                     project_group: s.project_group,
                     is_rstn_managed: s.is_rstn_managed,
+                    compose_service: s.compose_service,
+                    health: s.health,
                 })
                 .collect();
+
+            let compose = match active_compose_project().await {
+                Ok(project) => project,
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "COMPOSE_PARSE_ERROR".to_string(),
+                        message: e,
+                        context: Some("RefreshDockerServices".to_string()),
+                    });
+                    None
+                }
+            };
+            if let Some(project) = &compose {
+                // Compose services without a container yet
+                for service in &project.services {
+                    let exists = service_data.iter().any(|s| {
+                        s.project_group.as_deref() == Some(project.name.as_str())
+                            && s.compose_service.as_deref() == Some(service.name.as_str())
+                    });
+                    if !exists {
+                        service_data.push(actions::DockerServiceData {
+                            id: service.container_name(&project.name),
+                            name: service.name.clone(),
+                            image: service.image.clone().unwrap_or_default(),
+                            status: "stopped".to_string(),
+                            port: service.host_port(),
+                            service_type: "Other".to_string(),
+                            project_group: Some(project.name.clone()),
+                            is_rstn_managed: false,
+                            compose_service: Some(service.name.clone()),
+                            health: None,
+                        });
+                    }
+                }
+            }
+
             let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetComposeProject {
+                project: compose.map(|project| actions::ComposeProjectData {
+                    services: project
+                        .services
+                        .iter()
+                        .map(|s| actions::ComposeServiceData {
+                            name: s.name.clone(),
+                            image: s.image.clone(),
+                            ports: s.ports.clone(),
+                            depends_on: s.depends_on.clone(),
+                            container_name: s.container_name(&project.name),
+                        })
+                        .collect(),
+                    file: project.file.to_string_lossy().to_string(),
+                    name: project.name,
+                }),
+            });
             reduce(&mut state, Action::SetDockerServices { services: service_data });
+            drop(state);
+            sync_stats_monitors().await;
         }
         Err(e) => {
             let mut state = get_app_state().write().await;
@@ -719,7 +2086,7 @@ async fn refresh_docker_services_internal() {
     }
 }
 
-/// Refresh justfile commands for the active worktree
+/// Refresh tasks from every task source for the active worktree
 async fn refresh_justfile_commands() {
     let worktree_path = {
         let state = get_app_state().read().await;
@@ -730,29 +2097,38 @@ async fn refresh_justfile_commands() {
     };
 
     if let Some(path) = worktree_path {
-        let justfile_path = std::path::Path::new(&path).join("justfile");
-        if justfile_path.exists() {
-            match justfile::parse_justfile(&justfile_path.to_string_lossy()) {
-                Ok(commands) => {
-                    let command_data: Vec<actions::JustCommandData> = commands
+        let (tasks, errors) = task_source::discover_tasks(&path);
+        let command_data: Vec<actions::JustCommandData> = tasks
+            .into_iter()
+            .map(|task| {
+                let c = task.command;
+                actions::JustCommandData {
+                    name: c.name,
+                    source: task.source,
+                    description: c.description,
+                    recipe: c.recipe,
+                    parameters: c
+                        .parameters
                         .into_iter()
-                        .map(|c| actions::JustCommandData {
-                            name: c.name,
-                            description: c.description,
-                            recipe: c.recipe,
+                        .map(|p| actions::JustParameterData {
+                            name: p.name,
+                            default: p.default,
+                            kind: p.kind,
+                            export: p.export,
                         })
-                        .collect();
-                    let mut state = get_app_state().write().await;
-                    reduce(&mut state, Action::SetJustfileCommands { commands: command_data });
-                }
-                Err(e) => {
-                    let mut state = get_app_state().write().await;
-                    reduce(&mut state, Action::SetTasksError { error: Some(e) });
+                        .collect(),
+                    dependencies: c.dependencies,
+                    group: c.group,
+                    private: c.private,
+                    aliases: c.aliases,
+                    confirm: c.confirm,
                 }
-            }
-        } else {
-            let mut state = get_app_state().write().await;
-            reduce(&mut state, Action::SetJustfileCommands { commands: Vec::new() });
+            })
+            .collect();
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetJustfileCommands { commands: command_data });
+        if !errors.is_empty() {
+            reduce(&mut state, Action::SetTasksError { error: Some(errors.join("\n")) });
         }
     }
 }
@@ -812,9 +2188,16 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
 
         Action::RefreshDockerServices => {
             refresh_docker_services_internal().await;
+            refresh_service_snapshots().await;
+        }
+
+        Action::ReloadServiceTemplates => {
+            reload_service_templates().await;
+            refresh_docker_services_internal().await;
         }
 
         Action::StartDockerService { ref service_id } => {
+            ensure_service_templates().await;
             // Check for port conflict first
             match docker_check_port_conflict(service_id.clone()).await {
                 Ok(Some(conflict_info)) => {
@@ -837,19 +2220,12 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                 }
                 Ok(None) => {
                     // No conflict, proceed with start
-                    match docker_start_service(service_id.clone()).await {
-                        Ok(()) => {
-                            refresh_docker_services_internal().await;
-                        }
-                        Err(e) => {
-                            let mut state = get_app_state().write().await;
-                            reduce(&mut state, Action::SetError {
-                                code: "DOCKER_START_ERROR".to_string(),
-                                message: e.to_string(),
-                                context: Some(format!("StartDockerService: {}", service_id)),
-                            });
-                        }
-                    }
+                    start_docker_service_internal(
+                        service_id,
+                        None,
+                        format!("StartDockerService: {}", service_id),
+                    )
+                    .await;
                 }
                 Err(e) => {
                     let mut state = get_app_state().write().await;
@@ -884,6 +2260,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                 Ok(()) => {
                     // Refresh services to get updated status
                     refresh_docker_services_internal().await;
+                    watch_service_readiness(service_id);
                 }
                 Err(e) => {
                     let mut state = get_app_state().write().await;
@@ -896,6 +2273,38 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::UpdateDockerImage { ref service_id } => {
+            if let Ok(updated) = pull_service_image(service_id, true).await {
+                let service = {
+                    let state = get_app_state().read().await;
+                    state
+                        .docker
+                        .services
+                        .iter()
+                        .find(|s| &s.id == service_id)
+                        .map(|s| (s.is_rstn_managed, s.status == app_state::ServiceStatus::Running, s.port))
+                };
+                // Without a container there is nothing to recreate
+                let notification = match (updated, service) {
+                    (false, _) => Some((
+                        format!("The image of {} is up to date", service_id),
+                        actions::NotificationTypeData::Success,
+                    )),
+                    (true, Some((true, was_running, port))) => {
+                        recreate_on_new_image(service_id, was_running, port.map(|p| p as u16)).await
+                    }
+                    (true, _) => Some((
+                        format!("Updated the image of {}", service_id),
+                        actions::NotificationTypeData::Success,
+                    )),
+                };
+                if let Some((message, notification_type)) = notification {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::AddNotification { message, notification_type });
+                }
+            }
+        }
+
         Action::FetchDockerLogs { ref service_id, tail } => {
             match docker_get_logs(service_id.clone(), Some(tail)).await {
                 Ok(logs) => {
@@ -914,22 +2323,172 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
-        Action::CreateDatabase { ref service_id, ref db_name } => {
-            match docker_create_database(service_id.clone(), db_name.clone()).await {
-                Ok(connection_string) => {
-                    let mut state = get_app_state().write().await;
-                    reduce(&mut state, Action::SetDockerConnectionString { 
-                        connection_string: Some(connection_string) 
-                    });
-                }
-                Err(e) => {
-                    let mut state = get_app_state().write().await;
-                    reduce(&mut state, Action::SetError {
-                        code: "DOCKER_CREATE_DB_ERROR".to_string(),
-                        message: e.to_string(),
-                        context: Some(format!("CreateDatabase: {} in {}", db_name, service_id)),
+        Action::FollowDockerLogs {
+            ref service_id,
+            stdout,
+            stderr,
+            timestamps,
+            since,
+            ref filter,
+        } => {
+            stop_log_follow();
+            let cancel = tokio_util::sync::CancellationToken::new();
+            *LOG_FOLLOW.lock().unwrap() = Some(cancel.clone());
+            let options = docker::LogFollowOptions {
+                stdout,
+                stderr,
+                timestamps,
+                since,
+            };
+            tokio::spawn(follow_docker_logs(
+                service_id.clone(),
+                options,
+                filter.clone(),
+                cancel,
+            ));
+        }
+
+        Action::StopFollowingDockerLogs => {
+            stop_log_follow();
+        }
+
+        Action::SelectDockerService { .. } => {
+            let following = get_app_state().read().await.docker.log_follow.is_some();
+            if !following {
+                stop_log_follow();
+            }
+        }
+
+        Action::CreateDatabase { ref service_id, ref db_name } => {
+            match docker_create_database(service_id.clone(), db_name.clone()).await {
+                Ok(connection_string) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetDockerConnectionString { 
+                        connection_string: Some(connection_string) 
+                    });
+                }
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "DOCKER_CREATE_DB_ERROR".to_string(),
+                        message: e.to_string(),
+                        context: Some(format!("CreateDatabase: {} in {}", db_name, service_id)),
+                    });
+                }
+            }
+        }
+
+        Action::CreateServiceSnapshot { ref service_id, ref database } => {
+            if let Err(e) = docker_create_snapshot(service_id.clone(), database.clone()).await {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "DOCKER_SNAPSHOT_ERROR".to_string(),
+                    message: e.to_string(),
+                    context: Some(format!("CreateServiceSnapshot: {}", service_id)),
+                });
+            }
+            refresh_service_snapshots().await;
+        }
+
+        Action::RestoreServiceSnapshot { ref service_id, ref snapshot_id, ref target_database } => {
+            match docker_restore_snapshot(service_id.clone(), snapshot_id.clone(), target_database.clone()).await {
+                Ok(connection_string) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetDockerConnectionString {
+                        connection_string: Some(connection_string)
+                    });
+                }
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "DOCKER_RESTORE_ERROR".to_string(),
+                        message: e.to_string(),
+                        context: Some(format!("RestoreServiceSnapshot: {} in {}", snapshot_id, service_id)),
+                    });
+                }
+            }
+            refresh_service_snapshots().await;
+        }
+
+        Action::DeleteServiceSnapshot { ref service_id, ref snapshot_id } => {
+            if let Err(e) = snapshots::delete_snapshot(&snapshots::snapshots_root(), service_id, snapshot_id) {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "DOCKER_SNAPSHOT_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("DeleteServiceSnapshot: {} in {}", snapshot_id, service_id)),
+                });
+            }
+            refresh_service_snapshots().await;
+        }
+
+        Action::RefreshServiceSnapshots => {
+            refresh_service_snapshots().await;
+        }
+
+        Action::OpenSqlConsole { ref service_id, ref database } => {
+            let result = describe_database(service_id, database.as_deref()).await;
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(schema) => reduce(&mut state, Action::SetSqlSchema {
+                    service_id: service_id.clone(),
+                    schema,
+                }),
+                Err(error) => reduce(&mut state, Action::SetSqlConsoleError {
+                    service_id: service_id.clone(),
+                    error,
+                }),
+            }
+        }
+
+        Action::RunSqlQuery { ref service_id, ref database, ref sql, limit, timeout_ms } => {
+            let timeout = timeout_ms.map(std::time::Duration::from_millis);
+            let result = run_sql_query(service_id, database.as_deref(), sql, limit, timeout).await;
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(result) => reduce(&mut state, Action::SetSqlQueryResult {
+                    service_id: service_id.clone(),
+                    result,
+                }),
+                Err(error) => reduce(&mut state, Action::SetSqlConsoleError {
+                    service_id: service_id.clone(),
+                    error,
+                }),
+            }
+        }
+
+        Action::LoadConnectionInfo { ref service_id } => {
+            let result = match get_docker_manager().await {
+                Ok(dm) => dm.connection_info(service_id).await,
+                Err(e) => Err(e.to_string()),
+            };
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(info) => reduce(&mut state, Action::SetConnectionInfo { info: Some(info) }),
+                Err(e) => reduce(&mut state, Action::SetError {
+                    code: "DOCKER_CONNECTION_INFO_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("LoadConnectionInfo: {}", service_id)),
+                }),
+            }
+        }
+
+        Action::ExportConnectionInfo { ref service_id, ref worktree_path } => {
+            let result = export_connection_info(service_id, worktree_path).await;
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(info) => {
+                    let message = format!("Set {} in {}/.env", info.env_var, worktree_path);
+                    reduce(&mut state, Action::SetConnectionInfo { info: Some(info) });
+                    reduce(&mut state, Action::AddNotification {
+                        message,
+                        notification_type: actions::NotificationTypeData::Success,
                     });
                 }
+                Err(e) => reduce(&mut state, Action::AddNotification {
+                    message: format!("Export of {} connection failed: {}", service_id, e),
+                    notification_type: actions::NotificationTypeData::Error,
+                }),
             }
         }
 
@@ -1074,31 +2633,119 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
 
         Action::LoadJustfileCommands | Action::RefreshJustfile => {
             refresh_justfile_commands().await;
+            load_task_history(None).await;
         }
 
-        Action::RunJustCommand { ref name, ref cwd } => {
-            match justfile::run_just_command(name, cwd) {
-                Ok(output) => {
-                    let mut state = get_app_state().write().await;
-                    for line in output.lines() {
-                        reduce(&mut state, Action::AppendTaskOutput { line: line.to_string() });
+        Action::LoadTaskHistory => {
+            load_task_history(None).await;
+        }
+
+        Action::ViewTaskRun { ref id } => {
+            let (Some(db), Some((project_id, _))) = (get_db_manager(), active_worktree_key().await)
+            else {
+                return Ok(());
+            };
+            let result = db.get_task_run(&project_id, id);
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(Some(row)) => {
+                    reduce(&mut state, Action::SetViewedTaskRun { run: Some(task_run_data(row)) });
+                }
+                Ok(None) => {
+                    reduce(&mut state, Action::SetTasksError {
+                        error: Some(format!("Task run not found: {}", id)),
+                    });
+                }
+                Err(e) => {
+                    reduce(&mut state, Action::SetTasksError {
+                        error: Some(format!("Failed to load task run: {}", e)),
+                    });
+                }
+            }
+        }
+
+        Action::RerunTask { ref id } => {
+            let (Some(db), Some((project_id, worktree_path))) =
+                (get_db_manager(), active_worktree_key().await)
+            else {
+                return Ok(());
+            };
+            match db.get_task_run(&project_id, id) {
+                Ok(Some(row)) => {
+                    let rerun = Action::RunJustCommand {
+                        name: row.task_name,
+                        cwd: worktree_path,
+                        args: row.args,
+                        named_args: row.named_args,
+                    };
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, rerun.clone());
                     }
-                    reduce(&mut state, Action::SetTaskStatus {
-                        name: name.clone(),
-                        status: actions::TaskStatusData::Success,
+                    Box::pin(handle_async_action(rerun)).await?;
+                }
+                Ok(None) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetTasksError {
+                        error: Some(format!("Task run not found: {}", id)),
+                    });
+                }
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetTasksError {
+                        error: Some(format!("Failed to load task run: {}", e)),
                     });
                 }
+            }
+        }
+
+        Action::RunJustCommand {
+            ref name,
+            ref cwd,
+            ref args,
+            ref named_args,
+        } => {
+            start_task_run(name, cwd, args, named_args).await;
+        }
+
+        Action::CancelJustCommand { ref run_id } => {
+            get_task_runner().cancel(run_id);
+        }
+
+        Action::StartTaskWatch {
+            ref name,
+            ref cwd,
+            ref patterns,
+            debounce_ms,
+            ref args,
+            ref named_args,
+            ..
+        } => {
+            let debounce = debounce_ms
+                .map(std::time::Duration::from_millis)
+                .unwrap_or(task_watch::DEFAULT_DEBOUNCE);
+            match get_task_watcher().start(&task_watch_key(cwd, name), cwd, patterns, debounce) {
+                Ok(changes) => {
+                    tokio::spawn(drive_task_watch(name.clone(), cwd.clone(), changes));
+                    start_task_run(name, cwd, args, named_args).await;
+                }
                 Err(e) => {
                     let mut state = get_app_state().write().await;
-                    reduce(&mut state, Action::AppendTaskOutput { line: e.clone() });
-                    reduce(&mut state, Action::SetTaskStatus {
+                    reduce(&mut state, Action::StopTaskWatch {
                         name: name.clone(),
-                        status: actions::TaskStatusData::Error,
+                        cwd: cwd.clone(),
+                    });
+                    reduce(&mut state, Action::SetTasksError {
+                        error: Some(format!("Failed to watch {}: {}", name, e)),
                     });
                 }
             }
         }
 
+        Action::StopTaskWatch { ref name, ref cwd } => {
+            get_task_watcher().stop(&task_watch_key(cwd, name));
+        }
+
         Action::OpenProject { ref path } => {
             // Initialize global database (user-scope, not project-specific)
             // Only initialize once, on first project open
@@ -1110,6 +2757,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
 
             // After opening a project, refresh worktrees from git
             refresh_worktrees_for_path(path).await;
+            reload_service_templates().await;
 
             // Check constitution existence for the active worktree
             // Supports both modular (.rstn/constitutions/) and legacy (.rstn/constitution.md)
@@ -1212,13 +2860,16 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                             if config.auto_copy_enabled {
                                 let copy_action = Action::CopyEnvFiles {
                                     from_worktree_path: source,
-                                    to_worktree_path: new_worktree.path,
+                                    to_worktree_path: new_worktree.path.clone(),
                                     patterns: Some(config.tracked_patterns),
                                 };
                                 // Handle env copy (will add notification)
                                 Box::pin(handle_async_action(copy_action)).await.ok();
                             }
                         }
+
+                        // After the env copy, so its DATABASE_URL is replaced
+                        create_worktree_database(&new_worktree.path, branch).await;
                     }
                     Err(e) => {
                         let mut state = get_app_state().write().await;
@@ -1266,13 +2917,16 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                             if config.auto_copy_enabled {
                                 let copy_action = Action::CopyEnvFiles {
                                     from_worktree_path: source,
-                                    to_worktree_path: new_worktree.path,
+                                    to_worktree_path: new_worktree.path.clone(),
                                     patterns: Some(config.tracked_patterns),
                                 };
                                 // Handle env copy (will add notification)
                                 Box::pin(handle_async_action(copy_action)).await.ok();
                             }
                         }
+
+                        // After the env copy, so its DATABASE_URL is replaced
+                        create_worktree_database(&new_worktree.path, branch).await;
                     }
                     Err(e) => {
                         let mut state = get_app_state().write().await;
@@ -1289,16 +2943,42 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
-        Action::RemoveWorktree { ref worktree_path } => {
-            // Get the active project path
-            let project_path = {
+        Action::RemoveWorktree { ref worktree_path, drop_database } => {
+            // Get the active project path and the removed worktree's id and terminal sessions
+            let (project_path, worktree_id, session_ids) = {
                 let state = get_app_state().read().await;
-                state.active_project().map(|p| p.path.clone())
+                let project = state.active_project();
+                let worktree = project.and_then(|p| p.worktrees.iter().find(|w| &w.path == worktree_path));
+                (
+                    project.map(|p| p.path.clone()),
+                    worktree.map(|w| w.id.clone()),
+                    worktree
+                        .map(|w| {
+                            w.terminal
+                                .sessions
+                                .iter()
+                                .map(|s| s.id.clone())
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default(),
+                )
             };
 
             if let Some(path) = project_path {
                 match worktree::remove_worktree(&path, worktree_path) {
                     Ok(()) => {
+                        // Shut down any shells still running in the removed worktree
+                        let manager = get_terminal_manager();
+                        for session_id in &session_ids {
+                            let _ = manager.kill(session_id).await;
+                        }
+                        // Also any the state no longer lists
+                        if let Some(worktree_id) = &worktree_id {
+                            manager.kill_worktree_sessions(worktree_id).await;
+                        }
+
+                        remove_worktree_database(worktree_path, drop_database).await;
+
                         // Refresh worktrees to get the updated list
                         refresh_worktrees_for_path(&path).await;
                     }
@@ -1314,21 +2994,27 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::ComposeUp { ref services } => {
+            run_compose_action("up", services).await;
+        }
+
+        Action::ComposeStop { ref services } => {
+            run_compose_action("stop", services).await;
+        }
+
+        Action::ComposeRestart { ref services } => {
+            run_compose_action("restart", services).await;
+        }
+
         Action::StartDockerServiceWithPort { ref service_id, port } => {
+            ensure_service_templates().await;
             // Start service with custom port
-            match docker_start_service_with_port(service_id.clone(), port).await {
-                Ok(()) => {
-                    refresh_docker_services_internal().await;
-                }
-                Err(e) => {
-                    let mut state = get_app_state().write().await;
-                    reduce(&mut state, Action::SetError {
-                        code: "DOCKER_START_ERROR".to_string(),
-                        message: e.to_string(),
-                        context: Some(format!("StartDockerServiceWithPort: {} on port {}", service_id, port)),
-                    });
-                }
-            }
+            start_docker_service_internal(
+                service_id,
+                Some(port),
+                format!("StartDockerServiceWithPort: {} on port {}", service_id, port),
+            )
+            .await;
         }
 
         Action::ResolveConflictByStoppingContainer { ref conflicting_container_id, ref service_id } => {
@@ -1336,19 +3022,12 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             match docker_stop_container(conflicting_container_id.clone()).await {
                 Ok(()) => {
                     // Now start the rstn service
-                    match docker_start_service(service_id.clone()).await {
-                        Ok(()) => {
-                            refresh_docker_services_internal().await;
-                        }
-                        Err(e) => {
-                            let mut state = get_app_state().write().await;
-                            reduce(&mut state, Action::SetError {
-                                code: "DOCKER_START_ERROR".to_string(),
-                                message: e.to_string(),
-                                context: Some(format!("ResolveConflict: failed to start {}", service_id)),
-                            });
-                        }
-                    }
+                    start_docker_service_internal(
+                        service_id,
+                        None,
+                        format!("ResolveConflict: failed to start {}", service_id),
+                    )
+                    .await;
                 }
                 Err(e) => {
                     let mut state = get_app_state().write().await;
@@ -1484,13 +3163,24 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::SetMcpError { .. }
         | Action::SetDockerAvailable { .. }
         | Action::SetDockerServices { .. }
-        | Action::SelectDockerService { .. }
+        | Action::SetDockerServiceHealth { .. }
+        | Action::SetDockerServiceStats { .. }
+        | Action::SetDockerImagePull { .. }
         | Action::SetDockerLogs { .. }
+        | Action::AppendDockerLogLines { .. }
+        | Action::DockerLogStreamEnded { .. }
         | Action::SetDockerLoading { .. }
         | Action::SetDockerLogsLoading { .. }
         | Action::SetPortConflict { .. }
         | Action::ClearPortConflict
         | Action::SetDockerConnectionString { .. }
+        | Action::SetServiceSnapshots { .. }
+        | Action::CloseSqlConsole
+        | Action::SetSqlSchema { .. }
+        | Action::SetSqlQueryResult { .. }
+        | Action::SetSqlConsoleError { .. }
+        | Action::SetConnectionInfo { .. }
+        | Action::SetComposeProject { .. }
         | Action::SetBranches { .. }
         | Action::SetBranchesLoading { .. }
         | Action::SetFileContent { .. }
@@ -1499,11 +3189,20 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::SetA2UIPayload { .. }
         | Action::SetJustfileCommands { .. }
         | Action::SetTaskStatus { .. }
-        | Action::SetActiveCommand { .. }
+        | Action::TaskRunStarted { .. }
+        | Action::JustCommandFinished { .. }
+        | Action::SelectTaskRun { .. }
         | Action::AppendTaskOutput { .. }
-        | Action::ClearTaskOutput
+        | Action::ClearTaskOutput { .. }
+        | Action::DismissTaskRun { .. }
+        | Action::TaskWatchTriggered { .. }
         | Action::SetTasksLoading { .. }
         | Action::SetTasksError { .. }
+        | Action::SetTaskHistory { .. }
+        | Action::SetViewedTaskRun { .. }
+        | Action::CloseTaskRun
+        | Action::SetTaskDiagnostics { .. }
+        | Action::ClearDiagnostics
         | Action::SetTheme { .. }
         | Action::SetProjectPath { .. }
         | Action::SetError { .. }
@@ -1513,6 +3212,8 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::SetEnvTrackedPatterns { .. }
         | Action::SetEnvAutoCopy { .. }
         | Action::SetEnvSourceWorktree { .. }
+        | Action::SetWorktreeDbConfig { .. }
+        | Action::SetWorktreeDatabase { .. }
         // Notification actions (sync)
         | Action::AddNotification { .. }
         | Action::DismissNotification { .. }
@@ -1531,8 +3232,19 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::ClearChatError
         | Action::ClearChat
         // Terminal actions (sync - state updates only)
+        | Action::MoveTerminalSession { .. }
+        | Action::SelectTerminalSession { .. }
         | Action::SetTerminalSession { .. }
+        | Action::SetTerminalSessionRunning { .. }
         | Action::SetTerminalSize { .. }
+        | Action::TerminalExited { .. }
+        | Action::SetTerminalSearchResults { .. }
+        | Action::ClearTerminalSearch
+        | Action::TerminalCommandFinished { .. }
+        | Action::SetTerminalRecording { .. }
+        | Action::SetTerminalReplay { .. }
+        | Action::TerminalReplayFinished { .. }
+        | Action::SetTerminalEnv { .. }
         // View actions (sync)
         | Action::SetActiveView { .. }
         | Action::SetExplorerEntries { .. }
@@ -4048,12 +5760,280 @@ Execute the plan now. Start implementing."#,
         }
 
         // Terminal actions (async - PTY operations)
-        Action::SpawnTerminal { .. }
-        | Action::ResizeTerminal { .. }
-        | Action::WriteTerminal { .. }
-        | Action::KillTerminal { .. } => {
-            // TODO: Add terminal manager handling
-            // These will be handled by a global terminal manager
+        Action::RefreshTerminalEnv
+        | Action::SetTerminalEnvOverride { .. }
+        | Action::RemoveTerminalEnvOverride { .. } => {
+            // Overrides only affect shells spawned from now on
+            resolve_active_terminal_env().await;
+        }
+
+        Action::SpawnTerminal { cols, rows } => {
+            // Restart the active session's shell if one is already running
+            spawn_active_terminal_session(cols, rows, true, "SpawnTerminal").await;
+        }
+
+        Action::CreateTerminalSession { cols, rows, .. } => {
+            // Reducer already added the session and made it active
+            spawn_active_terminal_session(cols, rows, false, "CreateTerminalSession").await;
+        }
+
+        Action::ExecContainerShell { .. } => {
+            // Reducer already added the container session and made it active
+            let size = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| (w.terminal.cols, w.terminal.rows))
+            };
+            let (cols, rows) = size.unwrap_or((80, 24));
+            spawn_active_terminal_session(cols, rows, false, "ExecContainerShell").await;
+        }
+
+        Action::RenameTerminalSession { ref session_id, ref name } => {
+            // Shell may not be running; the name lives in state either way
+            let _ = get_terminal_manager()
+                .rename(session_id, name.trim().to_string())
+                .await;
+        }
+
+        Action::CloseTerminalSession { ref session_id } => {
+            let manager = get_terminal_manager();
+            let _ = manager.kill(session_id).await;
+            manager.discard_history(session_id);
+        }
+
+        Action::SearchTerminalScrollback { ref session_id, ref pattern } => {
+            let result = get_terminal_manager().search_scrollback(
+                session_id,
+                pattern,
+                terminal::DEFAULT_SEARCH_LIMIT,
+            );
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(matches) => {
+                    reduce(&mut state, Action::SetTerminalSearchResults {
+                        session_id: session_id.clone(),
+                        pattern: pattern.clone(),
+                        matches: matches
+                            .into_iter()
+                            .map(|m| actions::TerminalSearchMatchData {
+                                line: m.line,
+                                text: m.text,
+                                start: m.start,
+                                end: m.end,
+                            })
+                            .collect(),
+                    });
+                }
+                Err(e) => {
+                    reduce(&mut state, Action::SetError {
+                        code: "TERMINAL_SEARCH_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("SearchTerminalScrollback: {}", session_id)),
+                    });
+                }
+            }
+        }
+
+        Action::ExplainTerminalFailure { ref session_id } => {
+            let session_id = match session_id {
+                Some(id) => Some(id.clone()),
+                None => {
+                    let state = get_app_state().read().await;
+                    state
+                        .active_project()
+                        .and_then(|p| p.active_worktree())
+                        .and_then(|w| w.terminal.session_id.clone())
+                }
+            };
+
+            let failure = session_id.and_then(|id| {
+                get_terminal_manager()
+                    .failed_commands(&id, 1, TERMINAL_EXPLAIN_OUTPUT_LINES)
+                    .ok()
+                    .and_then(|mut failures| failures.pop())
+            });
+
+            let Some((record, output)) = failure else {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetChatError {
+                    error: "No failed terminal command to explain".to_string(),
+                });
+                return Ok(());
+            };
+
+            let text = format!(
+                "Explain why this terminal command failed and how to fix it.\n\n{}",
+                format_command_failure(&record, &output)
+            );
+
+            // Same flow as a message typed into the chat
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SendChatMessage { text: text.clone() });
+            }
+            notify_state_update().await;
+            Box::pin(handle_async_action(Action::SendChatMessage { text })).await?;
+        }
+
+        Action::StartTerminalRecording { ref session_id } => {
+            let info = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| {
+                    let worktree = p.active_worktree()?;
+                    let session = worktree.terminal.session(session_id)?;
+                    Some((
+                        p.name.clone(),
+                        session.name.clone(),
+                        worktree.terminal.cols,
+                        worktree.terminal.rows,
+                    ))
+                })
+            };
+
+            let result = match info {
+                Some((project_name, session_name, cols, rows)) => {
+                    let file_name = format!(
+                        "{}-{}.cast",
+                        slugify(&session_name),
+                        chrono::Local::now().format("%Y%m%d-%H%M%S")
+                    );
+                    let path = terminal::recordings_dir(&project_name).join(file_name);
+                    let title = format!("{} / {}", project_name, session_name);
+                    get_terminal_manager()
+                        .start_recording(session_id, &path, cols, rows, Some(title))
+                        .map(|()| path)
+                }
+                None => Err(format!("Session not found: {}", session_id)),
+            };
+
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(path) => {
+                    reduce(&mut state, Action::SetTerminalRecording {
+                        session_id: session_id.clone(),
+                        path: Some(path.to_string_lossy().to_string()),
+                    });
+                }
+                Err(e) => {
+                    reduce(&mut state, Action::SetError {
+                        code: "TERMINAL_RECORDING_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("StartTerminalRecording: {}", session_id)),
+                    });
+                }
+            }
+        }
+
+        Action::StopTerminalRecording { ref session_id } => {
+            let result = get_terminal_manager().stop_recording(session_id);
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetTerminalRecording {
+                session_id: session_id.clone(),
+                path: None,
+            });
+            match result {
+                Ok(path) => {
+                    reduce(&mut state, Action::AddNotification {
+                        message: format!("Terminal recording saved to {}", path.display()),
+                        notification_type: actions::NotificationTypeData::Success,
+                    });
+                }
+                Err(e) => {
+                    reduce(&mut state, Action::SetError {
+                        code: "TERMINAL_RECORDING_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("StopTerminalRecording: {}", session_id)),
+                    });
+                }
+            }
+        }
+
+        Action::ReplayTerminalRecording { ref path, speed } => {
+            let cast_path = std::path::PathBuf::from(path);
+            match terminal::read_cast_header(&cast_path) {
+                Ok(header) => {
+                    let replay_id = format!("replay-{}", uuid::Uuid::new_v4());
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::SetTerminalReplay {
+                            replay: actions::TerminalReplayData {
+                                id: replay_id.clone(),
+                                path: path.clone(),
+                                speed,
+                                width: header.width,
+                                height: header.height,
+                            },
+                        });
+                    }
+
+                    tokio::spawn(async move {
+                        // Give the renderer time to attach to the replay ID
+                        tokio::time::sleep(REPLAY_START_DELAY).await;
+                        let result = get_terminal_manager()
+                            .replay(&replay_id, &cast_path, speed)
+                            .await;
+                        {
+                            let mut state = get_app_state().write().await;
+                            if let Err(e) = result {
+                                reduce(&mut state, Action::SetError {
+                                    code: "TERMINAL_REPLAY_ERROR".to_string(),
+                                    message: e,
+                                    context: Some(format!("ReplayTerminalRecording: {}", cast_path.display())),
+                                });
+                            }
+                            reduce(&mut state, Action::TerminalReplayFinished { replay_id });
+                        }
+                        notify_state_update().await;
+                    });
+                }
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "TERMINAL_REPLAY_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("ReplayTerminalRecording: {}", path)),
+                    });
+                }
+            }
+        }
+
+        Action::StopTerminalReplay { ref replay_id } => {
+            // Replay may have just finished on its own; that's fine
+            let _ = get_terminal_manager().stop_replay(replay_id);
+        }
+
+        Action::ClearTerminalScrollback { ref session_id } => {
+            // Nothing to clear if the session never produced output
+            let _ = get_terminal_manager().clear_scrollback(session_id);
+        }
+
+        Action::ResizeTerminal { ref session_id, cols, rows } => {
+            if let Err(e) = get_terminal_manager().resize(session_id, cols, rows).await {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "TERMINAL_RESIZE_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("ResizeTerminal: {}", session_id)),
+                });
+            }
+        }
+
+        Action::WriteTerminal { ref session_id, ref data } => {
+            if let Err(e) = get_terminal_manager().write(session_id, data.as_bytes()).await {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "TERMINAL_WRITE_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("WriteTerminal: {}", session_id)),
+                });
+            }
+        }
+
+        Action::KillTerminal { ref session_id } => {
+            // Session may already be gone if the shell exited; that's fine
+            let _ = get_terminal_manager().kill(session_id).await;
         }
 
         _ => {}
@@ -4062,6 +6042,165 @@ Execute the plan now. Start implementing."#,
     Ok(())
 }
 
+/// Start the shell for the active worktree's active terminal session.
+///
+/// With `restart`, an already running shell for the session is replaced;
+/// otherwise an existing shell is left untouched.
+async fn spawn_active_terminal_session(cols: u16, rows: u16, restart: bool, context: &str) {
+    let session_info = {
+        let state = get_app_state().read().await;
+        state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .and_then(|w| {
+                let session = w.terminal.session(w.terminal.session_id.as_deref()?)?;
+                Some((
+                    session.id.clone(),
+                    session.name.clone(),
+                    session.container.clone(),
+                    w.id.clone(),
+                    w.path.clone(),
+                ))
+            })
+    };
+
+    let Some((session_id, name, container, worktree_id, worktree_path)) = session_info else {
+        return;
+    };
+
+    let manager = get_terminal_manager();
+    if manager.has_session(&session_id).await {
+        if !restart {
+            return;
+        }
+        let _ = manager.kill(&session_id).await;
+    }
+
+    let result = match container {
+        // Shell inside a service's container, bridged over a Docker exec
+        Some(target) => {
+            open_container_session(&target, session_id.clone(), name, worktree_id, worktree_path, cols, rows)
+                .await
+        }
+        None => {
+            let env = resolve_active_terminal_env().await;
+            manager
+                .spawn_session(
+                    session_id.clone(),
+                    name,
+                    worktree_id,
+                    terminal::SpawnOptions {
+                        cwd: worktree_path,
+                        cols,
+                        rows,
+                        env,
+                    },
+                )
+                .await
+        }
+    };
+
+    let mut state = get_app_state().write().await;
+    match result {
+        Ok(()) => {
+            reduce(&mut state, Action::SetTerminalSessionRunning {
+                session_id,
+                is_running: true,
+            });
+        }
+        Err(e) => {
+            reduce(&mut state, Action::SetTerminalSessionRunning {
+                session_id,
+                is_running: false,
+            });
+            reduce(&mut state, Action::SetError {
+                code: "TERMINAL_SPAWN_ERROR".to_string(),
+                message: e,
+                context: Some(context.to_string()),
+            });
+        }
+    }
+}
+
+/// Start a terminal session whose shell runs inside a service's container.
+async fn open_container_session(
+    target: &terminal::ContainerShellTarget,
+    session_id: String,
+    name: String,
+    worktree_id: String,
+    cwd: String,
+    cols: u16,
+    rows: u16,
+) -> Result<(), String> {
+    let dm = get_docker_manager().await.map_err(|e| e.to_string())?;
+    let remote = dm.open_shell(&target.service_id, &target.shell, cols, rows).await?;
+    get_terminal_manager()
+        .spawn_remote_session(session_id, name, worktree_id, cwd, remote)
+        .await
+}
+
+/// Resolve the variables injected into the active worktree's terminals
+/// (running rstn services, tracked dotenv files, project overrides) and
+/// mirror them into state.
+async fn resolve_active_terminal_env() -> Vec<(String, String)> {
+    let inputs = {
+        let state = get_app_state().read().await;
+        state.active_project().and_then(|project| {
+            let worktree = project.active_worktree()?;
+            let services: Vec<String> = state
+                .docker
+                .services
+                .iter()
+                .filter(|s| s.is_rstn_managed && s.status == app_state::ServiceStatus::Running)
+                .map(|s| s.id.clone())
+                .collect();
+            Some((
+                project.path.clone(),
+                worktree.path.clone(),
+                project.env_config.tracked_patterns.clone(),
+                project.env_config.terminal_env_overrides.clone(),
+                services,
+            ))
+        })
+    };
+
+    let Some((project_path, worktree_path, patterns, overrides, services)) = inputs else {
+        return Vec::new();
+    };
+
+    // Read from the containers, so services from services.yaml and ports
+    // moved after a conflict are covered
+    let mut service_vars: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    if !services.is_empty() {
+        ensure_service_templates().await;
+        if let Ok(dm) = get_docker_manager().await {
+            for id in services {
+                if let Ok(vars) = dm.service_env_vars(&id).await {
+                    service_vars.push((id, vars));
+                }
+            }
+        }
+    }
+    let env_files = env::read_env_files(&worktree_path, &patterns);
+    let vars = terminal::resolve_terminal_env(&service_vars, &env_files, &overrides, &project_path);
+
+    let env = vars.iter().map(|v| (v.key.clone(), v.value.clone())).collect();
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::SetTerminalEnv {
+        worktree_path,
+        vars: vars
+            .into_iter()
+            .map(|v| actions::TerminalEnvVarData {
+                key: v.key,
+                value: v.value,
+                source: v.source,
+                origin: v.origin,
+            })
+            .collect(),
+    });
+    env
+}
+
 /// Convert intent to a URL-friendly slug
 fn slugify(intent: &str) -> String {
     intent
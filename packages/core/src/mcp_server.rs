@@ -105,18 +105,94 @@ fn get_available_tools() -> Vec<ToolInfo> {
         },
         ToolInfo {
             name: "run_just_task".to_string(),
-            description: "Run a Just task and return the output".to_string(),
+            description: "Run a project task and return the output. Justfile recipes use their plain name; package.json scripts, Makefile targets and cargo aliases are prefixed with 'npm:', 'make:' or 'cargo:'. Recipe arguments are validated against the recipe's parameters.".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "task_name": {
                         "type": "string",
-                        "description": "Name of the Just task to run"
+                        "description": "Task to run: a Just recipe (or alias), or 'npm:<script>', 'make:<target>', 'cargo:<alias>'"
+                    },
+                    "args": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Positional arguments for the recipe's parameters"
+                    },
+                    "named_args": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "Values for recipe parameters by name (variadic values are split on whitespace); make variables for Makefile targets"
                     }
                 },
                 "required": ["task_name"]
             }),
         },
+        ToolInfo {
+            name: "wait_for_service".to_string(),
+            description: "Wait until a Docker service (e.g. 'rstn-postgres') is ready to accept connections. Fails if the service is not running, reports unhealthy, or is not ready before the timeout.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "service_id": {
+                        "type": "string",
+                        "description": "Container name of the service"
+                    },
+                    "timeout_secs": {
+                        "type": "integer",
+                        "description": "Seconds to wait before giving up (default 120)"
+                    }
+                },
+                "required": ["service_id"]
+            }),
+        },
+        ToolInfo {
+            name: "describe_database".to_string(),
+            description: "List the databases, schemas and tables (with column types, nullability and defaults) of a running Postgres or MySQL service. Use it to inspect the dev schema before writing migrations or queries.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "service_id": {
+                        "type": "string",
+                        "description": "Database service ('rstn-postgres' or 'rstn-mysql')"
+                    },
+                    "database": {
+                        "type": "string",
+                        "description": "Database whose schemas and tables to list (default: the server's default database)"
+                    }
+                },
+                "required": ["service_id"]
+            }),
+        },
+        ToolInfo {
+            name: "run_sql_query".to_string(),
+            description: "Run a SQL query in a running Postgres or MySQL service and return typed rows as JSON. Results are capped at a row limit and the query is cancelled after a timeout.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "service_id": {
+                        "type": "string",
+                        "description": "Database service ('rstn-postgres' or 'rstn-mysql')"
+                    },
+                    "database": {
+                        "type": "string",
+                        "description": "Database to run the query in (default: the server's default database)"
+                    },
+                    "sql": {
+                        "type": "string",
+                        "description": "Query to run"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum rows to return (default 500, at most 10000)"
+                    },
+                    "timeout_secs": {
+                        "type": "integer",
+                        "description": "Seconds before the query is cancelled (default 30)"
+                    }
+                },
+                "required": ["service_id", "sql"]
+            }),
+        },
         // ====================================================================
         // ReviewGate Tools (CESDD ReviewGate Layer)
         // ====================================================================
@@ -358,12 +434,30 @@ impl McpServerContext {
                     .and_then(|v| v.as_str())
                     .ok_or("Missing 'task_name' parameter")?;
 
-                let output = tokio::process::Command::new("just")
-                    .arg(task_name)
+                let args: Vec<String> = match params.get("args") {
+                    Some(value) => serde_json::from_value(value.clone())
+                        .map_err(|_| "'args' must be an array of strings")?,
+                    None => Vec::new(),
+                };
+                let named_args: HashMap<String, String> = match params.get("named_args") {
+                    Some(value) => serde_json::from_value(value.clone())
+                        .map_err(|_| "'named_args' must be an object of strings")?,
+                    None => HashMap::new(),
+                };
+
+                let (program, argv) = crate::task_source::prepare_task(
+                    &self.worktree_root.to_string_lossy(),
+                    task_name,
+                    &args,
+                    &named_args,
+                )?;
+
+                let output = tokio::process::Command::new(&program)
+                    .args(&argv)
                     .current_dir(&self.worktree_root)
                     .output()
                     .await
-                    .map_err(|e| format!("Failed to run just task: {}", e))?;
+                    .map_err(|e| format!("Failed to run {} task: {}", program, e))?;
 
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 let stderr = String::from_utf8_lossy(&output.stderr);
@@ -380,6 +474,73 @@ impl McpServerContext {
                 }
             }
 
+            "wait_for_service" => {
+                let service_id = params
+                    .get("service_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'service_id' parameter")?;
+                let timeout = params
+                    .get("timeout_secs")
+                    .and_then(|v| v.as_u64())
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(crate::readiness::READY_TIMEOUT);
+
+                crate::wait_for_service_ready(service_id, timeout).await?;
+
+                Ok(serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": format!("{} is ready", service_id)
+                    }]
+                }))
+            }
+
+            "describe_database" => {
+                let service_id = params
+                    .get("service_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'service_id' parameter")?;
+                let database = params.get("database").and_then(|v| v.as_str());
+
+                let schema = crate::describe_database(service_id, database).await?;
+
+                Ok(serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": serde_json::to_string_pretty(&schema).unwrap()
+                    }]
+                }))
+            }
+
+            "run_sql_query" => {
+                let service_id = params
+                    .get("service_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'service_id' parameter")?;
+                let sql = params
+                    .get("sql")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'sql' parameter")?;
+                let database = params.get("database").and_then(|v| v.as_str());
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .map(|l| l as usize);
+                let timeout = params
+                    .get("timeout_secs")
+                    .and_then(|v| v.as_u64())
+                    .map(std::time::Duration::from_secs);
+
+                let result = crate::run_sql_query(service_id, database, sql, limit, timeout).await?;
+
+                Ok(serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": serde_json::to_string_pretty(&result).unwrap()
+                    }]
+                }))
+            }
+
             // ================================================================
             // ReviewGate Tools
             // ================================================================
@@ -985,7 +1146,7 @@ mod tests {
     #[test]
     fn test_available_tools() {
         let tools = get_available_tools();
-        assert_eq!(tools.len(), 8); // 4 base tools + 3 ReviewGate tools + 1 A2UI tool
+        assert_eq!(tools.len(), 11); // 7 base tools + 3 ReviewGate tools + 1 A2UI tool
 
         let tool_names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
         // Base tools
@@ -993,6 +1154,9 @@ mod tests {
         assert!(tool_names.contains(&"list_directory"));
         assert!(tool_names.contains(&"get_project_context"));
         assert!(tool_names.contains(&"run_just_task"));
+        assert!(tool_names.contains(&"wait_for_service"));
+        assert!(tool_names.contains(&"describe_database"));
+        assert!(tool_names.contains(&"run_sql_query"));
         // ReviewGate tools
         assert!(tool_names.contains(&"submit_for_review"));
         assert!(tool_names.contains(&"get_review_feedback"));
@@ -1052,6 +1216,55 @@ mod tests {
         assert!(!content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_execute_run_just_task_validates_args() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("justfile"), "migrate name:\n    echo {{name}}\n").unwrap();
+        let context = McpServerContext {
+            worktree_root: dir.path().to_path_buf(),
+            worktree_id: "test-worktree".to_string(),
+            project_name: "test-project".to_string(),
+        };
+
+        let err = context
+            .execute_tool("run_just_task", &serde_json::json!({ "task_name": "migrate" }))
+            .await
+            .unwrap_err();
+        assert!(err.contains("requires argument 'name'"));
+
+        let err = context
+            .execute_tool(
+                "run_just_task",
+                &serde_json::json!({ "task_name": "migrate", "named_args": { "nme": "x" } }),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.contains("no parameter 'nme'"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_run_just_task_other_sources() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("Makefile"), "build:\n\techo built\n").unwrap();
+        let context = McpServerContext {
+            worktree_root: dir.path().to_path_buf(),
+            worktree_id: "test-worktree".to_string(),
+            project_name: "test-project".to_string(),
+        };
+
+        let err = context
+            .execute_tool("run_just_task", &serde_json::json!({ "task_name": "make:deploy" }))
+            .await
+            .unwrap_err();
+        assert!(err.contains("Unknown make target 'deploy'"));
+
+        let err = context
+            .execute_tool("run_just_task", &serde_json::json!({ "task_name": "npm:build" }))
+            .await
+            .unwrap_err();
+        assert!(err.contains("No package.json"));
+    }
+
     #[tokio::test]
     async fn test_mcp_server_manager_start_stop() {
         match TcpListener::bind("127.0.0.1:0").await {
@@ -0,0 +1,658 @@
+//! Problem matchers that turn task output into structured diagnostics.
+//!
+//! Output is fed line by line (as it streams in) to a [`DiagnosticCollector`],
+//! which runs every built-in matcher over it: rustc/cargo, tsc, eslint,
+//! pytest and go. Some formats span several lines (a rustc header followed
+//! by its `-->` location), so matchers keep state between lines.
+
+use crate::terminal::strip_ansi;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Maximum diagnostics kept per task run
+pub const MAX_DIAGNOSTICS: usize = 500;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl DiagnosticSeverity {
+    fn parse(text: &str) -> Self {
+        match text.to_ascii_lowercase().as_str() {
+            "error" | "fatal" => DiagnosticSeverity::Error,
+            "warning" | "warn" => DiagnosticSeverity::Warning,
+            _ => DiagnosticSeverity::Info,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Info => "info",
+        }
+    }
+}
+
+/// A problem found in task output.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Diagnostic {
+    /// File path (relative to the worktree when it lies inside it)
+    pub file: String,
+    /// 1-based line number
+    pub line: u32,
+    /// 1-based column, when the tool reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Matcher that produced it ("rustc", "tsc", "eslint", "pytest", "go")
+    pub source: String,
+    /// Task whose output contained it
+    pub task: String,
+    /// Run whose output contained it
+    #[serde(default)]
+    pub run_id: String,
+}
+
+impl Diagnostic {
+    /// `file:line:col: severity: message`, as fed to the AI context.
+    pub fn to_context_line(&self) -> String {
+        let location = match self.column {
+            Some(column) => format!("{}:{}:{}", self.file, self.line, column),
+            None => format!("{}:{}", self.file, self.line),
+        };
+        format!("{}: {}: {}", location, self.severity.as_str(), self.message)
+    }
+}
+
+/// Error and warning counts shown as an explorer badge.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiagnosticBadge {
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+/// A match before it's attributed to a task and worktree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub file: String,
+    pub line: u32,
+    pub column: Option<u32>,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+/// A built-in output format.
+pub trait ProblemMatcher: Send {
+    fn name(&self) -> &'static str;
+
+    /// Feed one line of output (escape sequences already stripped).
+    fn feed(&mut self, line: &str) -> Option<Match>;
+}
+
+/// All built-in matchers.
+pub fn matchers() -> Vec<Box<dyn ProblemMatcher>> {
+    vec![
+        Box::new(RustcMatcher::new()),
+        Box::new(TscMatcher::new()),
+        Box::new(EslintMatcher::new()),
+        Box::new(PytestMatcher::new()),
+        Box::new(GoMatcher::new()),
+    ]
+}
+
+fn number(text: Option<regex::Match>) -> Option<u32> {
+    text.and_then(|m| m.as_str().parse().ok())
+}
+
+// ============================================================================
+// rustc / cargo
+// ============================================================================
+
+/// `error[E0308]: message` followed by `--> file:line:col`, and test panics.
+pub struct RustcMatcher {
+    header: Regex,
+    location: Regex,
+    panic: Regex,
+    pending: Option<(DiagnosticSeverity, String)>,
+    pending_panic: Option<(String, u32, u32)>,
+}
+
+impl RustcMatcher {
+    pub fn new() -> Self {
+        Self {
+            header: Regex::new(r"^(error|warning)(?:\[(\w+)\])?: (.+)$").unwrap(),
+            location: Regex::new(r"^\s*--> (.+?):(\d+):(\d+)$").unwrap(),
+            panic: Regex::new(r"panicked at (.+?):(\d+):(\d+):?$").unwrap(),
+            pending: None,
+            pending_panic: None,
+        }
+    }
+}
+
+impl Default for RustcMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProblemMatcher for RustcMatcher {
+    fn name(&self) -> &'static str {
+        "rustc"
+    }
+
+    fn feed(&mut self, line: &str) -> Option<Match> {
+        if let Some((file, line_no, column)) = self.pending_panic.take() {
+            let message = line.trim();
+            return Some(Match {
+                file,
+                line: line_no,
+                column: Some(column),
+                severity: DiagnosticSeverity::Error,
+                message: if message.is_empty() {
+                    "panicked".to_string()
+                } else {
+                    format!("panicked: {}", message)
+                },
+            });
+        }
+
+        if let Some(caps) = self.header.captures(line) {
+            let message = match caps.get(2) {
+                Some(code) => format!("[{}] {}", code.as_str(), &caps[3]),
+                None => caps[3].to_string(),
+            };
+            self.pending = Some((DiagnosticSeverity::parse(&caps[1]), message));
+            return None;
+        }
+
+        if let Some(caps) = self.location.captures(line) {
+            // Only the primary location; notes and help carry their own `-->`
+            let (severity, message) = self.pending.take()?;
+            return Some(Match {
+                file: caps[1].to_string(),
+                line: number(caps.get(2))?,
+                column: number(caps.get(3)),
+                severity,
+                message,
+            });
+        }
+
+        if let Some(caps) = self.panic.captures(line) {
+            self.pending_panic = Some((
+                caps[1].to_string(),
+                number(caps.get(2))?,
+                number(caps.get(3))?,
+            ));
+        }
+        None
+    }
+}
+
+// ============================================================================
+// tsc
+// ============================================================================
+
+/// `file(line,col): error TS1234: message` and the `--pretty` form.
+pub struct TscMatcher {
+    plain: Regex,
+    pretty: Regex,
+}
+
+impl TscMatcher {
+    pub fn new() -> Self {
+        Self {
+            plain: Regex::new(r"^(.+?)\((\d+),(\d+)\): (error|warning) (TS\d+): (.+)$").unwrap(),
+            pretty: Regex::new(r"^(.+?):(\d+):(\d+) - (error|warning) (TS\d+): (.+)$").unwrap(),
+        }
+    }
+}
+
+impl Default for TscMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProblemMatcher for TscMatcher {
+    fn name(&self) -> &'static str {
+        "tsc"
+    }
+
+    fn feed(&mut self, line: &str) -> Option<Match> {
+        let caps = self.plain.captures(line).or_else(|| self.pretty.captures(line))?;
+        Some(Match {
+            file: caps[1].trim().to_string(),
+            line: number(caps.get(2))?,
+            column: number(caps.get(3)),
+            severity: DiagnosticSeverity::parse(&caps[4]),
+            message: format!("[{}] {}", &caps[5], &caps[6]),
+        })
+    }
+}
+
+// ============================================================================
+// eslint
+// ============================================================================
+
+/// The default `stylish` format (file line, then indented entries) and `compact`.
+pub struct EslintMatcher {
+    file: Regex,
+    entry: Regex,
+    compact: Regex,
+    current_file: Option<String>,
+}
+
+impl EslintMatcher {
+    pub fn new() -> Self {
+        Self {
+            file: Regex::new(r"^(\S.*\.(?:[cm]?[jt]sx?|vue|svelte))$").unwrap(),
+            entry: Regex::new(r"^\s+(\d+):(\d+)\s+(error|warning)\s+(.+?)(?:\s{2,}(\S+))?$").unwrap(),
+            compact: Regex::new(r"^(.+?): line (\d+), col (\d+), (Error|Warning) - (.+)$").unwrap(),
+            current_file: None,
+        }
+    }
+}
+
+impl Default for EslintMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProblemMatcher for EslintMatcher {
+    fn name(&self) -> &'static str {
+        "eslint"
+    }
+
+    fn feed(&mut self, line: &str) -> Option<Match> {
+        if line.trim().is_empty() {
+            self.current_file = None;
+            return None;
+        }
+
+        if let Some(caps) = self.compact.captures(line) {
+            return Some(Match {
+                file: caps[1].to_string(),
+                line: number(caps.get(2))?,
+                column: number(caps.get(3)),
+                severity: DiagnosticSeverity::parse(&caps[4]),
+                message: caps[5].to_string(),
+            });
+        }
+
+        if let Some(caps) = self.file.captures(line) {
+            self.current_file = Some(caps[1].to_string());
+            return None;
+        }
+
+        let file = self.current_file.clone()?;
+        let caps = self.entry.captures(line)?;
+        let message = match caps.get(5) {
+            Some(rule) => format!("{} ({})", &caps[4], rule.as_str()),
+            None => caps[4].to_string(),
+        };
+        Some(Match {
+            file,
+            line: number(caps.get(1))?,
+            column: number(caps.get(2)),
+            severity: DiagnosticSeverity::parse(&caps[3]),
+            message,
+        })
+    }
+}
+
+// ============================================================================
+// pytest
+// ============================================================================
+
+/// Failure locations (`tests/test_x.py:12: AssertionError`) with their `E` lines.
+pub struct PytestMatcher {
+    location: Regex,
+    explanation: Regex,
+    explained: Vec<String>,
+}
+
+impl PytestMatcher {
+    pub fn new() -> Self {
+        Self {
+            location: Regex::new(r"^(\S+\.py):(\d+): (\w*(?:Error|Exception|Failed|Exit)\b.*)$").unwrap(),
+            explanation: Regex::new(r"^E\s+(.+)$").unwrap(),
+            explained: Vec::new(),
+        }
+    }
+}
+
+impl Default for PytestMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProblemMatcher for PytestMatcher {
+    fn name(&self) -> &'static str {
+        "pytest"
+    }
+
+    fn feed(&mut self, line: &str) -> Option<Match> {
+        if let Some(caps) = self.explanation.captures(line) {
+            self.explained.push(caps[1].trim().to_string());
+            return None;
+        }
+
+        if line.starts_with("___") || line.starts_with("===") {
+            self.explained.clear();
+            return None;
+        }
+
+        let caps = self.location.captures(line)?;
+        let explained = std::mem::take(&mut self.explained);
+        let message = match explained.first() {
+            Some(first) => first.clone(),
+            None => caps[3].to_string(),
+        };
+        Some(Match {
+            file: caps[1].to_string(),
+            line: number(caps.get(2))?,
+            column: None,
+            severity: DiagnosticSeverity::Error,
+            message,
+        })
+    }
+}
+
+// ============================================================================
+// go
+// ============================================================================
+
+/// `./pkg/file.go:10:5: message` (build, vet) and `file_test.go:12: message` (test).
+pub struct GoMatcher {
+    location: Regex,
+}
+
+impl GoMatcher {
+    pub fn new() -> Self {
+        Self {
+            location: Regex::new(r"^\s*(?:vet: )?(?:\./)?(\S+\.go):(\d+)(?::(\d+))?: (.+)$").unwrap(),
+        }
+    }
+}
+
+impl Default for GoMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProblemMatcher for GoMatcher {
+    fn name(&self) -> &'static str {
+        "go"
+    }
+
+    fn feed(&mut self, line: &str) -> Option<Match> {
+        let caps = self.location.captures(line)?;
+        Some(Match {
+            file: caps[1].to_string(),
+            line: number(caps.get(2))?,
+            column: number(caps.get(3)),
+            severity: DiagnosticSeverity::Error,
+            message: caps[4].trim().to_string(),
+        })
+    }
+}
+
+// ============================================================================
+// Collector
+// ============================================================================
+
+/// Runs every matcher over a task's output and collects unique diagnostics.
+pub struct DiagnosticCollector {
+    task: String,
+    run_id: String,
+    root: String,
+    matchers: Vec<Box<dyn ProblemMatcher>>,
+    diagnostics: Vec<Diagnostic>,
+    seen: HashSet<(String, u32, Option<u32>, String)>,
+}
+
+impl DiagnosticCollector {
+    /// Collector for run `run_id` of `task` in the worktree at `root`.
+    pub fn new(task: &str, run_id: &str, root: &str) -> Self {
+        Self {
+            task: task.to_string(),
+            run_id: run_id.to_string(),
+            root: root.to_string(),
+            matchers: matchers(),
+            diagnostics: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Feed one raw output line. Returns true if new diagnostics were found.
+    pub fn feed(&mut self, line: &str) -> bool {
+        let line = strip_ansi(line);
+        let mut found = false;
+        for matcher in self.matchers.iter_mut() {
+            let Some(m) = matcher.feed(&line) else {
+                continue;
+            };
+            if self.diagnostics.len() >= MAX_DIAGNOSTICS {
+                continue;
+            }
+            let file = relative_path(&m.file, &self.root);
+            if !self
+                .seen
+                .insert((file.clone(), m.line, m.column, m.message.clone()))
+            {
+                continue;
+            }
+            self.diagnostics.push(Diagnostic {
+                file,
+                line: m.line,
+                column: m.column,
+                severity: m.severity,
+                message: m.message,
+                source: matcher.name().to_string(),
+                task: self.task.clone(),
+                run_id: self.run_id.clone(),
+            });
+            found = true;
+        }
+        found
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// Path relative to `root` when it lies inside it (leading `./` dropped).
+fn relative_path(file: &str, root: &str) -> String {
+    let file = file.trim().trim_start_matches("./");
+    match Path::new(file).strip_prefix(root) {
+        Ok(relative) => relative.to_string_lossy().to_string(),
+        Err(_) => file.to_string(),
+    }
+}
+
+/// Explorer badges keyed by absolute path.
+///
+/// Each file's counts are also added to every directory above it, up to and
+/// including the worktree root, so collapsed folders show their problems.
+pub fn diagnostic_badges(root: &str, diagnostics: &[Diagnostic]) -> HashMap<String, DiagnosticBadge> {
+    let root = Path::new(root);
+    let mut badges: HashMap<String, DiagnosticBadge> = HashMap::new();
+
+    for diagnostic in diagnostics {
+        let path = root.join(&diagnostic.file);
+        if !path.starts_with(root) {
+            continue;
+        }
+        for ancestor in path.ancestors() {
+            let badge = badges.entry(ancestor.to_string_lossy().to_string()).or_default();
+            match diagnostic.severity {
+                DiagnosticSeverity::Error => badge.errors += 1,
+                DiagnosticSeverity::Warning => badge.warnings += 1,
+                DiagnosticSeverity::Info => {}
+            }
+            if ancestor == root {
+                break;
+            }
+        }
+    }
+
+    badges.retain(|_, badge| badge.errors > 0 || badge.warnings > 0);
+    badges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(output: &str) -> Vec<Diagnostic> {
+        let mut collector = DiagnosticCollector::new("build", "r1", "/work/repo");
+        for line in output.lines() {
+            collector.feed(line);
+        }
+        collector.diagnostics().to_vec()
+    }
+
+    #[test]
+    fn test_rustc() {
+        let output = "\
+   Compiling app v0.1.0
+error[E0308]: mismatched types
+  --> src/main.rs:4:18
+   |
+4  |     let x: u32 = \"a\";
+   |            ---   ^^^ expected `u32`, found `&str`
+   |
+note: required by a bound in `foo`
+  --> src/lib.rs:1:1
+warning: unused variable: `y`
+ --> /work/repo/src/lib.rs:9:9
+error: could not compile `app` (bin \"app\") due to 1 previous error
+thread 'tests::it_works' panicked at src/lib.rs:20:9:
+assertion `left == right` failed";
+        let diagnostics = collect(output);
+        assert_eq!(diagnostics.len(), 3);
+
+        assert_eq!(diagnostics[0].file, "src/main.rs");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (4, Some(18)));
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostics[0].message, "[E0308] mismatched types");
+        assert_eq!(diagnostics[0].source, "rustc");
+
+        assert_eq!(diagnostics[1].file, "src/lib.rs");
+        assert_eq!(diagnostics[1].severity, DiagnosticSeverity::Warning);
+
+        assert_eq!(diagnostics[2].line, 20);
+        assert_eq!(diagnostics[2].message, "panicked: assertion `left == right` failed");
+    }
+
+    #[test]
+    fn test_tsc() {
+        let output = "\
+src/app.ts(12,5): error TS2322: Type 'string' is not assignable to type 'number'.
+\x1b[96msrc/util.ts\x1b[0m:3:1 - \x1b[91merror\x1b[0m \x1b[90mTS2304: \x1b[0mCannot find name 'foo'.";
+        let diagnostics = collect(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "src/app.ts");
+        assert_eq!(diagnostics[0].message, "[TS2322] Type 'string' is not assignable to type 'number'.");
+        assert_eq!(diagnostics[1].file, "src/util.ts");
+        assert_eq!((diagnostics[1].line, diagnostics[1].column), (3, Some(1)));
+        assert!(diagnostics.iter().all(|d| d.source == "tsc"));
+    }
+
+    #[test]
+    fn test_eslint() {
+        let output = "
+/work/repo/src/index.js
+  1:10  error    'foo' is defined but never used  no-unused-vars
+  4:1   warning  Unexpected console statement     no-console
+
+src/other.ts: line 2, col 3, Error - Missing semicolon. (semi)
+
+✖ 3 problems (2 errors, 1 warning)";
+        let diagnostics = collect(output);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].file, "src/index.js");
+        assert_eq!(diagnostics[0].message, "'foo' is defined but never used (no-unused-vars)");
+        assert_eq!(diagnostics[1].severity, DiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[2].file, "src/other.ts");
+        assert_eq!(diagnostics[2].line, 2);
+    }
+
+    #[test]
+    fn test_pytest() {
+        let output = "\
+______________________________ test_add ______________________________
+
+    def test_add():
+>       assert add(1, 2) == 4
+E       assert 3 == 4
+E        +  where 3 = add(1, 2)
+
+tests/test_math.py:7: AssertionError
+=========================== short test summary info ===========================
+FAILED tests/test_math.py::test_add - assert 3 == 4";
+        let diagnostics = collect(output);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, "tests/test_math.py");
+        assert_eq!(diagnostics[0].line, 7);
+        assert_eq!(diagnostics[0].column, None);
+        assert_eq!(diagnostics[0].message, "assert 3 == 4");
+        assert_eq!(diagnostics[0].source, "pytest");
+    }
+
+    #[test]
+    fn test_go() {
+        let output = "\
+# example.com/app
+./main.go:10:2: undefined: foo
+--- FAIL: TestAdd (0.00s)
+    math_test.go:12: expected 4, got 3
+FAIL";
+        let diagnostics = collect(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "main.go");
+        assert_eq!(diagnostics[0].message, "undefined: foo");
+        assert_eq!(diagnostics[1].file, "math_test.go");
+        assert_eq!((diagnostics[1].line, diagnostics[1].column), (12, None));
+    }
+
+    #[test]
+    fn test_collector_deduplicates() {
+        let mut collector = DiagnosticCollector::new("lint", "r1", "/work/repo");
+        assert!(collector.feed("src/a.ts(1,1): error TS1005: ';' expected."));
+        assert!(!collector.feed("src/a.ts(1,1): error TS1005: ';' expected."));
+        assert_eq!(collector.diagnostics()[0].task, "lint");
+    }
+
+    #[test]
+    fn test_diagnostic_badges() {
+        let diagnostics = collect(
+            "src/a.ts(1,1): error TS1: x\nsrc/a.ts(2,1): warning TS2: y\nsrc/ui/b.ts(1,1): error TS3: z",
+        );
+        let badges = diagnostic_badges("/work/repo", &diagnostics);
+        assert_eq!(badges["/work/repo/src/a.ts"], DiagnosticBadge { errors: 1, warnings: 1 });
+        assert_eq!(badges["/work/repo/src/ui"], DiagnosticBadge { errors: 1, warnings: 0 });
+        assert_eq!(badges["/work/repo/src"], DiagnosticBadge { errors: 2, warnings: 1 });
+        assert_eq!(badges["/work/repo"], DiagnosticBadge { errors: 2, warnings: 1 });
+        assert!(!badges.contains_key("/work"));
+    }
+
+    #[test]
+    fn test_context_line() {
+        let diagnostics = collect("./main.go:10:2: undefined: foo");
+        assert_eq!(diagnostics[0].to_context_line(), "main.go:10:2: error: undefined: foo");
+    }
+}
@@ -0,0 +1,101 @@
+//! Readiness probes for Docker services.
+//!
+//! A running container is not necessarily accepting connections: Postgres
+//! and MySQL run an init phase after start, during which clients are
+//! refused. Each service gets a probe telling when it is actually ready:
+//!
+//! - built-in databases and brokers: their own CLI ping (`pg_isready`,
+//!   `mysqladmin ping`, `redis-cli ping`, ...) run inside the container
+//! - containers with a Docker HEALTHCHECK: the health status Docker reports
+//! - anything else publishing a port: a TCP connect to the host port
+
+use crate::app_state::ServiceHealth;
+use std::time::Duration;
+
+/// How long to wait for a service after it was started
+pub const READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Delay between readiness checks
+pub const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Timeout for a single TCP readiness check
+pub const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How a service's readiness is checked
+#[derive(Debug, Clone, PartialEq)]
+pub enum Probe {
+    /// Command run in the container; ready when it exits with 0
+    Exec(Vec<&'static str>),
+    /// The container's Docker HEALTHCHECK status
+    DockerHealth,
+    /// TCP connect to a host port
+    Tcp(u16),
+}
+
+/// Readiness command of a built-in service
+///
+/// Postgres and MySQL are pinged over TCP: during their init phase they
+/// only listen on the Unix socket, which would report ready too early.
+pub fn builtin_probe(service_id: &str) -> Option<Probe> {
+    let cmd = match service_id {
+        "rstn-postgres" => vec!["pg_isready", "-h", "127.0.0.1", "-U", "postgres"],
+        "rstn-mysql" => vec!["mysqladmin", "ping", "-h", "127.0.0.1", "--silent"],
+        "rstn-mongodb" => vec!["mongosh", "--quiet", "--eval", "db.adminCommand('ping')"],
+        "rstn-redis" => vec!["redis-cli", "ping"],
+        "rstn-rabbitmq" => vec!["rabbitmq-diagnostics", "-q", "ping"],
+        _ => return None,
+    };
+    Some(Probe::Exec(cmd))
+}
+
+/// Pick the probe for a service: its built-in command, then the container's
+/// HEALTHCHECK, then a TCP check on its published port.
+///
+/// `None` means nothing can be checked, so a running container counts as ready.
+pub fn select_probe(service_id: &str, has_healthcheck: bool, host_port: Option<u16>) -> Option<Probe> {
+    builtin_probe(service_id)
+        .or_else(|| has_healthcheck.then_some(Probe::DockerHealth))
+        .or_else(|| host_port.map(Probe::Tcp))
+}
+
+/// Health Docker reports in a container's status text, e.g.
+/// "Up 5 minutes (healthy)" or "Up 2 seconds (health: starting)"
+pub fn parse_status_health(status: &str) -> Option<ServiceHealth> {
+    if status.contains("(healthy)") {
+        Some(ServiceHealth::Healthy)
+    } else if status.contains("(unhealthy)") {
+        Some(ServiceHealth::Unhealthy)
+    } else if status.contains("(health: starting)") {
+        Some(ServiceHealth::Starting)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_probe_prefers_builtin_then_healthcheck_then_tcp() {
+        assert!(matches!(
+            select_probe("rstn-postgres", true, Some(5432)),
+            Some(Probe::Exec(cmd)) if cmd[0] == "pg_isready"
+        ));
+        assert_eq!(select_probe("rstn-minio", true, Some(9000)), Some(Probe::DockerHealth));
+        assert_eq!(select_probe("rstn-nats", false, Some(4222)), Some(Probe::Tcp(4222)));
+        assert_eq!(select_probe("worker", false, None), None);
+    }
+
+    #[test]
+    fn test_parse_status_health() {
+        assert_eq!(parse_status_health("Up 5 minutes (healthy)"), Some(ServiceHealth::Healthy));
+        assert_eq!(parse_status_health("Up 1 hour (unhealthy)"), Some(ServiceHealth::Unhealthy));
+        assert_eq!(
+            parse_status_health("Up 2 seconds (health: starting)"),
+            Some(ServiceHealth::Starting)
+        );
+        assert_eq!(parse_status_health("Up 5 minutes"), None);
+        assert_eq!(parse_status_health("Exited (0) 3 hours ago"), None);
+    }
+}
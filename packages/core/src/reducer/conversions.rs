@@ -1,14 +1,58 @@
 use crate::actions::{
-    DockerServiceData, JustCommandData, TaskStatusData, McpStatusData, 
+    ComposeProjectData, DockerLogLineData, DockerServiceData, ServiceSnapshotData, JustCommandData, JustParameterData, TaskRunData, TaskRunStatsData,
+    TaskStatusData, McpStatusData,
     PortConflictData, ConflictingContainerData, FileEntryData, CommentData,
     ReviewPolicyData, ReviewContentTypeData, ReviewFileActionData, ReviewStatusData,
 };
 use crate::app_state::{
-    DockerServiceInfo, ServiceStatus, ServiceType, JustCommandInfo, TaskStatus,
+    ComposeProjectInfo, ComposeServiceInfo, DockerLogLine, DockerServiceInfo, ServiceHealth, ServiceSnapshotInfo, ServiceStatus, ServiceType, JustCommandInfo, JustParameterInfo,
+    JustParameterKind, TaskRunInfo, TaskRunStatsInfo, TaskStatus,
     McpStatus, PortConflict, ConflictingContainer, FileEntry, Comment,
     ReviewPolicy, ReviewContentType, ReviewFileAction, ReviewStatus,
 };
 
+impl From<DockerLogLineData> for DockerLogLine {
+    fn from(data: DockerLogLineData) -> Self {
+        Self {
+            stream: data.stream,
+            timestamp: data.timestamp,
+            text: data.text,
+        }
+    }
+}
+
+impl From<ServiceSnapshotData> for ServiceSnapshotInfo {
+    fn from(data: ServiceSnapshotData) -> Self {
+        Self {
+            id: data.id,
+            service_id: data.service_id,
+            database: data.database,
+            created_at: data.created_at,
+            size_bytes: data.size_bytes,
+        }
+    }
+}
+
+impl From<ComposeProjectData> for ComposeProjectInfo {
+    fn from(data: ComposeProjectData) -> Self {
+        Self {
+            name: data.name,
+            file: data.file,
+            services: data
+                .services
+                .into_iter()
+                .map(|s| ComposeServiceInfo {
+                    name: s.name,
+                    image: s.image,
+                    ports: s.ports,
+                    depends_on: s.depends_on,
+                    container_name: s.container_name,
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<DockerServiceData> for DockerServiceInfo {
     fn from(data: DockerServiceData) -> Self {
         Self {
@@ -31,6 +75,15 @@ impl From<DockerServiceData> for DockerServiceInfo {
             },
             project_group: data.project_group,
             is_rstn_managed: data.is_rstn_managed,
+            compose_service: data.compose_service,
+            health: match data.health.as_deref() {
+                Some("starting") => Some(ServiceHealth::Starting),
+                Some("healthy") => Some(ServiceHealth::Healthy),
+                Some("unhealthy") => Some(ServiceHealth::Unhealthy),
+                _ => None,
+            },
+            stats: None,
+            pull: None,
         }
     }
 }
@@ -39,8 +92,64 @@ impl From<JustCommandData> for JustCommandInfo {
     fn from(data: JustCommandData) -> Self {
         Self {
             name: data.name,
+            source: data.source,
             description: data.description,
             recipe: data.recipe,
+            parameters: data.parameters.into_iter().map(Into::into).collect(),
+            dependencies: data.dependencies,
+            group: data.group,
+            private: data.private,
+            aliases: data.aliases,
+            confirm: data.confirm,
+        }
+    }
+}
+
+impl From<JustParameterData> for JustParameterInfo {
+    fn from(data: JustParameterData) -> Self {
+        Self {
+            name: data.name,
+            default: data.default,
+            kind: match data.kind.as_str() {
+                "plus" => JustParameterKind::Plus,
+                "star" => JustParameterKind::Star,
+                _ => JustParameterKind::Singular,
+            },
+            export: data.export,
+        }
+    }
+}
+
+impl From<TaskRunData> for TaskRunInfo {
+    fn from(data: TaskRunData) -> Self {
+        Self {
+            id: data.id,
+            name: data.name,
+            command_line: data.command_line,
+            args: data.args,
+            named_args: data.named_args,
+            started_at: data.started_at,
+            finished_at: data.finished_at,
+            duration_ms: data.duration_ms,
+            exit_code: data.exit_code,
+            status: data.status.into(),
+            output: data.output,
+            output_truncated: data.output_truncated,
+        }
+    }
+}
+
+impl From<TaskRunStatsData> for TaskRunStatsInfo {
+    fn from(data: TaskRunStatsData) -> Self {
+        Self {
+            name: data.name,
+            runs: data.runs,
+            failures: data.failures,
+            flips: data.flips,
+            flaky: data.flaky,
+            avg_duration_ms: data.avg_duration_ms,
+            max_duration_ms: data.max_duration_ms,
+            last_duration_ms: data.last_duration_ms,
         }
     }
 }
@@ -52,6 +161,7 @@ impl From<TaskStatusData> for TaskStatus {
             TaskStatusData::Running => TaskStatus::Running,
             TaskStatusData::Success => TaskStatus::Success,
             TaskStatusData::Error => TaskStatus::Error,
+            TaskStatusData::Cancelled => TaskStatus::Cancelled,
         }
     }
 }
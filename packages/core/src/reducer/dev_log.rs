@@ -49,6 +49,12 @@ pub fn log_action_if_interesting(state: &mut AppState, action: &Action) {
         Action::StopDockerService { .. } => ("StopDockerService", true),
         Action::RestartDockerService { .. } => ("RestartDockerService", true),
         Action::SetPortConflict { .. } => ("SetPortConflict", true),
+        Action::ComposeUp { .. } => ("ComposeUp", true),
+        Action::ComposeStop { .. } => ("ComposeStop", true),
+        Action::ComposeRestart { .. } => ("ComposeRestart", true),
+        Action::CreateServiceSnapshot { .. } => ("CreateServiceSnapshot", true),
+        Action::RestoreServiceSnapshot { .. } => ("RestoreServiceSnapshot", true),
+        Action::DeleteServiceSnapshot { .. } => ("DeleteServiceSnapshot", true),
         Action::StartConstitutionWorkflow => ("StartConstitutionWorkflow", true),
         Action::ClearConstitutionWorkflow => ("ClearConstitutionWorkflow", true),
         Action::AnswerConstitutionQuestion { .. } => ("AnswerConstitutionQuestion", true),
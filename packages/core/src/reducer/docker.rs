@@ -1,5 +1,23 @@
 use crate::actions::Action;
-use crate::app_state::{AppState, ServiceStatus, PendingConflict};
+use crate::app_state::{
+    AppState, DockerLogFollow, DockerServiceInfo, PendingConflict, ServiceStats, ServiceStatus,
+    SqlConsoleState,
+};
+
+/// Followed log lines kept in state (oldest are dropped first)
+pub const MAX_LOG_LINES: usize = 5000;
+
+/// Resource usage samples kept per service for sparklines
+pub const STATS_HISTORY_LEN: usize = 60;
+
+/// The SQL console, if it is open for `service_id`
+fn sql_console_mut<'a>(state: &'a mut AppState, service_id: &str) -> Option<&'a mut SqlConsoleState> {
+    state
+        .docker
+        .sql_console
+        .as_mut()
+        .filter(|console| console.service_id == service_id)
+}
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -12,15 +30,89 @@ pub fn reduce(state: &mut AppState, action: Action) {
             state.docker.is_loading = false;
         }
 
-        Action::RefreshDockerServices => {
+        Action::RefreshDockerServices | Action::ReloadServiceTemplates => {
             state.docker.is_loading = true;
         }
 
         Action::SetDockerServices { services } => {
-            state.docker.services = services.into_iter().map(|s| s.into()).collect();
+            let previous = std::mem::take(&mut state.docker.services);
+            state.docker.services = services
+                .into_iter()
+                .map(|s| {
+                    let mut service: DockerServiceInfo = s.into();
+                    // Probe results and stats outlive refreshes while the
+                    // service keeps running
+                    if service.status == ServiceStatus::Running {
+                        if let Some(prev) = previous
+                            .iter()
+                            .find(|p| p.id == service.id && p.status == ServiceStatus::Running)
+                        {
+                            service.health = service.health.or(prev.health);
+                            service.stats = prev.stats.clone();
+                        }
+                    }
+                    // A pull outlives refreshes until it finishes
+                    service.pull = previous
+                        .iter()
+                        .find(|p| p.id == service.id)
+                        .and_then(|p| p.pull.clone());
+                    service
+                })
+                .collect();
             state.docker.is_loading = false;
         }
 
+        Action::SetDockerServiceStats { service_id, stats } => {
+            if let Some(service) = state
+                .docker
+                .services
+                .iter_mut()
+                .find(|s| s.id == service_id)
+            {
+                service.stats = stats.map(|sample| {
+                    let mut stats = service.stats.take().unwrap_or_default();
+                    push_sample(&mut stats.cpu_history, sample.cpu_percent);
+                    push_sample(&mut stats.memory_history, sample.memory_usage);
+                    ServiceStats {
+                        cpu_percent: sample.cpu_percent,
+                        memory_usage: sample.memory_usage,
+                        memory_limit: sample.memory_limit,
+                        net_rx_bytes: sample.net_rx_bytes,
+                        net_tx_bytes: sample.net_tx_bytes,
+                        block_read_bytes: sample.block_read_bytes,
+                        block_write_bytes: sample.block_write_bytes,
+                        ..stats
+                    }
+                });
+            }
+        }
+
+        Action::SetDockerServiceHealth { service_id, health } => {
+            if let Some(service) = state
+                .docker
+                .services
+                .iter_mut()
+                .find(|s| s.id == service_id)
+            {
+                service.health = health;
+            }
+        }
+
+        Action::SetDockerImagePull { service_id, pull } => {
+            if let Some(service) = state
+                .docker
+                .services
+                .iter_mut()
+                .find(|s| s.id == service_id)
+            {
+                service.pull = pull;
+            }
+        }
+
+        Action::UpdateDockerImage { .. } => {
+            // Async trigger
+        }
+
         Action::StartDockerService { service_id } => {
             if let Some(service) = state
                 .docker
@@ -40,6 +132,8 @@ pub fn reduce(state: &mut AppState, action: Action) {
                 .find(|s| s.id == service_id)
             {
                 service.status = ServiceStatus::Stopping;
+                service.health = None;
+                service.stats = None;
             }
         }
 
@@ -55,10 +149,63 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::SelectDockerService { service_id } => {
+            // Deselecting the followed service ends its subscription
+            if state.docker.log_follow.as_ref().map(|f| &f.service_id) != service_id.as_ref() {
+                state.docker.log_follow = None;
+                state.docker.log_lines.clear();
+            }
             state.docker.selected_service_id = service_id;
             state.docker.logs.clear();
         }
 
+        Action::FollowDockerLogs {
+            service_id,
+            stdout,
+            stderr,
+            timestamps,
+            since,
+            filter,
+        } => {
+            state.docker.log_follow = Some(DockerLogFollow {
+                service_id,
+                stdout,
+                stderr,
+                timestamps,
+                since,
+                filter: filter.filter(|f| !f.is_empty()),
+                is_streaming: true,
+            });
+            state.docker.log_lines.clear();
+        }
+
+        Action::StopFollowingDockerLogs => {
+            state.docker.log_follow = None;
+            state.docker.log_lines.clear();
+        }
+
+        // Lines from a replaced subscription are dropped
+        Action::AppendDockerLogLines { service_id, lines }
+            if state.docker.log_follow.as_ref().is_some_and(|f| f.service_id == service_id) =>
+        {
+            let log_lines = &mut state.docker.log_lines;
+            log_lines.extend(lines.into_iter().map(Into::into));
+            if log_lines.len() > MAX_LOG_LINES {
+                let excess = log_lines.len() - MAX_LOG_LINES;
+                log_lines.drain(..excess);
+            }
+        }
+
+        Action::DockerLogStreamEnded { service_id } => {
+            if let Some(follow) = state
+                .docker
+                .log_follow
+                .as_mut()
+                .filter(|f| f.service_id == service_id)
+            {
+                follow.is_streaming = false;
+            }
+        }
+
         Action::FetchDockerLogs { .. } => {
             state.docker.is_loading_logs = true;
         }
@@ -72,6 +219,86 @@ pub fn reduce(state: &mut AppState, action: Action) {
             // Async triggers
         }
 
+        Action::CreateServiceSnapshot { service_id, .. }
+        | Action::RestoreServiceSnapshot { service_id, .. } => {
+            state.docker.snapshot_in_progress = Some(service_id);
+        }
+
+        Action::DeleteServiceSnapshot { service_id, snapshot_id } => {
+            state
+                .docker
+                .snapshots
+                .retain(|s| !(s.service_id == service_id && s.id == snapshot_id));
+        }
+
+        Action::SetServiceSnapshots { snapshots } => {
+            state.docker.snapshots = snapshots.into_iter().map(Into::into).collect();
+            state.docker.snapshot_in_progress = None;
+        }
+
+        Action::OpenSqlConsole { service_id, database } => {
+            match state.docker.sql_console.as_mut() {
+                // Switching databases keeps the last result on screen
+                Some(console) if console.service_id == service_id => {
+                    console.database = database;
+                    console.is_loading_schema = true;
+                    console.error = None;
+                }
+                _ => {
+                    state.docker.sql_console = Some(SqlConsoleState {
+                        service_id,
+                        database,
+                        schema: Default::default(),
+                        is_loading_schema: true,
+                        is_running: false,
+                        result: None,
+                        error: None,
+                    });
+                }
+            }
+        }
+
+        Action::CloseSqlConsole => {
+            state.docker.sql_console = None;
+        }
+
+        Action::SetSqlSchema { service_id, schema } => {
+            if let Some(console) = sql_console_mut(state, &service_id) {
+                console.schema = schema;
+                console.is_loading_schema = false;
+            }
+        }
+
+        Action::RunSqlQuery { service_id, .. } => {
+            if let Some(console) = sql_console_mut(state, &service_id) {
+                console.is_running = true;
+                console.error = None;
+            }
+        }
+
+        Action::SetSqlQueryResult { service_id, result } => {
+            if let Some(console) = sql_console_mut(state, &service_id) {
+                console.result = Some(result);
+                console.is_running = false;
+            }
+        }
+
+        Action::SetSqlConsoleError { service_id, error } => {
+            if let Some(console) = sql_console_mut(state, &service_id) {
+                console.error = Some(error);
+                console.is_running = false;
+                console.is_loading_schema = false;
+            }
+        }
+
+        Action::LoadConnectionInfo { .. } | Action::ExportConnectionInfo { .. } => {
+            // Async triggers
+        }
+
+        Action::SetConnectionInfo { info } => {
+            state.docker.connection_info = info;
+        }
+
         Action::SetDockerConnectionString { connection_string } => {
             state.docker.last_connection_string = connection_string;
         }
@@ -112,6 +339,18 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::SetComposeProject { project } => {
+            state.docker.compose = project.map(Into::into);
+        }
+
+        Action::ComposeUp { services } | Action::ComposeRestart { services } => {
+            set_compose_status(state, &services, ServiceStatus::Starting);
+        }
+
+        Action::ComposeStop { services } => {
+            set_compose_status(state, &services, ServiceStatus::Stopping);
+        }
+
         Action::SetDockerLoading { is_loading } => {
             state.docker.is_loading = is_loading;
         }
@@ -122,3 +361,28 @@ pub fn reduce(state: &mut AppState, action: Action) {
         _ => {}
     }
 }
+
+/// Mark the compose project's services (all of them if `services` is empty)
+fn set_compose_status(state: &mut AppState, services: &[String], status: ServiceStatus) {
+    let Some(project) = state.docker.compose.as_ref().map(|c| c.name.clone()) else {
+        return;
+    };
+    for service in state.docker.services.iter_mut() {
+        let Some(name) = service.compose_service.as_ref() else {
+            continue;
+        };
+        if service.project_group.as_deref() == Some(project.as_str())
+            && (services.is_empty() || services.contains(name))
+        {
+            service.status = status;
+        }
+    }
+}
+
+/// Append to a rolling history, dropping the oldest past `STATS_HISTORY_LEN`
+fn push_sample<T>(history: &mut Vec<T>, sample: T) {
+    history.push(sample);
+    if history.len() > STATS_HISTORY_LEN {
+        history.remove(0);
+    }
+}
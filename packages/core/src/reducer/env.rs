@@ -1,5 +1,5 @@
 use crate::actions::Action;
-use crate::app_state::{AppState, EnvCopyResult};
+use crate::app_state::{AppState, EnvCopyResult, WorktreeDatabase};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -31,6 +31,53 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::SetTerminalEnvOverride { key, value } => {
+            if let Some(project) = state.active_project_mut() {
+                project.env_config.terminal_env_overrides.insert(key, value);
+            }
+            super::terminal::save_active_project(state);
+        }
+
+        Action::RemoveTerminalEnvOverride { key } => {
+            if let Some(project) = state.active_project_mut() {
+                project.env_config.terminal_env_overrides.remove(&key);
+            }
+            super::terminal::save_active_project(state);
+        }
+
+        Action::SetWorktreeDbConfig { enabled, service_id, template_database, env_var } => {
+            if let Some(project) = state.active_project_mut() {
+                let config = &mut project.env_config.worktree_db;
+                config.enabled = enabled;
+                config.service_id = service_id;
+                config.template_database = template_database.filter(|t| !t.is_empty());
+                config.env_var = env_var;
+            }
+            super::terminal::save_active_project(state);
+        }
+
+        Action::SetWorktreeDatabase { worktree_path, database } => {
+            if let Some(project) = state.active_project_mut() {
+                let databases = &mut project.env_config.worktree_db.databases;
+                match database {
+                    Some(db) => {
+                        databases.insert(
+                            worktree_path,
+                            WorktreeDatabase {
+                                service_id: db.service_id,
+                                name: db.name,
+                                connection_string: db.connection_string,
+                            },
+                        );
+                    }
+                    None => {
+                        databases.remove(&worktree_path);
+                    }
+                }
+            }
+            super::terminal::save_active_project(state);
+        }
+
         Action::SetAgentRulesEnabled { enabled } => {
             if let Some(project) = state.active_project_mut() {
                 project.agent_rules_config.enabled = enabled;
@@ -103,20 +103,47 @@ pub fn reduce(state: &mut AppState, action: Action) {
         Action::CheckDockerAvailability
         | Action::SetDockerAvailable { .. }
         | Action::RefreshDockerServices
+        | Action::ReloadServiceTemplates
         | Action::SetDockerServices { .. }
         | Action::StartDockerService { .. }
         | Action::StopDockerService { .. }
         | Action::RestartDockerService { .. }
+        | Action::SetDockerServiceHealth { .. }
+        | Action::SetDockerServiceStats { .. }
+        | Action::UpdateDockerImage { .. }
+        | Action::SetDockerImagePull { .. }
         | Action::SelectDockerService { .. }
         | Action::FetchDockerLogs { .. }
         | Action::SetDockerLogs { .. }
+        | Action::FollowDockerLogs { .. }
+        | Action::StopFollowingDockerLogs
+        | Action::AppendDockerLogLines { .. }
+        | Action::DockerLogStreamEnded { .. }
         | Action::CreateDatabase { .. }
         | Action::CreateVhost { .. }
+        | Action::LoadConnectionInfo { .. }
+        | Action::ExportConnectionInfo { .. }
+        | Action::SetConnectionInfo { .. }
         | Action::SetDockerConnectionString { .. }
+        | Action::CreateServiceSnapshot { .. }
+        | Action::RestoreServiceSnapshot { .. }
+        | Action::DeleteServiceSnapshot { .. }
+        | Action::RefreshServiceSnapshots
+        | Action::SetServiceSnapshots { .. }
+        | Action::OpenSqlConsole { .. }
+        | Action::CloseSqlConsole
+        | Action::SetSqlSchema { .. }
+        | Action::RunSqlQuery { .. }
+        | Action::SetSqlQueryResult { .. }
+        | Action::SetSqlConsoleError { .. }
         | Action::SetPortConflict { .. }
         | Action::ClearPortConflict
         | Action::StartDockerServiceWithPort { .. }
         | Action::ResolveConflictByStoppingContainer { .. }
+        | Action::SetComposeProject { .. }
+        | Action::ComposeUp { .. }
+        | Action::ComposeStop { .. }
+        | Action::ComposeRestart { .. }
         | Action::SetDockerLoading { .. }
         | Action::SetDockerLogsLoading { .. } => {
             docker::reduce(state, action);
@@ -126,12 +153,27 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::RefreshJustfile
         | Action::SetJustfileCommands { .. }
         | Action::RunJustCommand { .. }
+        | Action::TaskRunStarted { .. }
+        | Action::CancelJustCommand { .. }
+        | Action::JustCommandFinished { .. }
         | Action::SetTaskStatus { .. }
-        | Action::SetActiveCommand { .. }
+        | Action::SelectTaskRun { .. }
         | Action::AppendTaskOutput { .. }
-        | Action::ClearTaskOutput
+        | Action::ClearTaskOutput { .. }
+        | Action::DismissTaskRun { .. }
+        | Action::StartTaskWatch { .. }
+        | Action::StopTaskWatch { .. }
+        | Action::TaskWatchTriggered { .. }
         | Action::SetTasksLoading { .. }
-        | Action::SetTasksError { .. } => {
+        | Action::SetTasksError { .. }
+        | Action::LoadTaskHistory
+        | Action::SetTaskHistory { .. }
+        | Action::ViewTaskRun { .. }
+        | Action::SetViewedTaskRun { .. }
+        | Action::CloseTaskRun
+        | Action::RerunTask { .. }
+        | Action::SetTaskDiagnostics { .. }
+        | Action::ClearDiagnostics => {
             tasks::reduce(state, action);
         }
 
@@ -152,11 +194,34 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::SpawnTerminal { .. }
+        | Action::CreateTerminalSession { .. }
+        | Action::ExecContainerShell { .. }
+        | Action::RenameTerminalSession { .. }
+        | Action::MoveTerminalSession { .. }
+        | Action::SelectTerminalSession { .. }
+        | Action::CloseTerminalSession { .. }
         | Action::ResizeTerminal { .. }
         | Action::WriteTerminal { .. }
         | Action::KillTerminal { .. }
         | Action::SetTerminalSession { .. }
-        | Action::SetTerminalSize { .. } => {
+        | Action::SetTerminalSessionRunning { .. }
+        | Action::SetTerminalSize { .. }
+        | Action::TerminalExited { .. }
+        | Action::SearchTerminalScrollback { .. }
+        | Action::SetTerminalSearchResults { .. }
+        | Action::ClearTerminalSearch
+        | Action::ClearTerminalScrollback { .. }
+        | Action::TerminalCommandFinished { .. }
+        | Action::ExplainTerminalFailure { .. }
+        | Action::StartTerminalRecording { .. }
+        | Action::StopTerminalRecording { .. }
+        | Action::SetTerminalRecording { .. }
+        | Action::ReplayTerminalRecording { .. }
+        | Action::StopTerminalReplay { .. }
+        | Action::SetTerminalReplay { .. }
+        | Action::TerminalReplayFinished { .. }
+        | Action::RefreshTerminalEnv
+        | Action::SetTerminalEnv { .. } => {
             terminal::reduce(state, action);
         }
 
@@ -321,6 +386,10 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::SetEnvTrackedPatterns { .. }
         | Action::SetEnvAutoCopy { .. }
         | Action::SetEnvSourceWorktree { .. }
+        | Action::SetTerminalEnvOverride { .. }
+        | Action::RemoveTerminalEnvOverride { .. }
+        | Action::SetWorktreeDbConfig { .. }
+        | Action::SetWorktreeDatabase { .. }
         | Action::SetAgentRulesEnabled { .. }
         | Action::SetAgentRulesPrompt { .. }
         | Action::SetAgentRulesTempFile { .. }
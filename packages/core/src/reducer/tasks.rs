@@ -1,5 +1,12 @@
 use crate::actions::Action;
-use crate::app_state::{AppState, TaskStatus};
+use crate::app_state::{AppState, TaskRun, TaskStatus, TaskWatch, TasksState, WorktreeState};
+use crate::problem_matcher::{diagnostic_badges, Diagnostic};
+
+/// Output lines kept per run (oldest lines are dropped)
+const MAX_RUN_OUTPUT_LINES: usize = 10_000;
+
+/// Finished runs kept per worktree alongside the running ones
+const MAX_FINISHED_RUNS: usize = 20;
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -21,56 +28,239 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
-        Action::RunJustCommand { name, .. } => {
+        Action::RunJustCommand { .. } | Action::CancelJustCommand { .. } => {
+            // Async triggers; TaskRunStarted / JustCommandFinished report the run
+        }
+
+        Action::TaskRunStarted {
+            run_id,
+            name,
+            cwd,
+            command_line,
+        } => {
+            let Some(worktree) = worktree_at_path(state, &cwd) else {
+                return;
+            };
+            let tasks = &mut worktree.tasks;
+            tasks.task_statuses.insert(name.clone(), TaskStatus::Running);
+            tasks.runs.push(TaskRun {
+                id: run_id.clone(),
+                name: name.clone(),
+                cwd,
+                command_line,
+                status: TaskStatus::Running,
+                exit_code: None,
+                output: Vec::new(),
+                started_at: chrono::Utc::now().to_rfc3339(),
+            });
+            tasks.selected_run = Some(run_id);
+            prune_finished_runs(tasks);
+            refresh_task_activity(worktree);
+            // A new run reports its own problems: drop those of finished
+            // runs of the task, keeping runs still going
+            let running: Vec<String> = worktree
+                .tasks
+                .runs
+                .iter()
+                .filter(|r| r.status == TaskStatus::Running)
+                .map(|r| r.id.clone())
+                .collect();
+            let stale = |d: &Diagnostic| d.task == name && !running.contains(&d.run_id);
+            if worktree.diagnostics.iter().any(stale) {
+                worktree.diagnostics.retain(|d| !stale(d));
+                refresh_diagnostic_badges(worktree);
+            }
+        }
+
+        Action::StartTaskWatch {
+            name,
+            cwd,
+            patterns,
+            restart,
+            debounce_ms,
+            args,
+            named_args,
+        } => {
+            let Some(worktree) = worktree_at_path(state, &cwd) else {
+                return;
+            };
+            let watch = TaskWatch {
+                name,
+                patterns,
+                restart,
+                debounce_ms: debounce_ms
+                    .unwrap_or(crate::task_watch::DEFAULT_DEBOUNCE.as_millis() as u64),
+                args,
+                named_args,
+                triggers: 0,
+                last_triggered_at: None,
+                last_changed: Vec::new(),
+            };
+            let watches = &mut worktree.tasks.watches;
+            match watches.iter_mut().find(|w| w.name == watch.name) {
+                Some(existing) => *existing = watch,
+                None => watches.push(watch),
+            }
+            refresh_task_activity(worktree);
+        }
+
+        Action::StopTaskWatch { name, cwd } => {
+            if let Some(worktree) = worktree_at_path(state, &cwd) {
+                worktree.tasks.watches.retain(|w| w.name != name);
+                refresh_task_activity(worktree);
+            }
+        }
+
+        Action::TaskWatchTriggered { name, cwd, changed } => {
+            let Some(worktree) = worktree_at_path(state, &cwd) else {
+                return;
+            };
+            if let Some(watch) = worktree.tasks.watches.iter_mut().find(|w| w.name == name) {
+                watch.triggers += 1;
+                watch.last_triggered_at = Some(chrono::Utc::now().to_rfc3339());
+                watch.last_changed = changed;
+            }
+        }
+
+        Action::JustCommandFinished {
+            run_id,
+            exit_code,
+            cancelled,
+        } => {
+            let status = if cancelled {
+                TaskStatus::Cancelled
+            } else if exit_code == Some(0) {
+                TaskStatus::Success
+            } else {
+                TaskStatus::Error
+            };
+            if let Some(worktree) = worktree_with_run(state, &run_id) {
+                finish_run(worktree, &run_id, status, exit_code);
+            }
+        }
+
+        Action::SetTaskStatus { run_id, status } => {
+            if let Some(worktree) = worktree_with_run(state, &run_id) {
+                let status: TaskStatus = status.into();
+                if status == TaskStatus::Running || status == TaskStatus::Idle {
+                    if let Some(run) = worktree.tasks.runs.iter_mut().find(|r| r.id == run_id) {
+                        run.status = status;
+                    }
+                } else {
+                    let exit_code = worktree
+                        .tasks
+                        .runs
+                        .iter()
+                        .find(|r| r.id == run_id)
+                        .and_then(|r| r.exit_code);
+                    finish_run(worktree, &run_id, status, exit_code);
+                }
+            }
+        }
+
+        Action::SelectTaskRun { run_id } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
-                    worktree.tasks.active_command = Some(name.clone());
-                    worktree.tasks.task_statuses.insert(name, TaskStatus::Running);
-                    worktree.tasks.output.clear();
-                    worktree.is_modified = true;
+                    worktree.tasks.selected_run = run_id;
+                }
+            }
+        }
+
+        Action::AppendTaskOutput { run_id, line } => {
+            if let Some(run) = worktree_with_run(state, &run_id)
+                .and_then(|w| w.tasks.runs.iter_mut().find(|r| r.id == run_id))
+            {
+                run.output.push(line);
+                if run.output.len() > MAX_RUN_OUTPUT_LINES {
+                    let excess = run.output.len() - MAX_RUN_OUTPUT_LINES;
+                    run.output.drain(..excess);
+                }
+            }
+        }
+
+        Action::ClearTaskOutput { run_id } => {
+            if let Some(run) = worktree_with_run(state, &run_id)
+                .and_then(|w| w.tasks.runs.iter_mut().find(|r| r.id == run_id))
+            {
+                run.output.clear();
+            }
+        }
+
+        Action::DismissTaskRun { run_id } => {
+            if let Some(worktree) = worktree_with_run(state, &run_id) {
+                let tasks = &mut worktree.tasks;
+                tasks
+                    .runs
+                    .retain(|r| r.id != run_id || r.status == TaskStatus::Running);
+                if tasks.selected_run.as_ref() == Some(&run_id)
+                    && !tasks.runs.iter().any(|r| r.id == run_id)
+                {
+                    tasks.selected_run = tasks.runs.last().map(|r| r.id.clone());
                 }
             }
         }
 
-        Action::SetTaskStatus { name, status } => {
+        Action::SetTasksLoading { is_loading } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
-                    worktree.tasks.task_statuses.insert(name, status.into());
-                    if matches!(status, crate::actions::TaskStatusData::Success | crate::actions::TaskStatusData::Error) {
-                        worktree.is_modified = false;
-                    }
+                    worktree.tasks.is_loading = is_loading;
                 }
             }
         }
 
-        Action::SetActiveCommand { name } => {
+        Action::LoadTaskHistory | Action::ViewTaskRun { .. } | Action::RerunTask { .. } => {
+            // Async triggers; results arrive through SetTaskHistory / SetViewedTaskRun
+        }
+
+        Action::SetTaskHistory { runs, stats } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
-                    worktree.tasks.active_command = name;
+                    worktree.tasks.history = runs.into_iter().map(Into::into).collect();
+                    worktree.tasks.history_stats = stats.into_iter().map(Into::into).collect();
                 }
             }
         }
 
-        Action::AppendTaskOutput { line } => {
+        Action::SetViewedTaskRun { run } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
-                    worktree.tasks.output.push(line);
+                    worktree.tasks.viewed_run = run.map(Into::into);
                 }
             }
         }
 
-        Action::ClearTaskOutput => {
+        Action::CloseTaskRun => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
-                    worktree.tasks.output.clear();
+                    worktree.tasks.viewed_run = None;
                 }
             }
         }
 
-        Action::SetTasksLoading { is_loading } => {
+        Action::SetTaskDiagnostics { run_id, name, cwd, diagnostics } => {
+            let Some(worktree) = worktree_at_path(state, &cwd) else {
+                return;
+            };
+            // Concurrent runs of a task each keep their own diagnostics
+            worktree.diagnostics.retain(|d| d.run_id != run_id);
+            worktree.diagnostics.extend(diagnostics.into_iter().map(|d| Diagnostic {
+                file: d.file,
+                line: d.line,
+                column: d.column,
+                severity: d.severity,
+                message: d.message,
+                source: d.source,
+                task: name.clone(),
+                run_id: run_id.clone(),
+            }));
+            refresh_diagnostic_badges(worktree);
+        }
+
+        Action::ClearDiagnostics => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
-                    worktree.tasks.is_loading = is_loading;
+                    worktree.diagnostics.clear();
+                    refresh_diagnostic_badges(worktree);
                 }
             }
         }
@@ -86,3 +276,86 @@ pub fn reduce(state: &mut AppState, action: Action) {
         _ => {}
     }
 }
+
+/// Recompute explorer badges from the worktree's diagnostics
+fn refresh_diagnostic_badges(worktree: &mut WorktreeState) {
+    worktree.explorer.diagnostic_badges = diagnostic_badges(&worktree.path, &worktree.diagnostics);
+}
+
+/// Worktree a task started in `cwd` belongs to (falls back to the active worktree)
+fn worktree_at_path<'a>(state: &'a mut AppState, cwd: &str) -> Option<&'a mut WorktreeState> {
+    let by_path = state
+        .projects
+        .iter()
+        .enumerate()
+        .find_map(|(p, project)| {
+            project
+                .worktrees
+                .iter()
+                .position(|w| w.path == cwd)
+                .map(|w| (p, w))
+        });
+    match by_path {
+        Some((p, w)) => state.projects.get_mut(p)?.worktrees.get_mut(w),
+        None => state.active_project_mut()?.active_worktree_mut(),
+    }
+}
+
+/// Worktree that owns a run
+fn worktree_with_run<'a>(state: &'a mut AppState, run_id: &str) -> Option<&'a mut WorktreeState> {
+    state
+        .projects
+        .iter_mut()
+        .flat_map(|p| p.worktrees.iter_mut())
+        .find(|w| w.tasks.runs.iter().any(|r| r.id == run_id))
+}
+
+/// Record a run's outcome and mirror it into the per-task status
+fn finish_run(worktree: &mut WorktreeState, run_id: &str, status: TaskStatus, exit_code: Option<i32>) {
+    let tasks = &mut worktree.tasks;
+    let Some(run) = tasks.runs.iter_mut().find(|r| r.id == run_id) else {
+        return;
+    };
+    run.status = status;
+    run.exit_code = exit_code;
+    let name = run.name.clone();
+
+    // The task list shows the latest outcome unless another run is still going
+    let still_running = tasks
+        .runs
+        .iter()
+        .any(|r| r.name == name && r.status == TaskStatus::Running);
+    if !still_running {
+        tasks.task_statuses.insert(name.clone(), status);
+    }
+    match exit_code {
+        Some(code) => tasks.exit_codes.insert(name, code),
+        None => tasks.exit_codes.remove(&name),
+    };
+    refresh_task_activity(worktree);
+}
+
+/// Mark the worktree busy while a task runs or is watched
+fn refresh_task_activity(worktree: &mut WorktreeState) {
+    let tasks = &worktree.tasks;
+    worktree.is_modified =
+        !tasks.watches.is_empty() || tasks.runs.iter().any(|r| r.status == TaskStatus::Running);
+}
+
+/// Drop the oldest finished runs beyond the limit
+fn prune_finished_runs(tasks: &mut TasksState) {
+    let finished = tasks
+        .runs
+        .iter()
+        .filter(|r| r.status != TaskStatus::Running)
+        .count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_RUNS);
+    tasks.runs.retain(|r| {
+        if excess > 0 && r.status != TaskStatus::Running {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
@@ -1,17 +1,145 @@
 use crate::actions::Action;
 use crate::app_state::AppState;
+use crate::docker;
+use crate::persistence;
+use crate::terminal::{
+    CommandRecord, ContainerShellTarget, ScrollbackMatch, TerminalEnvVar, TerminalReplayState,
+    TerminalSearchState, TerminalState, MAX_STATE_COMMANDS,
+};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
         Action::SpawnTerminal { cols, rows } => {
+            let mut created = false;
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     worktree.terminal.cols = cols;
                     worktree.terminal.rows = rows;
+
+                    // Ensure there is an active session to start the shell in
+                    let has_active = worktree
+                        .terminal
+                        .session_id
+                        .as_deref()
+                        .is_some_and(|id| worktree.terminal.session(id).is_some());
+                    if !has_active {
+                        match worktree.terminal.sessions.first() {
+                            Some(first) => worktree.terminal.session_id = Some(first.id.clone()),
+                            None => {
+                                worktree.terminal.add_session(None);
+                                created = true;
+                            }
+                        }
+                    }
+                }
+            }
+            if created {
+                save_active_project(state);
+            }
+        }
+
+        Action::CreateTerminalSession { name, cols, rows } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.terminal.cols = cols;
+                    worktree.terminal.rows = rows;
+                    worktree.terminal.add_session(name);
+                }
+            }
+            save_active_project(state);
+        }
+
+        Action::ExecContainerShell { service_id, shell } => {
+            let shell = shell
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| docker::default_shell(&service_id).to_string());
+            let service_name = state
+                .docker
+                .services
+                .iter()
+                .find(|s| s.id == service_id)
+                .map(|s| s.name.clone())
+                .unwrap_or_else(|| service_id.clone());
+            let program = shell.split_whitespace().next().unwrap_or("sh");
+            let name = format!("{} ({})", program, service_name);
+
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree
+                        .terminal
+                        .add_container_session(name, ContainerShellTarget { service_id, shell });
+                }
+            }
+            save_active_project(state);
+        }
+
+        Action::RenameTerminalSession { session_id, name } => {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(session) = worktree.terminal.session_mut(&session_id) {
+                        session.name = name;
+                    }
+                }
+            }
+            save_active_project(state);
+        }
+
+        Action::MoveTerminalSession { session_id, to_index } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.terminal.move_session(&session_id, to_index);
+                }
+            }
+            save_active_project(state);
+        }
+
+        Action::SelectTerminalSession { session_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if worktree.terminal.session(&session_id).is_some() {
+                        worktree.terminal.session_id = Some(session_id);
+                    }
                 }
             }
         }
 
+        Action::CloseTerminalSession { session_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.terminal.remove_session(&session_id);
+                    clear_search_for(&mut worktree.terminal, &session_id);
+                }
+            }
+            save_active_project(state);
+        }
+
+        Action::ResizeTerminal { cols, rows, .. } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.terminal.cols = cols;
+                    worktree.terminal.rows = rows;
+                }
+            }
+        }
+
+        Action::KillTerminal { session_id }
+        | Action::TerminalExited { session_id } => {
+            // The session may belong to any worktree, not just the active one
+            set_session_running(state, &session_id, false);
+        }
+
+        Action::SetTerminalSessionRunning {
+            session_id,
+            is_running,
+        } => {
+            set_session_running(state, &session_id, is_running);
+        }
+
         Action::SetTerminalSession { session_id } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -20,6 +148,151 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::SearchTerminalScrollback { .. } => {
+            // Async trigger
+        }
+
+        Action::SetTerminalSearchResults {
+            session_id,
+            pattern,
+            matches,
+        } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.terminal.search = Some(TerminalSearchState {
+                        session_id,
+                        pattern,
+                        matches: matches
+                            .into_iter()
+                            .map(|m| ScrollbackMatch {
+                                line: m.line,
+                                text: m.text,
+                                start: m.start,
+                                end: m.end,
+                            })
+                            .collect(),
+                    });
+                }
+            }
+        }
+
+        Action::ClearTerminalSearch => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.terminal.search = None;
+                }
+            }
+        }
+
+        Action::ClearTerminalScrollback { session_id } => {
+            // Stale results would point at lines that no longer exist
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    clear_search_for(&mut worktree.terminal, &session_id);
+                }
+            }
+        }
+
+        Action::TerminalCommandFinished {
+            session_id,
+            command,
+        } => {
+            let record = CommandRecord {
+                id: command.id,
+                command: command.command,
+                cwd: command.cwd,
+                started_at: command.started_at,
+                duration_ms: command.duration_ms,
+                exit_code: command.exit_code,
+                output_start: command.output_start,
+                output_end: command.output_end,
+            };
+            // The session may belong to any worktree, not just the active one
+            for project in state.projects.iter_mut() {
+                for worktree in project.worktrees.iter_mut() {
+                    if let Some(session) = worktree.terminal.session_mut(&session_id) {
+                        session.commands.push(record.clone());
+                        let overflow = session.commands.len().saturating_sub(MAX_STATE_COMMANDS);
+                        session.commands.drain(..overflow);
+                    }
+                }
+            }
+        }
+
+        Action::ExplainTerminalFailure { .. } => {
+            // Async trigger (builds a chat message from the failed command)
+        }
+
+        Action::StartTerminalRecording { .. }
+        | Action::StopTerminalRecording { .. }
+        | Action::ReplayTerminalRecording { .. }
+        | Action::StopTerminalReplay { .. } => {
+            // Async triggers
+        }
+
+        Action::SetTerminalRecording { session_id, path } => {
+            for project in state.projects.iter_mut() {
+                for worktree in project.worktrees.iter_mut() {
+                    if let Some(session) = worktree.terminal.session_mut(&session_id) {
+                        session.recording_path = path.clone();
+                    }
+                }
+            }
+        }
+
+        Action::SetTerminalReplay { replay } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.terminal.replay = Some(TerminalReplayState {
+                        id: replay.id,
+                        path: replay.path,
+                        speed: replay.speed,
+                        width: replay.width,
+                        height: replay.height,
+                    });
+                }
+            }
+        }
+
+        Action::TerminalReplayFinished { replay_id } => {
+            // The user may have switched worktrees while the replay ran
+            for project in state.projects.iter_mut() {
+                for worktree in project.worktrees.iter_mut() {
+                    if worktree
+                        .terminal
+                        .replay
+                        .as_ref()
+                        .is_some_and(|r| r.id == replay_id)
+                    {
+                        worktree.terminal.replay = None;
+                    }
+                }
+            }
+        }
+
+        Action::RefreshTerminalEnv => {
+            // Async trigger
+        }
+
+        Action::SetTerminalEnv {
+            worktree_path,
+            vars,
+        } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.worktrees.iter_mut().find(|w| w.path == worktree_path) {
+                    worktree.terminal.env = vars
+                        .into_iter()
+                        .map(|v| TerminalEnvVar {
+                            key: v.key,
+                            value: v.value,
+                            source: v.source,
+                            origin: v.origin,
+                        })
+                        .collect();
+                }
+            }
+        }
+
         Action::SetTerminalSize { cols, rows } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -31,3 +304,32 @@ pub fn reduce(state: &mut AppState, action: Action) {
         _ => {}
     }
 }
+
+fn clear_search_for(terminal: &mut TerminalState, session_id: &str) {
+    if terminal
+        .search
+        .as_ref()
+        .is_some_and(|search| search.session_id == session_id)
+    {
+        terminal.search = None;
+    }
+}
+
+fn set_session_running(state: &mut AppState, session_id: &str, is_running: bool) {
+    for project in state.projects.iter_mut() {
+        for worktree in project.worktrees.iter_mut() {
+            if let Some(session) = worktree.terminal.session_mut(session_id) {
+                session.is_running = is_running;
+            }
+        }
+    }
+}
+
+/// Persist project-level changes (only for real paths)
+pub(super) fn save_active_project(state: &AppState) {
+    if let Some(project) = state.active_project() {
+        if std::path::Path::new(&project.path).exists() {
+            let _ = persistence::save_project(project);
+        }
+    }
+}
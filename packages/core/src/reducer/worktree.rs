@@ -1,5 +1,6 @@
 use crate::actions::Action;
 use crate::app_state::{AppState, WorktreeState};
+use crate::persistence;
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -17,9 +18,27 @@ pub fn reduce(state: &mut AppState, action: Action) {
 
         Action::SetWorktrees { worktrees } => {
             if let Some(project) = state.active_project_mut() {
+                // Persisted terminal sessions for worktrees we haven't seen yet
+                let persisted = if std::path::Path::new(&project.path).exists() {
+                    persistence::load_project(&project.path).ok().flatten()
+                } else {
+                    None
+                };
+
                 let new_worktrees: Vec<WorktreeState> = worktrees
                     .into_iter()
-                    .map(|w| WorktreeState::new(w.path, w.branch, w.is_main))
+                    .map(|w| {
+                        let mut worktree = WorktreeState::new(w.path, w.branch, w.is_main);
+                        // Keep terminal sessions of worktrees that are still present
+                        if let Some(existing) =
+                            project.worktrees.iter().find(|e| e.path == worktree.path)
+                        {
+                            worktree.terminal = existing.terminal.clone();
+                        } else if let Some(persisted) = &persisted {
+                            persisted.restore_terminal_sessions(&mut worktree);
+                        }
+                        worktree
+                    })
                     .collect();
 
                 project.worktrees = new_worktrees;
@@ -0,0 +1,539 @@
+//! User-defined Docker service templates.
+//!
+//! Templates come from `~/.rstn/services.yaml` (per user) and
+//! `.rstn/services.yaml` (per project) and are merged over the built-in
+//! services by ID, later layers winning:
+//!
+//! ```yaml
+//! services:
+//!   minio:
+//!     name: MinIO
+//!     image: minio/minio:latest
+//!     type: other              # database | cache | message_broker | other
+//!     ports: ["9000:9000", 9001]
+//!     env:
+//!       MINIO_ROOT_USER: minio
+//!     volumes: ["rstn-minio-data:/data"]
+//!     command: server /data --console-address :9001
+//!     healthcheck:
+//!       test: ["CMD", "curl", "-f", "http://localhost:9000/minio/health/live"]
+//!       interval: 10s
+//! ```
+
+use crate::docker::{ServiceConfig, BUILTIN_SERVICES};
+use crate::state::ServiceType;
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+
+/// Templates file name, under `~/.rstn/` and `<project>/.rstn/`
+pub const TEMPLATES_FILE: &str = "services.yaml";
+
+/// Where a template was defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateOrigin {
+    Builtin,
+    User,
+    Project,
+}
+
+/// A host port published to a container port
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    pub host: u16,
+    pub container: u16,
+}
+
+/// Container health check (Docker HEALTHCHECK semantics)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthCheck {
+    /// `["CMD", ...]` or `["CMD-SHELL", "..."]`
+    pub test: Vec<String>,
+    pub interval: Option<Duration>,
+    pub timeout: Option<Duration>,
+    pub retries: Option<u32>,
+    pub start_period: Option<Duration>,
+}
+
+/// A service that can be started as an rstn-managed container
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceTemplate {
+    /// Container name (`rstn-<key>`)
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    /// The first mapping is the service's primary port
+    pub ports: Vec<PortMapping>,
+    pub env: Vec<(String, String)>,
+    /// Docker bind specs (`volume:/path` or `/host/path:/path[:ro]`)
+    pub volumes: Vec<String>,
+    /// Overrides the image's default command when not empty
+    pub command: Vec<String>,
+    pub health_check: Option<HealthCheck>,
+    pub service_type: ServiceType,
+    pub origin: TemplateOrigin,
+}
+
+impl ServiceTemplate {
+    /// Primary port mapping, if the service publishes any
+    pub fn primary_port(&self) -> Option<PortMapping> {
+        self.ports.first().copied()
+    }
+}
+
+impl From<&ServiceConfig> for ServiceTemplate {
+    fn from(config: &ServiceConfig) -> Self {
+        Self {
+            id: config.id.to_string(),
+            name: config.name.to_string(),
+            image: config.image.to_string(),
+            ports: vec![PortMapping { host: config.port, container: config.internal_port }],
+            env: config
+                .env
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            volumes: config
+                .data_dir
+                .map(|dir| vec![format!("{}-data:{}", config.id, dir)])
+                .unwrap_or_default(),
+            command: Vec::new(),
+            health_check: None,
+            service_type: config.service_type,
+            origin: TemplateOrigin::Builtin,
+        }
+    }
+}
+
+/// The built-in services as templates
+pub fn builtin_templates() -> Vec<ServiceTemplate> {
+    BUILTIN_SERVICES.iter().map(ServiceTemplate::from).collect()
+}
+
+/// Built-in templates merged with the user's and the project's.
+///
+/// Returns the merged templates and one error per file that couldn't be
+/// read or parsed (its templates are skipped).
+pub fn load_templates(project_root: Option<&Path>) -> (Vec<ServiceTemplate>, Vec<String>) {
+    let mut templates = builtin_templates();
+    let mut errors = Vec::new();
+
+    let user_dir = crate::persistence::get_rstn_dir();
+    let mut files = vec![(user_dir.join(TEMPLATES_FILE), user_dir.clone(), TemplateOrigin::User)];
+    if let Some(root) = project_root {
+        files.push((root.join(".rstn").join(TEMPLATES_FILE), root.to_path_buf(), TemplateOrigin::Project));
+    }
+
+    for (path, base, origin) in files {
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        match parse_templates(&text, &base, origin) {
+            Ok(layer) => merge_templates(&mut templates, layer),
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    (templates, errors)
+}
+
+/// Replace templates with the same ID and append new ones
+pub fn merge_templates(templates: &mut Vec<ServiceTemplate>, layer: Vec<ServiceTemplate>) {
+    for template in layer {
+        match templates.iter_mut().find(|t| t.id == template.id) {
+            Some(existing) => *existing = template,
+            None => templates.push(template),
+        }
+    }
+}
+
+/// Parse a YAML document (services.yaml, compose files) into a JSON value,
+/// resolving anchors, aliases and `<<` merge keys
+pub(crate) fn parse_yaml(text: &str) -> Result<Value, String> {
+    let mut doc: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+    doc.apply_merge().map_err(|e| e.to_string())?;
+    serde_json::to_value(doc).map_err(|e| e.to_string())
+}
+
+/// Scalar value as a string (numbers and booleans included), for fields
+/// like environment variables that YAML may type as non-strings
+pub(crate) fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Null => Some(String::new()),
+        _ => None,
+    }
+}
+
+/// Parse a services.yaml file. Relative bind-mount paths (`./data:/data`)
+/// resolve against `base`.
+pub fn parse_templates(text: &str, base: &Path, origin: TemplateOrigin) -> Result<Vec<ServiceTemplate>, String> {
+    let doc = parse_yaml(text)?;
+    let services = match doc.get("services") {
+        Some(Value::Object(services)) => services,
+        Some(Value::Null) | None => return Ok(Vec::new()),
+        Some(_) => return Err("`services` must be a mapping".to_string()),
+    };
+
+    services
+        .iter()
+        .map(|(key, spec)| {
+            parse_template(key, spec, base, origin).map_err(|e| format!("service {}: {}", key, e))
+        })
+        .collect()
+}
+
+fn parse_template(key: &str, spec: &Value, base: &Path, origin: TemplateOrigin) -> Result<ServiceTemplate, String> {
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("name must contain only letters, digits, `-` and `_`".to_string());
+    }
+    let Value::Object(spec) = spec else {
+        return Err("expected a mapping".to_string());
+    };
+
+    let image = spec
+        .get("image")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .ok_or("`image` is required")?
+        .to_string();
+    let id = if key.starts_with("rstn-") {
+        key.to_string()
+    } else {
+        format!("rstn-{}", key)
+    };
+
+    Ok(ServiceTemplate {
+        name: spec
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(key.trim_start_matches("rstn-"))
+            .to_string(),
+        ports: list(spec.get("ports"), "ports")?
+            .iter()
+            .map(parse_port)
+            .collect::<Result<_, _>>()?,
+        env: parse_env(spec.get("env").or_else(|| spec.get("environment")))?,
+        volumes: list(spec.get("volumes"), "volumes")?
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(|v| resolve_volume(v, base))
+                    .ok_or_else(|| "`volumes` entries must be strings".to_string())
+            })
+            .collect::<Result<_, _>>()?,
+        command: parse_command(spec.get("command"), "command")?,
+        health_check: spec.get("healthcheck").map(parse_health_check).transpose()?,
+        service_type: match spec.get("type").and_then(Value::as_str) {
+            Some(kind) => parse_service_type(kind)?,
+            None => ServiceType::Other,
+        },
+        origin,
+        id,
+        image,
+    })
+}
+
+fn list<'a>(value: Option<&'a Value>, field: &str) -> Result<&'a [Value], String> {
+    match value {
+        None | Some(Value::Null) => Ok(&[]),
+        Some(Value::Array(items)) => Ok(items),
+        Some(_) => Err(format!("`{}` must be a list", field)),
+    }
+}
+
+/// `"8080:80"`, `"127.0.0.1:8080:80"`, `"80/tcp"` or `80`
+fn parse_port(value: &Value) -> Result<PortMapping, String> {
+    let text = scalar_string(value).ok_or("`ports` entries must be strings or numbers")?;
+    let spec = text.split('/').next().unwrap_or_default();
+    let parts: Vec<&str> = spec.split(':').collect();
+    let port = |s: &str| {
+        s.trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|p| *p > 0)
+            .ok_or_else(|| format!("invalid port: {}", text))
+    };
+    match parts.as_slice() {
+        [container] => {
+            let container = port(container)?;
+            Ok(PortMapping { host: container, container })
+        }
+        [.., host, container] => Ok(PortMapping { host: port(host)?, container: port(container)? }),
+        [] => Err(format!("invalid port: {}", text)),
+    }
+}
+
+/// Mapping (`KEY: value`) or list (`KEY=value`) form
+fn parse_env(value: Option<&Value>) -> Result<Vec<(String, String)>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Object(map)) => map
+            .iter()
+            .map(|(k, v)| {
+                scalar_string(v)
+                    .map(|v| (k.clone(), v))
+                    .ok_or_else(|| format!("env {} must be a scalar", k))
+            })
+            .collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                let item = item.as_str().ok_or("`env` entries must be strings")?;
+                let (k, v) = item.split_once('=').unwrap_or((item, ""));
+                Ok((k.to_string(), v.to_string()))
+            })
+            .collect(),
+        Some(_) => Err("`env` must be a mapping or a list".to_string()),
+    }
+}
+
+/// A list of arguments, or a string split on whitespace
+fn parse_command(value: Option<&Value>, field: &str) -> Result<Vec<String>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(s)) => Ok(s.split_whitespace().map(str::to_string).collect()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| scalar_string(item).ok_or_else(|| format!("`{}` entries must be strings", field)))
+            .collect(),
+        Some(_) => Err(format!("`{}` must be a string or a list", field)),
+    }
+}
+
+fn parse_health_check(value: &Value) -> Result<HealthCheck, String> {
+    let Value::Object(spec) = value else {
+        return Err("`healthcheck` must be a mapping".to_string());
+    };
+    let test = match spec.get("test") {
+        // A plain string runs through the container's shell
+        Some(Value::String(cmd)) => vec!["CMD-SHELL".to_string(), cmd.clone()],
+        other => parse_command(other, "healthcheck.test")?,
+    };
+    if test.is_empty() {
+        return Err("`healthcheck.test` is required".to_string());
+    }
+    let duration = |field: &str| {
+        spec.get(field)
+            .and_then(scalar_string)
+            .map(|s| parse_duration(&s).ok_or_else(|| format!("invalid healthcheck.{}: {}", field, s)))
+            .transpose()
+    };
+    Ok(HealthCheck {
+        test,
+        interval: duration("interval")?,
+        timeout: duration("timeout")?,
+        start_period: duration("start_period")?,
+        retries: spec
+            .get("retries")
+            .map(|r| {
+                r.as_u64()
+                    .and_then(|r| u32::try_from(r).ok())
+                    .ok_or("`healthcheck.retries` must be a number")
+            })
+            .transpose()?,
+    })
+}
+
+/// Parse Go-style durations (`500ms`, `10s`, `1m30s`, `2h`); bare numbers
+/// are seconds. Negative, infinite or too large durations are rejected.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    if let Ok(secs) = text.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .filter(|i| *i > 0)?;
+        let amount: f64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let scale = match &rest[..unit_len] {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return None,
+        };
+        total = total.checked_add(Duration::try_from_secs_f64(amount * scale).ok()?)?;
+        rest = &rest[unit_len..];
+    }
+    (!text.is_empty()).then_some(total)
+}
+
+fn parse_service_type(kind: &str) -> Result<ServiceType, String> {
+    match kind.to_ascii_lowercase().replace(['-', ' '], "_").as_str() {
+        "database" | "db" => Ok(ServiceType::Database),
+        "cache" => Ok(ServiceType::Cache),
+        "message_broker" | "messagebroker" | "broker" => Ok(ServiceType::MessageBroker),
+        "other" => Ok(ServiceType::Other),
+        _ => Err(format!("unknown type: {} (database, cache, message_broker or other)", kind)),
+    }
+}
+
+/// Make relative bind-mount sources absolute; named volumes are kept
+fn resolve_volume(spec: &str, base: &Path) -> String {
+    match spec.split_once(':') {
+        Some((source, target)) if source.starts_with('.') || source.starts_with('~') => {
+            let source = match source.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+                None => base.join(source.trim_start_matches("./")),
+            };
+            format!("{}:{}", source.display(), target)
+        }
+        _ => spec.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATES: &str = r#"
+services:
+  minio:
+    name: MinIO
+    image: minio/minio:latest
+    ports: ["9000:9000", 9001]
+    env:
+      MINIO_ROOT_USER: minio
+      MINIO_PORT: 9000
+    volumes:
+      - rstn-minio-data:/data
+      - ./fixtures:/fixtures:ro
+    command: server /data --console-address :9001
+    healthcheck:
+      test: curl -f http://localhost:9000/minio/health/live
+      interval: 10s
+      timeout: 1m30s
+      retries: 3
+  postgres:
+    image: postgres:17
+    type: database
+    ports: ["127.0.0.1:5433:5432/tcp"]
+    environment:
+      - POSTGRES_PASSWORD=secret
+"#;
+
+    #[test]
+    fn test_parse_templates() {
+        let templates = parse_templates(TEMPLATES, Path::new("/work/app"), TemplateOrigin::Project).unwrap();
+        assert_eq!(templates.len(), 2);
+
+        let minio = &templates[0];
+        assert_eq!(minio.id, "rstn-minio");
+        assert_eq!(minio.name, "MinIO");
+        assert_eq!(
+            minio.ports,
+            vec![
+                PortMapping { host: 9000, container: 9000 },
+                PortMapping { host: 9001, container: 9001 }
+            ]
+        );
+        assert_eq!(
+            minio.env,
+            vec![
+                ("MINIO_PORT".to_string(), "9000".to_string()),
+                ("MINIO_ROOT_USER".to_string(), "minio".to_string())
+            ]
+        );
+        assert_eq!(minio.volumes, vec!["rstn-minio-data:/data", "/work/app/fixtures:/fixtures:ro"]);
+        assert_eq!(minio.command, vec!["server", "/data", "--console-address", ":9001"]);
+        let health = minio.health_check.as_ref().unwrap();
+        assert_eq!(health.test[0], "CMD-SHELL");
+        assert_eq!(health.interval, Some(Duration::from_secs(10)));
+        assert_eq!(health.timeout, Some(Duration::from_secs(90)));
+        assert_eq!(health.retries, Some(3));
+        assert_eq!(minio.service_type, ServiceType::Other);
+
+        let postgres = &templates[1];
+        assert_eq!(postgres.id, "rstn-postgres");
+        assert_eq!(postgres.name, "postgres");
+        assert_eq!(postgres.primary_port(), Some(PortMapping { host: 5433, container: 5432 }));
+        assert_eq!(postgres.env, vec![("POSTGRES_PASSWORD".to_string(), "secret".to_string())]);
+        assert_eq!(postgres.service_type, ServiceType::Database);
+    }
+
+    #[test]
+    fn test_builtins_keep_data_in_named_volumes() {
+        let templates = builtin_templates();
+        let volumes = |id: &str| templates.iter().find(|t| t.id == id).unwrap().volumes.clone();
+        assert_eq!(volumes("rstn-postgres"), vec!["rstn-postgres-data:/var/lib/postgresql/data"]);
+        assert_eq!(volumes("rstn-redis"), vec!["rstn-redis-data:/data"]);
+        assert!(volumes("rstn-nats").is_empty());
+    }
+
+    #[test]
+    fn test_merge_overrides_builtins_by_id() {
+        let mut templates = builtin_templates();
+        let builtin_count = templates.len();
+        let layer = parse_templates(TEMPLATES, Path::new("/p"), TemplateOrigin::User).unwrap();
+        merge_templates(&mut templates, layer);
+
+        assert_eq!(templates.len(), builtin_count + 1);
+        let postgres = templates.iter().find(|t| t.id == "rstn-postgres").unwrap();
+        assert_eq!(postgres.image, "postgres:17");
+        assert_eq!(postgres.origin, TemplateOrigin::User);
+        assert_eq!(templates.last().unwrap().id, "rstn-minio");
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        let base = Path::new("/p");
+        let err = |text: &str| parse_templates(text, base, TemplateOrigin::Project).unwrap_err();
+        assert!(err("services:\n  x:\n    name: no image\n").contains("image"));
+        assert!(err("services:\n  x:\n    image: a\n    ports: [\"http\"]\n").contains("invalid port"));
+        assert!(err("services:\n  x:\n    image: a\n    type: queue\n").contains("unknown type"));
+        assert!(err("services:\n  \"bad name\":\n    image: a\n").contains("name must"));
+        assert!(err("services: [a]\n").contains("mapping"));
+        assert!(parse_templates("# nothing yet\n", base, TemplateOrigin::Project).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_templates_with_anchors() {
+        let text = r#"
+services:
+  api: &api
+    image: shop/api
+    env:
+      LOG_LEVEL: debug
+  api-next:
+    <<: *api
+    image: shop/api:next
+"#;
+        let templates = parse_templates(text, Path::new("/p"), TemplateOrigin::Project).unwrap();
+        let next = templates.iter().find(|t| t.id == "rstn-api-next").unwrap();
+        assert_eq!(next.image, "shop/api:next");
+        assert_eq!(next.env, vec![("LOG_LEVEL".to_string(), "debug".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("5"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("inf"), None);
+        assert_eq!(parse_duration("NaN"), None);
+        assert_eq!(parse_duration("-1"), None);
+        assert_eq!(parse_duration("1e30"), None);
+        assert_eq!(parse_duration("99999999999999999999h"), None);
+        assert_eq!(parse_duration("10000000000000000000s10000000000000000000s"), None);
+    }
+
+    #[test]
+    fn test_load_templates_reports_bad_project_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".rstn")).unwrap();
+        std::fs::write(dir.path().join(".rstn").join(TEMPLATES_FILE), "services:\n  x: {}\n").unwrap();
+        let (templates, errors) = load_templates(Some(dir.path()));
+        assert!(templates.iter().any(|t| t.id == "rstn-postgres"));
+        assert!(errors.iter().any(|e| e.contains("image")));
+    }
+}
@@ -0,0 +1,348 @@
+//! Database snapshots for rstn-managed services.
+//!
+//! Snapshots are dump files stored under `~/.rstn/snapshots/<service>/`,
+//! named `<database>-<UTC timestamp>.<ext>` (`<database>-<UTC timestamp>-<n>.<ext>`
+//! for further snapshots taken in the same second). This module owns the file
+//! layout and the dump/restore commands; `DockerManager` runs the commands
+//! inside the service's container.
+
+use chrono::{DateTime, NaiveDateTime, Timelike, Utc};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Timestamp format used in snapshot file names
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Redis data file inside the container (the image's default `dir`)
+pub const REDIS_DUMP_PATH: &str = "/data/dump.rdb";
+
+/// Database label used for Redis snapshots (Redis has no named databases)
+const REDIS_LABEL: &str = "redis";
+
+/// Snapshots of one database that can be taken within the same second
+const MAX_SNAPSHOTS_PER_SECOND: u32 = 100;
+
+/// How a service's data is dumped and restored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotKind {
+    Postgres,
+    MySql,
+    MongoDb,
+    Redis,
+}
+
+impl SnapshotKind {
+    /// Snapshot support for a built-in service
+    pub fn for_service(service_id: &str) -> Option<Self> {
+        match service_id {
+            "rstn-postgres" => Some(Self::Postgres),
+            "rstn-mysql" => Some(Self::MySql),
+            "rstn-mongodb" => Some(Self::MongoDb),
+            "rstn-redis" => Some(Self::Redis),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Postgres | Self::MySql => "sql",
+            Self::MongoDb => "archive",
+            Self::Redis => "rdb",
+        }
+    }
+
+    /// Whether snapshots are of a single named database
+    pub fn has_databases(self) -> bool {
+        !matches!(self, Self::Redis)
+    }
+
+    /// Command writing the dump of `database` to stdout.
+    ///
+    /// Redis dumps in two steps: `SAVE`, then reading `REDIS_DUMP_PATH`.
+    pub fn dump_command(self, database: &str) -> Vec<String> {
+        let cmd: &[&str] = match self {
+            Self::Postgres => &["pg_dump", "-U", "postgres", "--no-owner", "--no-privileges", database],
+            Self::MySql => &["mysqldump", "-u", "root", "-pmysql", "--single-transaction", "--routines", database],
+            Self::MongoDb => &["mongodump", "--quiet", "--archive", "--db", database],
+            Self::Redis => &["cat", REDIS_DUMP_PATH],
+        };
+        cmd.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// Command reading a dump of `source` from stdin into `target`.
+    ///
+    /// Postgres and MySQL expect `target` to have been created first.
+    pub fn restore_command(self, source: &str, target: &str) -> Vec<String> {
+        match self {
+            Self::Postgres => vec![
+                "psql".into(),
+                "-U".into(),
+                "postgres".into(),
+                "-q".into(),
+                "-v".into(),
+                "ON_ERROR_STOP=1".into(),
+                "-d".into(),
+                target.into(),
+            ],
+            Self::MySql => vec!["mysql".into(), "-u".into(), "root".into(), "-pmysql".into(), target.into()],
+            Self::MongoDb => vec![
+                "mongorestore".into(),
+                "--quiet".into(),
+                "--archive".into(),
+                format!("--nsFrom={}.*", source),
+                format!("--nsTo={}.*", target),
+            ],
+            Self::Redis => vec!["sh".into(), "-c".into(), format!("cat > {}", REDIS_DUMP_PATH)],
+        }
+    }
+}
+
+/// A snapshot file on disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// File name, unique per service
+    pub id: String,
+    pub service_id: String,
+    pub database: String,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    pub path: PathBuf,
+}
+
+/// Root directory for snapshots (`~/.rstn/snapshots`)
+pub fn snapshots_root() -> PathBuf {
+    crate::persistence::get_rstn_dir().join("snapshots")
+}
+
+/// Database names are limited to letters, digits and `_`, as for
+/// `CreateDatabase`
+pub fn validate_database_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err("Database name must contain only alphanumeric characters and underscores".to_string());
+    }
+    Ok(())
+}
+
+/// Database a snapshot is taken of. Redis ignores `database`; the others
+/// require it.
+pub fn snapshot_database(kind: SnapshotKind, database: Option<&str>) -> Result<String, String> {
+    if !kind.has_databases() {
+        return Ok(REDIS_LABEL.to_string());
+    }
+    let database = database
+        .filter(|d| !d.is_empty())
+        .ok_or("A database name is required")?;
+    validate_database_name(database)?;
+    Ok(database.to_string())
+}
+
+/// File name of a snapshot; `seq` numbers the ones after the first taken
+/// in the same second (0 for the first)
+pub fn snapshot_file_name(database: &str, created_at: DateTime<Utc>, kind: SnapshotKind, seq: u32) -> String {
+    let timestamp = created_at.format(TIMESTAMP_FORMAT);
+    match seq {
+        0 => format!("{}-{}.{}", database, timestamp, kind.extension()),
+        seq => format!("{}-{}-{}.{}", database, timestamp, seq, kind.extension()),
+    }
+}
+
+/// Split a snapshot file name into its database and creation time
+pub fn parse_snapshot_file_name(name: &str) -> Option<(String, DateTime<Utc>)> {
+    let (stem, _ext) = name.rsplit_once('.')?;
+    let (mut database, mut timestamp) = stem.rsplit_once('-')?;
+    if !timestamp.is_empty() && timestamp.chars().all(|c| c.is_ascii_digit()) {
+        (database, timestamp) = database.rsplit_once('-')?;
+    }
+    if database.is_empty() {
+        return None;
+    }
+    let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()?
+        .and_utc();
+    Some((database.to_string(), created_at))
+}
+
+/// Write a dump to `<root>/<service>/` and return it
+pub fn save_snapshot(
+    root: &Path,
+    service_id: &str,
+    kind: SnapshotKind,
+    database: &str,
+    data: &[u8],
+) -> Result<Snapshot, String> {
+    let dir = root.join(service_id);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    // File names have second resolution; never overwrite a snapshot taken
+    // in the same second
+    let created_at = Utc::now().with_nanosecond(0).unwrap_or_else(Utc::now);
+    let mut seq = 0;
+    let (id, path, mut file) = loop {
+        let id = snapshot_file_name(database, created_at, kind, seq);
+        let path = dir.join(&id);
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => break (id, path, file),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && seq < MAX_SNAPSHOTS_PER_SECOND => {
+                seq += 1;
+            }
+            Err(e) => return Err(format!("Failed to create {}: {}", path.display(), e)),
+        }
+    };
+    if let Err(e) = file.write_all(data) {
+        let _ = std::fs::remove_file(&path);
+        return Err(format!("Failed to write {}: {}", path.display(), e));
+    }
+
+    Ok(Snapshot {
+        id,
+        service_id: service_id.to_string(),
+        database: database.to_string(),
+        created_at,
+        size_bytes: data.len() as u64,
+        path,
+    })
+}
+
+/// Snapshots of one service, newest first
+pub fn list_snapshots(root: &Path, service_id: &str) -> Vec<Snapshot> {
+    let Ok(entries) = std::fs::read_dir(root.join(service_id)) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<Snapshot> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+            let id = entry.file_name().to_string_lossy().to_string();
+            let (database, created_at) = parse_snapshot_file_name(&id)?;
+            Some(Snapshot {
+                id,
+                service_id: service_id.to_string(),
+                database,
+                created_at,
+                size_bytes: meta.len(),
+                path: entry.path(),
+            })
+        })
+        .collect();
+    // Within a second, numbered (longer) names are the later snapshots
+    snapshots.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| b.id.len().cmp(&a.id.len()))
+            .then_with(|| b.id.cmp(&a.id))
+    });
+    snapshots
+}
+
+/// Snapshots of every service, grouped by service and newest first
+pub fn list_all_snapshots(root: &Path) -> Vec<Snapshot> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut services: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    services.sort();
+    services
+        .iter()
+        .flat_map(|service| list_snapshots(root, service))
+        .collect()
+}
+
+/// Look up a snapshot by ID
+pub fn find_snapshot(root: &Path, service_id: &str, snapshot_id: &str) -> Result<Snapshot, String> {
+    list_snapshots(root, service_id)
+        .into_iter()
+        .find(|s| s.id == snapshot_id)
+        .ok_or_else(|| format!("Snapshot not found: {}", snapshot_id))
+}
+
+pub fn delete_snapshot(root: &Path, service_id: &str, snapshot_id: &str) -> Result<(), String> {
+    let snapshot = find_snapshot(root, service_id, snapshot_id)?;
+    std::fs::remove_file(&snapshot.path)
+        .map_err(|e| format!("Failed to delete {}: {}", snapshot.path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_file_names_round_trip() {
+        let created = Utc.with_ymd_and_hms(2026, 10, 17, 9, 30, 5).unwrap();
+        let name = snapshot_file_name("app_dev", created, SnapshotKind::Postgres, 0);
+        assert_eq!(name, "app_dev-20261017T093005Z.sql");
+        assert_eq!(parse_snapshot_file_name(&name), Some(("app_dev".to_string(), created)));
+        let second = snapshot_file_name("app_dev", created, SnapshotKind::Postgres, 2);
+        assert_eq!(second, "app_dev-20261017T093005Z-2.sql");
+        assert_eq!(parse_snapshot_file_name(&second), Some(("app_dev".to_string(), created)));
+
+        assert_eq!(parse_snapshot_file_name("notes.txt"), None);
+        assert_eq!(parse_snapshot_file_name("-20261017T093005Z.sql"), None);
+        assert_eq!(parse_snapshot_file_name("db-yesterday.sql"), None);
+    }
+
+    #[test]
+    fn test_snapshot_database() {
+        assert_eq!(snapshot_database(SnapshotKind::Redis, None).unwrap(), "redis");
+        assert_eq!(snapshot_database(SnapshotKind::MySql, Some("shop")).unwrap(), "shop");
+        assert!(snapshot_database(SnapshotKind::Postgres, None).is_err());
+        assert!(snapshot_database(SnapshotKind::Postgres, Some("a; DROP")).is_err());
+    }
+
+    #[test]
+    fn test_restore_command_renames_mongo_namespace() {
+        let cmd = SnapshotKind::MongoDb.restore_command("app", "app_copy");
+        assert!(cmd.contains(&"--nsFrom=app.*".to_string()));
+        assert!(cmd.contains(&"--nsTo=app_copy.*".to_string()));
+        assert_eq!(SnapshotKind::Postgres.restore_command("app", "copy").last().unwrap(), "copy");
+    }
+
+    #[test]
+    fn test_save_list_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("rstn-postgres")).unwrap();
+        std::fs::write(root.join("rstn-postgres/app-20250101T000000Z.sql"), "old").unwrap();
+        std::fs::write(root.join("rstn-postgres/README"), "ignored").unwrap();
+
+        let saved = save_snapshot(root, "rstn-postgres", SnapshotKind::Postgres, "app", b"dump").unwrap();
+        assert_eq!(saved.size_bytes, 4);
+        assert_eq!(std::fs::read(&saved.path).unwrap(), b"dump");
+        save_snapshot(root, "rstn-redis", SnapshotKind::Redis, "redis", b"rdb").unwrap();
+
+        let listed = list_snapshots(root, "rstn-postgres");
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0], saved);
+        assert_eq!(listed[1].id, "app-20250101T000000Z.sql");
+
+        let all = list_all_snapshots(root);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[2].service_id, "rstn-redis");
+
+        // Another snapshot in the same second doesn't replace the first
+        // (taken files are laid out for this second and the next)
+        let now = Utc::now().with_nanosecond(0).unwrap();
+        for created in [now, now + chrono::Duration::seconds(1)] {
+            let name = snapshot_file_name("shop", created, SnapshotKind::Postgres, 0);
+            std::fs::write(root.join("rstn-postgres").join(name), "taken").unwrap();
+        }
+        let again = save_snapshot(root, "rstn-postgres", SnapshotKind::Postgres, "shop", b"dump 2").unwrap();
+        assert!(again.id.ends_with("-1.sql"));
+        assert_eq!(std::fs::read(&again.path).unwrap(), b"dump 2");
+        let listed = list_snapshots(root, "rstn-postgres");
+        assert_eq!(listed.iter().filter(|s| s.database == "shop").count(), 3);
+        for snapshot in listed.iter().filter(|s| s.database == "shop") {
+            delete_snapshot(root, "rstn-postgres", &snapshot.id).unwrap();
+        }
+
+        delete_snapshot(root, "rstn-postgres", &saved.id).unwrap();
+        assert_eq!(list_snapshots(root, "rstn-postgres").len(), 1);
+        assert!(delete_snapshot(root, "rstn-postgres", "../rstn-redis").is_err());
+        assert!(list_snapshots(root, "rstn-mysql").is_empty());
+    }
+}
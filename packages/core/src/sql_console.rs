@@ -0,0 +1,738 @@
+//! SQL console and schema browser for the built-in database services.
+//!
+//! Queries run through the database's own CLI inside its container
+//! (`psql`, `mysql`), so no client library or host port is needed. Output
+//! is requested in a machine-readable form and converted to typed rows:
+//!
+//! - Postgres: `psql --csv`, with column types from `\gdesc`
+//! - MySQL: `mysql --batch --column-type-info` (tab-separated rows after
+//!   one metadata block per column)
+//!
+//! Row limits are enforced server-side (a `LIMIT` wrapper for Postgres read
+//! queries, `sql_select_limit` for MySQL) and again while reading the output,
+//! which stops after `limit + 1` rows; timeouts via `statement_timeout` /
+//! `max_execution_time`. Queries carry a tag comment so that one still
+//! running when the client gives up can be cancelled from another session.
+
+use crate::app_state::{SqlColumn, SqlQueryResult, SqlTableColumn, SqlTableInfo};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Rows returned when no limit is given
+pub const DEFAULT_ROW_LIMIT: usize = 500;
+
+/// Upper bound for a requested row limit
+pub const MAX_ROW_LIMIT: usize = 10_000;
+
+/// Query timeout when none is given
+pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time the client waits beyond the server-side timeout before giving up
+pub const CLIENT_TIMEOUT_GRACE: Duration = Duration::from_secs(5);
+
+/// How psql prints NULL (distinguishes it from an empty string in CSV)
+const PSQL_NULL: &str = "\\N";
+
+/// Postgres schemas that belong to the server itself
+const PG_SYSTEM_SCHEMAS: &str =
+    "table_schema NOT IN ('pg_catalog', 'information_schema') AND table_schema NOT LIKE 'pg\\_%'";
+
+/// MySQL databases that belong to the server itself
+const MYSQL_SYSTEM_DATABASES: &str = "('mysql', 'information_schema', 'performance_schema', 'sys')";
+
+/// SQL flavour of a database service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+}
+
+impl SqlDialect {
+    /// Dialect of a built-in service (None for services without SQL)
+    pub fn for_service(service_id: &str) -> Option<Self> {
+        match service_id {
+            "rstn-postgres" => Some(Self::Postgres),
+            "rstn-mysql" => Some(Self::MySql),
+            _ => None,
+        }
+    }
+}
+
+/// Clamp a requested row limit to `1..=MAX_ROW_LIMIT`
+pub fn row_limit(limit: Option<usize>) -> usize {
+    limit.unwrap_or(DEFAULT_ROW_LIMIT).clamp(1, MAX_ROW_LIMIT)
+}
+
+/// Strip surrounding whitespace and trailing semicolons
+fn trim_statement(sql: &str) -> &str {
+    sql.trim().trim_end_matches(|c: char| c == ';' || c.is_whitespace())
+}
+
+/// Whether `sql` is a single statement that only reads (so it can be
+/// wrapped in a subquery)
+fn is_single_read_query(sql: &str) -> bool {
+    if sql.contains(';') {
+        return false;
+    }
+    let lower = sql.to_ascii_lowercase();
+    let mut words = lower.split(|c: char| !c.is_ascii_alphanumeric() && c != '_');
+    match words.next() {
+        Some("select") | Some("values") | Some("table") => true,
+        // Data-modifying CTEs must stay at the top level
+        Some("with") => !words.any(|w| matches!(w, "insert" | "update" | "delete" | "merge")),
+        _ => false,
+    }
+}
+
+/// The statement actually sent for a query: Postgres read queries are
+/// wrapped to fetch at most `limit + 1` rows (the extra row tells that the
+/// result was truncated).
+pub fn limit_query(dialect: SqlDialect, sql: &str, limit: usize) -> String {
+    let sql = trim_statement(sql);
+    match dialect {
+        SqlDialect::Postgres if is_single_read_query(sql) => {
+            format!("SELECT * FROM (\n{}\n) AS rstn_query LIMIT {}", sql, limit + 1)
+        }
+        _ => sql.to_string(),
+    }
+}
+
+/// Tag marking one query, e.g. "rstn-query-3f2a..."
+pub fn query_tag() -> String {
+    format!("rstn-query-{}", uuid::Uuid::new_v4().simple())
+}
+
+/// `sql` with `tag` in a leading comment, which the server keeps in its
+/// list of running queries
+pub fn tag_query(tag: &str, sql: &str) -> String {
+    format!("/* {} */ {}", tag, sql)
+}
+
+/// Command cancelling, from another session, the query tagged `tag`
+/// (`pg_cancel_backend` / `KILL QUERY`)
+pub fn cancel_command(dialect: SqlDialect, tag: &str) -> Vec<String> {
+    let cmd: Vec<&str> = match dialect {
+        SqlDialect::Postgres => vec![
+            "sh",
+            "-c",
+            r#"psql -X -q -U postgres -d postgres -c "SELECT pg_cancel_backend(pid) FROM pg_stat_activity WHERE query LIKE '%$1%' AND pid <> pg_backend_pid()""#,
+            "sh",
+            tag,
+        ],
+        SqlDialect::MySql => vec![
+            "sh",
+            "-c",
+            r#"for id in $(mysql -u root -pmysql -N -B -e "SELECT ID FROM information_schema.PROCESSLIST WHERE INFO LIKE '%$1%' AND ID <> CONNECTION_ID()"); do mysql -u root -pmysql -e "KILL QUERY $id"; done"#,
+            "sh",
+            tag,
+        ],
+    };
+    cmd.into_iter().map(str::to_string).collect()
+}
+
+/// Command running `sql` in the service's container
+pub fn query_command(
+    dialect: SqlDialect,
+    database: Option<&str>,
+    sql: &str,
+    limit: usize,
+    timeout: Duration,
+) -> Vec<String> {
+    let timeout_ms = timeout.as_millis();
+    let mut cmd: Vec<String> = match dialect {
+        SqlDialect::Postgres => vec![
+            "psql",
+            "-X",
+            "-q",
+            "--csv",
+            "-v",
+            "ON_ERROR_STOP=1",
+            "-P",
+            "null=\\N",
+            "-U",
+            "postgres",
+            "-d",
+            database.unwrap_or("postgres"),
+            "-c",
+        ],
+        // --comments keeps the query's tag
+        SqlDialect::MySql => vec!["mysql", "-u", "root", "-pmysql", "--batch", "--column-type-info", "--comments"],
+    }
+    .into_iter()
+    .map(str::to_string)
+    .collect();
+
+    match dialect {
+        SqlDialect::Postgres => {
+            cmd.push(format!("SET statement_timeout = {}", timeout_ms));
+            cmd.push("-c".to_string());
+            cmd.push(sql.to_string());
+        }
+        SqlDialect::MySql => {
+            if let Some(database) = database {
+                cmd.push("-D".to_string());
+                cmd.push(database.to_string());
+            }
+            cmd.push("-e".to_string());
+            cmd.push(format!(
+                "SET SESSION max_execution_time = {}; SET SESSION sql_select_limit = {};\n{}",
+                timeout_ms,
+                limit + 1,
+                sql
+            ));
+        }
+    }
+    cmd
+}
+
+/// Command describing the result columns of a Postgres query without
+/// running it (`\gdesc`). None for multi-statement input.
+pub fn describe_command(database: Option<&str>, sql: &str) -> Option<Vec<String>> {
+    let sql = trim_statement(sql);
+    if sql.is_empty() || sql.contains(';') {
+        return None;
+    }
+    // The query is passed as an argument so it needs no shell quoting
+    let script = r#"printf '%s\n\\gdesc\n' "$1" | psql -X -q --csv -v ON_ERROR_STOP=1 -U postgres -d "$2""#;
+    Some(
+        ["sh", "-c", script, "sh", sql, database.unwrap_or("postgres")]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Where [`RowCounter`] is in `mysql --batch --column-type-info` output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MySqlPhase {
+    /// Before the first result set
+    Start,
+    /// In a column's metadata block
+    Metadata,
+    /// After a metadata block: another one or the header follows
+    BlockEnd,
+    Rows,
+}
+
+/// Counts result rows in query output as it streams in, so reading can
+/// stop once there are more than the limit. Follows the same rules as
+/// [`parse_psql_output`] and [`parse_mysql_output`].
+#[derive(Debug)]
+pub struct RowCounter {
+    dialect: SqlDialect,
+    /// Postgres: inside a quoted CSV field
+    in_quotes: bool,
+    /// Postgres: records seen, header included
+    records: usize,
+    /// MySQL: the unfinished last line and the one before it
+    line: String,
+    previous: String,
+    phase: MySqlPhase,
+    /// MySQL: rows of the current result set
+    rows: usize,
+}
+
+impl RowCounter {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self {
+            dialect,
+            in_quotes: false,
+            records: 0,
+            line: String::new(),
+            previous: String::new(),
+            phase: MySqlPhase::Start,
+            rows: 0,
+        }
+    }
+
+    /// Fold in the next chunk of stdout and return the complete rows so far
+    pub fn feed(&mut self, chunk: &str) -> usize {
+        match self.dialect {
+            SqlDialect::Postgres => {
+                for c in chunk.chars() {
+                    match c {
+                        '"' => self.in_quotes = !self.in_quotes,
+                        '\n' if !self.in_quotes => self.records += 1,
+                        _ => {}
+                    }
+                }
+                self.records.saturating_sub(1)
+            }
+            SqlDialect::MySql => {
+                let mut rest = chunk;
+                while let Some((head, tail)) = rest.split_once('\n') {
+                    self.line.push_str(head);
+                    let line = std::mem::take(&mut self.line);
+                    self.mysql_line(line.trim_end_matches('\r'));
+                    self.previous = line;
+                    rest = tail;
+                }
+                self.line.push_str(rest);
+                self.rows
+            }
+        }
+    }
+
+    fn mysql_line(&mut self, line: &str) {
+        if line.starts_with("Catalog:") && self.previous.starts_with("Field ") {
+            // A new result set: the previous line wasn't a row
+            self.phase = MySqlPhase::Metadata;
+            self.rows = 0;
+            return;
+        }
+        match self.phase {
+            MySqlPhase::Metadata if line.is_empty() => self.phase = MySqlPhase::BlockEnd,
+            MySqlPhase::BlockEnd if line.starts_with("Field ") => self.phase = MySqlPhase::Metadata,
+            MySqlPhase::BlockEnd => self.phase = MySqlPhase::Rows,
+            MySqlPhase::Rows => self.rows += 1,
+            MySqlPhase::Start | MySqlPhase::Metadata => {}
+        }
+    }
+}
+
+/// Parse CSV as printed by `psql --csv`. Unquoted `\N` fields are NULL.
+pub fn parse_csv(text: &str) -> Vec<Vec<Option<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    let finish_field = |row: &mut Vec<Option<String>>, field: &mut String, quoted: &mut bool| {
+        let value = std::mem::take(field);
+        row.push(if !*quoted && value == PSQL_NULL { None } else { Some(value) });
+        *quoted = false;
+    };
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' => finish_field(&mut row, &mut field, &mut quoted),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                finish_field(&mut row, &mut field, &mut quoted);
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || quoted || !row.is_empty() {
+        finish_field(&mut row, &mut field, &mut quoted);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Split `psql --csv` query output into columns and raw rows, taking column
+/// types from `\gdesc` output (when available and matching)
+pub fn parse_psql_output(
+    output: &str,
+    describe: Option<&str>,
+) -> (Vec<SqlColumn>, Vec<Vec<Option<String>>>) {
+    let mut rows = parse_csv(output).into_iter();
+    let Some(header) = rows.next() else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let types: Vec<Option<String>> = describe
+        .map(|d| {
+            parse_csv(d)
+                .into_iter()
+                .skip(1)
+                .map(|row| row.into_iter().nth(1).flatten())
+                .collect()
+        })
+        .filter(|types: &Vec<_>| types.len() == header.len())
+        .unwrap_or_else(|| vec![None; header.len()]);
+
+    let columns = header
+        .into_iter()
+        .zip(types)
+        .map(|(name, data_type)| SqlColumn {
+            name: name.unwrap_or_default(),
+            data_type,
+        })
+        .collect();
+    (columns, rows.collect())
+}
+
+/// Undo the escaping `mysql --batch` applies to values
+fn unescape_mysql(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('0') => out.push('\0'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Parse `mysql --batch --column-type-info` output into columns and raw
+/// rows. With several result sets, the last one wins (like psql).
+pub fn parse_mysql_output(output: &str) -> (Vec<SqlColumn>, Vec<Vec<Option<String>>>) {
+    let lines: Vec<&str> = output.lines().collect();
+    let is_field_start = |i: usize| {
+        lines[i].starts_with("Field ")
+            && lines.get(i + 1).is_some_and(|next| next.starts_with("Catalog:"))
+    };
+
+    let mut columns: Vec<SqlColumn> = Vec::new();
+    let mut rows: Vec<Vec<Option<String>>> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if !is_field_start(i) {
+            i += 1;
+            continue;
+        }
+
+        // Metadata blocks, one per column, each ending with a blank line
+        columns.clear();
+        rows.clear();
+        while i < lines.len() && is_field_start(i) {
+            let name = lines[i]
+                .split_once('`')
+                .and_then(|(_, rest)| rest.rsplit_once('`'))
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_default();
+            let mut data_type = None;
+            while i < lines.len() && !lines[i].is_empty() {
+                if let Some(value) = lines[i].strip_prefix("Type:") {
+                    data_type = Some(value.trim().to_string());
+                }
+                i += 1;
+            }
+            columns.push(SqlColumn { name, data_type });
+            i += 1;
+        }
+
+        // Header line, then one line per row until the next result set
+        i += 1;
+        while i < lines.len() && !is_field_start(i) {
+            rows.push(
+                lines[i]
+                    .split('\t')
+                    .map(|value| (value != "NULL").then(|| unescape_mysql(value)))
+                    .collect(),
+            );
+            i += 1;
+        }
+    }
+    (columns, rows)
+}
+
+/// Convert a raw value to JSON according to its column type. Exact
+/// numerics (NUMERIC/DECIMAL) stay strings so no precision is lost.
+pub fn typed_value(data_type: Option<&str>, raw: Option<&str>) -> Value {
+    let Some(raw) = raw else {
+        return Value::Null;
+    };
+    let data_type = data_type.unwrap_or_default().to_ascii_lowercase();
+    let typed = match data_type.as_str() {
+        "smallint" | "integer" | "bigint" | "tiny" | "short" | "long" | "longlong" | "int24"
+        | "year" => raw.parse::<i64>().ok().map(Value::from),
+        "real" | "double precision" | "float" | "double" => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        "boolean" => match raw {
+            "t" | "true" => Some(Value::Bool(true)),
+            "f" | "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        "json" | "jsonb" => serde_json::from_str(raw).ok(),
+        _ => None,
+    };
+    typed.unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Typed result of at most `limit` rows
+pub fn build_result(
+    columns: Vec<SqlColumn>,
+    raw_rows: Vec<Vec<Option<String>>>,
+    limit: usize,
+    elapsed: Duration,
+) -> SqlQueryResult {
+    let truncated = raw_rows.len() > limit;
+    let rows = raw_rows
+        .into_iter()
+        .take(limit)
+        .map(|row| {
+            columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    typed_value(column.data_type.as_deref(), row.get(i).and_then(|v| v.as_deref()))
+                })
+                .collect()
+        })
+        .collect();
+    SqlQueryResult {
+        columns,
+        rows,
+        truncated,
+        elapsed_ms: elapsed.as_millis() as u64,
+    }
+}
+
+/// Error text of a failed query, without the CLI's password warning
+pub fn error_message(stderr: &str) -> String {
+    stderr
+        .lines()
+        .filter(|line| !line.contains("Using a password on the command line"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+// ============================================================================
+// Schema browser
+// ============================================================================
+
+/// Query listing the databases of the server
+pub fn databases_query(dialect: SqlDialect) -> String {
+    match dialect {
+        SqlDialect::Postgres => {
+            "SELECT datname FROM pg_database WHERE NOT datistemplate ORDER BY datname".to_string()
+        }
+        SqlDialect::MySql => format!(
+            "SELECT schema_name FROM information_schema.schemata \
+             WHERE schema_name NOT IN {} ORDER BY schema_name",
+            MYSQL_SYSTEM_DATABASES
+        ),
+    }
+}
+
+/// Query listing the schemas of a Postgres database (MySQL has none below
+/// the database)
+pub fn schemas_query() -> String {
+    format!(
+        "SELECT schema_name FROM information_schema.schemata WHERE {} ORDER BY schema_name",
+        PG_SYSTEM_SCHEMAS.replace("table_schema", "schema_name")
+    )
+}
+
+/// Query listing table columns: schema, table, column, type, nullable, default
+pub fn columns_query(dialect: SqlDialect, database: Option<&str>) -> String {
+    match dialect {
+        SqlDialect::Postgres => format!(
+            "SELECT table_schema, table_name, column_name, data_type, is_nullable, column_default \
+             FROM information_schema.columns WHERE {} \
+             ORDER BY table_schema, table_name, ordinal_position",
+            PG_SYSTEM_SCHEMAS
+        ),
+        SqlDialect::MySql => {
+            let filter = match database {
+                Some(_) => "table_schema = DATABASE()".to_string(),
+                None => format!("table_schema NOT IN {}", MYSQL_SYSTEM_DATABASES),
+            };
+            format!(
+                "SELECT table_schema, table_name, column_name, column_type, is_nullable, column_default \
+                 FROM information_schema.columns WHERE {} \
+                 ORDER BY table_schema, table_name, ordinal_position",
+                filter
+            )
+        }
+    }
+}
+
+/// First column of each row as text
+pub fn first_column(result: &SqlQueryResult) -> Vec<String> {
+    result
+        .rows
+        .iter()
+        .filter_map(|row| row.first().and_then(Value::as_str).map(str::to_string))
+        .collect()
+}
+
+/// Group the rows of `columns_query` into tables
+pub fn group_tables(result: &SqlQueryResult) -> Vec<SqlTableInfo> {
+    let text = |row: &[Value], i: usize| row.get(i).and_then(Value::as_str).map(str::to_string);
+
+    let mut tables: Vec<SqlTableInfo> = Vec::new();
+    for row in &result.rows {
+        let (Some(schema), Some(table), Some(name)) = (text(row, 0), text(row, 1), text(row, 2)) else {
+            continue;
+        };
+        let column = SqlTableColumn {
+            name,
+            data_type: text(row, 3).unwrap_or_default(),
+            nullable: text(row, 4).as_deref() == Some("YES"),
+            default: text(row, 5),
+        };
+        match tables.last_mut() {
+            Some(last) if last.schema == schema && last.name == table => last.columns.push(column),
+            _ => tables.push(SqlTableInfo {
+                schema,
+                name: table,
+                columns: vec![column],
+            }),
+        }
+    }
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_query_wraps_postgres_reads_only() {
+        assert_eq!(
+            limit_query(SqlDialect::Postgres, "select * from users;\n", 10),
+            "SELECT * FROM (\nselect * from users\n) AS rstn_query LIMIT 11"
+        );
+        let cte = "WITH gone AS (DELETE FROM users RETURNING id) SELECT * FROM gone";
+        assert_eq!(limit_query(SqlDialect::Postgres, cte, 10), cte);
+        assert_eq!(limit_query(SqlDialect::Postgres, "update users set x = 1", 10), "update users set x = 1");
+        assert_eq!(limit_query(SqlDialect::Postgres, "select 1; select 2", 10), "select 1; select 2");
+        // MySQL limits through sql_select_limit instead
+        assert_eq!(limit_query(SqlDialect::MySql, "select * from users", 10), "select * from users");
+    }
+
+    #[test]
+    fn test_parse_psql_output_with_types() {
+        let output = "id,name,active,meta,note\n1,\"Doe, Jane\",t,\"{\"\"a\"\": 1}\",\\N\n2,\"multi\nline\",f,\\N,\"\"\n";
+        let describe = "Column,Type\nid,integer\nname,text\nactive,boolean\nmeta,jsonb\nnote,text\n";
+        let (columns, rows) = parse_psql_output(output, Some(describe));
+        assert_eq!(columns[0], SqlColumn { name: "id".to_string(), data_type: Some("integer".to_string()) });
+
+        let result = build_result(columns, rows, 10, Duration::from_millis(3));
+        assert!(!result.truncated);
+        assert_eq!(
+            result.rows[0],
+            vec![
+                Value::from(1),
+                Value::from("Doe, Jane"),
+                Value::Bool(true),
+                serde_json::json!({"a": 1}),
+                Value::Null
+            ]
+        );
+        assert_eq!(result.rows[1][1], Value::from("multi\nline"));
+        assert_eq!(result.rows[1][3], Value::Null);
+        // Quoted empty string is not NULL
+        assert_eq!(result.rows[1][4], Value::from(""));
+    }
+
+    #[test]
+    fn test_parse_psql_output_without_rows_or_types() {
+        assert_eq!(parse_psql_output("", None), (Vec::new(), Vec::new()));
+
+        let (columns, rows) = parse_psql_output("n\n1\n2\n3\n", Some("garbage"));
+        assert_eq!(columns[0].data_type, None);
+        let result = build_result(columns, rows, 2, Duration::ZERO);
+        assert!(result.truncated);
+        assert_eq!(result.rows, vec![vec![Value::from("1")], vec![Value::from("2")]]);
+    }
+
+    #[test]
+    fn test_parse_mysql_output() {
+        let field = |n: u32, name: &str, ty: &str| {
+            format!(
+                "Field {:>3}:  `{}`\nCatalog:    `def`\nDatabase:   `app`\nTable:      `users`\n\
+                 Org_table:  `users`\nType:       {}\nCollation:  binary (63)\nLength:     11\n\
+                 Max_length: 1\nDecimals:   0\nFlags:      NOT_NULL \n\n",
+                n, name, ty
+            )
+        };
+        let output = format!(
+            "{}{}{}id\tname\tscore\n1\tline\\nbreak\t1.5\n2\tNULL\tNULL\n",
+            field(1, "id", "LONG"),
+            field(2, "name", "VAR_STRING"),
+            field(3, "score", "DOUBLE")
+        );
+        let (columns, rows) = parse_mysql_output(&output);
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "name", "score"]);
+
+        let result = build_result(columns, rows, 10, Duration::ZERO);
+        assert_eq!(result.rows[0], vec![Value::from(1), Value::from("line\nbreak"), Value::from(1.5)]);
+        assert_eq!(result.rows[1], vec![Value::from(2), Value::Null, Value::Null]);
+
+        // Statements without a result set print nothing
+        assert_eq!(parse_mysql_output(""), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn test_row_counter_follows_the_parsers() {
+        let psql = "id,note\n1,\"multi\nline\"\n2,\"say \"\"hi\"\"\"\n3,x";
+        let mut counter = RowCounter::new(SqlDialect::Postgres);
+        let counts: Vec<usize> = psql
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| counter.feed(std::str::from_utf8(chunk).unwrap()))
+            .collect();
+        assert_eq!(counts.last(), Some(&2));
+        assert_eq!(parse_psql_output(psql, None).1.len(), 3);
+
+        let mysql = "Field   1:  `id`\nCatalog:    `def`\nType:       LONG\n\nid\n1\n2\nField   1:  `n`\nCatalog:    `def`\nType:       LONG\n\nn\n7\n";
+        let mut counter = RowCounter::new(SqlDialect::MySql);
+        assert_eq!(counter.feed(&mysql[..40]), 0);
+        assert_eq!(counter.feed(&mysql[40..70]), 2);
+        assert_eq!(counter.feed(&mysql[70..]), 1);
+        assert_eq!(parse_mysql_output(mysql).1.len(), 1);
+    }
+
+    #[test]
+    fn test_group_tables() {
+        let row = |schema: &str, table: &str, column: &str, nullable: &str| {
+            vec![
+                Value::from(schema),
+                Value::from(table),
+                Value::from(column),
+                Value::from("integer"),
+                Value::from(nullable),
+                Value::Null,
+            ]
+        };
+        let result = SqlQueryResult {
+            columns: Vec::new(),
+            rows: vec![
+                row("public", "posts", "id", "NO"),
+                row("public", "posts", "user_id", "YES"),
+                row("public", "users", "id", "NO"),
+            ],
+            truncated: false,
+            elapsed_ms: 0,
+        };
+        let tables = group_tables(&result);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].name, "posts");
+        assert_eq!(tables[0].columns.len(), 2);
+        assert!(tables[0].columns[1].nullable);
+        assert!(!tables[1].columns[0].nullable);
+    }
+
+    #[test]
+    fn test_error_message_drops_password_warning() {
+        let stderr = "mysql: [Warning] Using a password on the command line interface can be insecure.\n\
+                      ERROR 1146 (42S02) at line 1: Table 'app.nope' doesn't exist\n";
+        assert_eq!(error_message(stderr), "ERROR 1146 (42S02) at line 1: Table 'app.nope' doesn't exist");
+    }
+}
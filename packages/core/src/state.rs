@@ -45,6 +45,10 @@ pub struct DockerService {
     pub project_group: Option<String>,
     /// Whether this container is managed by rstn (rstn-* prefix)
     pub is_rstn_managed: bool,
+    /// Compose service name (for containers of a compose project)
+    pub compose_service: Option<String>,
+    /// Docker HEALTHCHECK status ("starting", "healthy", "unhealthy")
+    pub health: Option<String>,
 }
 
 /// Port conflict information for napi export
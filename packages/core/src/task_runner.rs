@@ -0,0 +1,327 @@
+//! Async task process execution with streamed output and cancellation.
+//!
+//! Tasks run in their own process group so cancelling a recipe also stops
+//! whatever it spawned (dev servers, test runners, watchers).
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// How long a cancelled task gets to exit after SIGTERM before SIGKILL.
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// How long background processes left behind by a finished task may keep
+/// its output pipes open before the group is killed.
+const ORPHAN_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Which pipe a line of output came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStream {
+    Stdout,
+    Stderr,
+}
+
+/// How a task process ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskExit {
+    /// Exit code (None if killed by a signal)
+    pub code: Option<i32>,
+    /// Whether the task was stopped through `TaskRunner::cancel`
+    pub cancelled: bool,
+}
+
+impl TaskExit {
+    pub fn success(&self) -> bool {
+        !self.cancelled && self.code == Some(0)
+    }
+}
+
+/// Events streamed from a running task. `Exited` is always the last event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskEvent {
+    Output { line: String, stream: TaskStream },
+    Exited(TaskExit),
+}
+
+/// Runs task processes keyed by caller-chosen IDs.
+#[derive(Default)]
+pub struct TaskRunner {
+    running: Arc<Mutex<HashMap<String, CancellationToken>>>,
+}
+
+impl TaskRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a task is running under this key.
+    pub fn is_running(&self, key: &str) -> bool {
+        self.running
+            .lock()
+            .map(|running| running.contains_key(key))
+            .unwrap_or(false)
+    }
+
+    /// Spawn `program args...` in `cwd` and stream its output.
+    ///
+    /// Must be called from within a tokio runtime. Fails if a task is
+    /// already running under `key` or the process can't be started.
+    pub fn spawn(
+        &self,
+        key: &str,
+        program: &str,
+        args: &[String],
+        cwd: &str,
+    ) -> Result<mpsc::UnboundedReceiver<TaskEvent>, String> {
+        if !std::path::Path::new(cwd).exists() {
+            return Err(format!("Directory does not exist: {}", cwd));
+        }
+
+        let mut running = self
+            .running
+            .lock()
+            .map_err(|_| "Task registry poisoned".to_string())?;
+        if running.contains_key(key) {
+            return Err(format!("Task already running: {}", key));
+        }
+
+        let mut cmd = Command::new(program);
+        cmd.args(args)
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+        let pid = child.id();
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let stdout = child.stdout.take().map(|out| {
+            tokio::spawn(forward_lines(out, TaskStream::Stdout, tx.clone()))
+        });
+        let stderr = child.stderr.take().map(|err| {
+            tokio::spawn(forward_lines(err, TaskStream::Stderr, tx.clone()))
+        });
+
+        let cancel = CancellationToken::new();
+        running.insert(key.to_string(), cancel.clone());
+        drop(running);
+
+        let registry = Arc::clone(&self.running);
+        let key = key.to_string();
+        tokio::spawn(async move {
+            let exit = wait_or_cancel(&mut child, pid, &cancel).await;
+
+            // Deliver all output before the exit event
+            let mut readers: Vec<_> = [stdout, stderr].into_iter().flatten().collect();
+            let drain = async {
+                for reader in readers.iter_mut() {
+                    let _ = reader.await;
+                }
+            };
+            if tokio::time::timeout(ORPHAN_GRACE_PERIOD, drain).await.is_err() {
+                signal_group(pid, Signal::Kill);
+                for reader in readers {
+                    let _ = reader.await;
+                }
+            }
+            if let Ok(mut running) = registry.lock() {
+                running.remove(&key);
+            }
+            let _ = tx.send(TaskEvent::Exited(exit));
+        });
+
+        Ok(rx)
+    }
+
+    /// Cancel a running task (kills its whole process group).
+    ///
+    /// Returns false if nothing is running under this key.
+    pub fn cancel(&self, key: &str) -> bool {
+        match self.running.lock() {
+            Ok(running) => match running.get(key) {
+                Some(token) => {
+                    token.cancel();
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+}
+
+/// Forward lines from a pipe (lossy UTF-8, line endings stripped).
+async fn forward_lines<R: AsyncRead + Unpin>(
+    pipe: R,
+    stream: TaskStream,
+    tx: mpsc::UnboundedSender<TaskEvent>,
+) {
+    let mut reader = BufReader::new(pipe);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buf)
+                    .trim_end_matches(['\n', '\r'])
+                    .to_string();
+                if tx.send(TaskEvent::Output { line, stream }).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn wait_or_cancel(
+    child: &mut Child,
+    pid: Option<u32>,
+    cancel: &CancellationToken,
+) -> TaskExit {
+    tokio::select! {
+        status = child.wait() => TaskExit {
+            code: status.ok().and_then(|s| s.code()),
+            cancelled: false,
+        },
+        _ = cancel.cancelled() => {
+            signal_group(pid, Signal::Term);
+            #[cfg(not(unix))]
+            let _ = child.start_kill();
+            let status = match tokio::time::timeout(CANCEL_GRACE_PERIOD, child.wait()).await {
+                Ok(status) => status.ok(),
+                Err(_) => {
+                    signal_group(pid, Signal::Kill);
+                    let _ = child.start_kill();
+                    child.wait().await.ok()
+                }
+            };
+            TaskExit {
+                code: status.and_then(|s| s.code()),
+                cancelled: true,
+            }
+        }
+    }
+}
+
+enum Signal {
+    Term,
+    Kill,
+}
+
+/// Signal the task's whole process group (the task leads its own group).
+#[cfg(unix)]
+fn signal_group(pid: Option<u32>, signal: Signal) {
+    let Some(pid) = pid else {
+        return;
+    };
+    let signal = match signal {
+        Signal::Term => libc::SIGTERM,
+        Signal::Kill => libc::SIGKILL,
+    };
+    // SAFETY: signalling a process group we created; no memory is touched
+    unsafe {
+        libc::killpg(pid as libc::pid_t, signal);
+    }
+}
+
+/// Without process groups only the direct child can be stopped (via `start_kill`).
+#[cfg(not(unix))]
+fn signal_group(_pid: Option<u32>, _signal: Signal) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect(mut rx: mpsc::UnboundedReceiver<TaskEvent>) -> (Vec<String>, TaskExit) {
+        let mut lines = Vec::new();
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Output { line, .. } => lines.push(line),
+                TaskEvent::Exited(exit) => return (lines, exit),
+            }
+        }
+        panic!("task ended without an exit event");
+    }
+
+    fn sh(script: &str) -> Vec<String> {
+        vec!["-c".to_string(), script.to_string()]
+    }
+
+    #[tokio::test]
+    async fn test_streams_output_and_exit_code() {
+        let runner = TaskRunner::new();
+        let rx = runner
+            .spawn("t", "sh", &sh("echo one; echo two >&2; exit 3"), "/tmp")
+            .unwrap();
+        assert!(runner.is_running("t"));
+
+        let (mut lines, exit) = collect(rx).await;
+        lines.sort();
+        assert_eq!(lines, vec!["one", "two"]);
+        assert_eq!(exit, TaskExit { code: Some(3), cancelled: false });
+        assert!(!exit.success());
+        assert!(!runner.is_running("t"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_duplicate_key() {
+        let runner = TaskRunner::new();
+        let rx = runner.spawn("t", "sh", &sh("sleep 5"), "/tmp").unwrap();
+        assert!(runner.spawn("t", "sh", &sh("true"), "/tmp").is_err());
+        assert!(runner.cancel("t"));
+        let (_, exit) = collect(rx).await;
+        assert!(exit.cancelled);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancel_kills_process_group() {
+        let runner = TaskRunner::new();
+        // The backgrounded sleep keeps stdout open; cancelling must kill it too
+        let rx = runner
+            .spawn("t", "sh", &sh("sleep 30 & echo started; wait"), "/tmp")
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(runner.cancel("t"));
+
+        let (lines, exit) = tokio::time::timeout(Duration::from_secs(10), collect(rx))
+            .await
+            .expect("cancelled task did not exit");
+        assert_eq!(lines, vec!["started"]);
+        assert!(exit.cancelled);
+        assert!(!runner.cancel("t"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_background_children_do_not_block_exit() {
+        let runner = TaskRunner::new();
+        let rx = runner
+            .spawn("t", "sh", &sh("sleep 30 & echo done"), "/tmp")
+            .unwrap();
+
+        let (lines, exit) = tokio::time::timeout(Duration::from_secs(10), collect(rx))
+            .await
+            .expect("finished task did not report exit");
+        assert_eq!(lines, vec!["done"]);
+        assert!(exit.success());
+    }
+
+    #[tokio::test]
+    async fn test_missing_directory() {
+        let runner = TaskRunner::new();
+        assert!(runner.spawn("t", "sh", &sh("true"), "/nonexistent/dir").is_err());
+    }
+}
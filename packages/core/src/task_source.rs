@@ -0,0 +1,820 @@
+//! Task sources for the Tasks tab and the MCP `run_just_task` tool.
+//!
+//! Justfile recipes, `package.json` scripts, Makefile targets and cargo
+//! aliases are discovered into one task list. Justfile recipes keep their
+//! plain names; tasks from other sources are namespaced (`npm:build`,
+//! `make:test`, `cargo:xtask`) so names never collide and every task maps
+//! back to the runner that executes it.
+
+use crate::justfile::{self, JustCommand};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Makefile names GNU make looks for, in lookup order
+const MAKEFILE_NAMES: &[&str] = &["GNUmakefile", "makefile", "Makefile"];
+
+/// Cargo config files that may define `[alias]`, in lookup order
+const CARGO_CONFIG_NAMES: &[&str] = &[".cargo/config.toml", ".cargo/config"];
+
+/// Where a task is defined (and therefore how it runs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskSourceKind {
+    /// Justfile recipe, run with `just`
+    #[default]
+    Just,
+    /// `package.json` script, run with the project's package manager
+    Npm,
+    /// Makefile target, run with `make`
+    Make,
+    /// Cargo alias from `.cargo/config.toml`, run with `cargo`
+    Cargo,
+}
+
+impl TaskSourceKind {
+    /// Name prefix for tasks from this source (None for justfile recipes).
+    pub fn prefix(&self) -> Option<&'static str> {
+        match self {
+            TaskSourceKind::Just => None,
+            TaskSourceKind::Npm => Some("npm"),
+            TaskSourceKind::Make => Some("make"),
+            TaskSourceKind::Cargo => Some("cargo"),
+        }
+    }
+
+    /// Task name as shown in the task list.
+    pub fn qualify(&self, name: &str) -> String {
+        match self.prefix() {
+            Some(prefix) => format!("{}:{}", prefix, name),
+            None => name.to_string(),
+        }
+    }
+}
+
+/// A task discovered in a worktree.
+#[derive(Debug, Clone)]
+pub struct Task {
+    pub source: TaskSourceKind,
+    /// Task definition; `name` is qualified (see [`TaskSourceKind::qualify`])
+    pub command: JustCommand,
+}
+
+/// A place tasks can be defined.
+pub trait TaskSource: Send + Sync {
+    fn kind(&self) -> TaskSourceKind;
+
+    /// Tasks defined in `dir`, with unqualified names.
+    /// Returns an empty list when the source file doesn't exist.
+    fn discover(&self, dir: &Path) -> Result<Vec<JustCommand>, String>;
+
+    /// Program and argv that run `task` in `dir`.
+    ///
+    /// `args` are passed through to the task; `named` is only meaningful
+    /// for sources with named parameters (just recipes, make variables).
+    fn command(
+        &self,
+        dir: &Path,
+        task: &str,
+        args: &[String],
+        named: &HashMap<String, String>,
+    ) -> Result<(String, Vec<String>), String>;
+}
+
+/// All task sources, in the order their tasks are listed.
+pub fn sources() -> Vec<Box<dyn TaskSource>> {
+    vec![
+        Box::new(JustSource),
+        Box::new(NpmSource),
+        Box::new(MakeSource),
+        Box::new(CargoAliasSource),
+    ]
+}
+
+fn source_for(kind: TaskSourceKind) -> Box<dyn TaskSource> {
+    match kind {
+        TaskSourceKind::Just => Box::new(JustSource),
+        TaskSourceKind::Npm => Box::new(NpmSource),
+        TaskSourceKind::Make => Box::new(MakeSource),
+        TaskSourceKind::Cargo => Box::new(CargoAliasSource),
+    }
+}
+
+/// Discover tasks from every source in `dir`.
+///
+/// A source that fails to parse doesn't hide the others; its error is
+/// returned alongside the tasks that were found.
+pub fn discover_tasks(dir: &str) -> (Vec<Task>, Vec<String>) {
+    let dir = Path::new(dir);
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+
+    for source in sources() {
+        let kind = source.kind();
+        match source.discover(dir) {
+            Ok(commands) => {
+                tasks.extend(commands.into_iter().map(|mut command| {
+                    command.name = kind.qualify(&command.name);
+                    command.dependencies = command
+                        .dependencies
+                        .iter()
+                        .map(|dep| kind.qualify(dep))
+                        .collect();
+                    command.aliases =
+                        command.aliases.iter().map(|a| kind.qualify(a)).collect();
+                    Task {
+                        source: kind,
+                        command,
+                    }
+                }));
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (tasks, errors)
+}
+
+/// Split a qualified task name into its source and source-local name.
+///
+/// `npm:build` → (Npm, "build"); `db::migrate` stays a justfile module path.
+pub fn split_task_name(name: &str) -> (TaskSourceKind, &str) {
+    for kind in [TaskSourceKind::Npm, TaskSourceKind::Make, TaskSourceKind::Cargo] {
+        let Some(prefix) = kind.prefix() else {
+            continue;
+        };
+        if let Some(task) = name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            if !task.is_empty() && !task.starts_with(':') {
+                return (kind, task);
+            }
+        }
+    }
+    (TaskSourceKind::Just, name)
+}
+
+/// Resolve a qualified task name to the program and argv that run it.
+pub fn prepare_task(
+    dir: &str,
+    name: &str,
+    args: &[String],
+    named: &HashMap<String, String>,
+) -> Result<(String, Vec<String>), String> {
+    let (kind, task) = split_task_name(name);
+    source_for(kind).command(Path::new(dir), task, args, named)
+}
+
+fn reject_named_args(kind: TaskSourceKind, named: &HashMap<String, String>) -> Result<(), String> {
+    if named.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} tasks don't take named arguments",
+            kind.prefix().unwrap_or("just")
+        ))
+    }
+}
+
+/// Find a task by name in `commands`, or fail with a source-specific error.
+fn require_task(commands: &[JustCommand], task: &str, what: &str) -> Result<(), String> {
+    if commands.iter().any(|c| c.name == task) {
+        Ok(())
+    } else {
+        Err(format!("Unknown {} '{}'", what, task))
+    }
+}
+
+// ============================================================================
+// Justfile
+// ============================================================================
+
+/// Justfile recipes (see [`crate::justfile`]).
+pub struct JustSource;
+
+impl TaskSource for JustSource {
+    fn kind(&self) -> TaskSourceKind {
+        TaskSourceKind::Just
+    }
+
+    fn discover(&self, dir: &Path) -> Result<Vec<JustCommand>, String> {
+        match justfile::find_justfile(&dir.to_string_lossy()) {
+            Some(path) => justfile::parse_justfile(&path.to_string_lossy()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn command(
+        &self,
+        dir: &Path,
+        task: &str,
+        args: &[String],
+        named: &HashMap<String, String>,
+    ) -> Result<(String, Vec<String>), String> {
+        let argv = justfile::prepare_recipe_args(&dir.to_string_lossy(), task, args, named)?;
+        Ok(("just".to_string(), argv))
+    }
+}
+
+// ============================================================================
+// package.json scripts
+// ============================================================================
+
+/// `package.json` scripts, run with the package manager the project uses.
+pub struct NpmSource;
+
+impl NpmSource {
+    fn read_package_json(dir: &Path) -> Result<Option<Value>, String> {
+        let path = dir.join("package.json");
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+}
+
+impl TaskSource for NpmSource {
+    fn kind(&self) -> TaskSourceKind {
+        TaskSourceKind::Npm
+    }
+
+    fn discover(&self, dir: &Path) -> Result<Vec<JustCommand>, String> {
+        let Some(package) = Self::read_package_json(dir)? else {
+            return Ok(Vec::new());
+        };
+        Ok(parse_package_scripts(&package))
+    }
+
+    fn command(
+        &self,
+        dir: &Path,
+        task: &str,
+        args: &[String],
+        named: &HashMap<String, String>,
+    ) -> Result<(String, Vec<String>), String> {
+        reject_named_args(self.kind(), named)?;
+        let package = Self::read_package_json(dir)?
+            .ok_or_else(|| format!("No package.json found in {}", dir.display()))?;
+        require_task(&parse_package_scripts(&package), task, "npm script")?;
+
+        let manager = detect_package_manager(dir, &package);
+        let mut argv = vec!["run".to_string(), task.to_string()];
+        if !args.is_empty() {
+            // npm needs `--` to stop parsing its own flags; the others forward as-is
+            if manager == "npm" {
+                argv.push("--".to_string());
+            }
+            argv.extend(args.iter().cloned());
+        }
+        Ok((manager.to_string(), argv))
+    }
+}
+
+/// Scripts from a parsed `package.json`.
+///
+/// `pre<name>`/`post<name>` lifecycle hooks of another script are marked
+/// private and listed as that script's dependencies.
+pub fn parse_package_scripts(package: &Value) -> Vec<JustCommand> {
+    let Some(scripts) = package.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+
+    let is_hook_of = |name: &str, prefix: &str| {
+        name.strip_prefix(prefix)
+            .is_some_and(|target| !target.is_empty() && scripts.contains_key(target))
+    };
+
+    scripts
+        .iter()
+        .filter_map(|(name, body)| {
+            let recipe = body.as_str()?.to_string();
+            let dependencies = ["pre", "post"]
+                .iter()
+                .map(|hook| format!("{}{}", hook, name))
+                .filter(|hook| scripts.contains_key(hook))
+                .collect();
+            Some(JustCommand {
+                name: name.clone(),
+                recipe,
+                dependencies,
+                private: is_hook_of(name, "pre") || is_hook_of(name, "post"),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Package manager for a project: the `packageManager` field, then lockfiles.
+pub fn detect_package_manager(dir: &Path, package: &Value) -> &'static str {
+    const MANAGERS: &[(&str, &[&str])] = &[
+        ("pnpm", &["pnpm-lock.yaml"]),
+        ("yarn", &["yarn.lock"]),
+        ("bun", &["bun.lockb", "bun.lock"]),
+        ("npm", &["package-lock.json"]),
+    ];
+
+    if let Some(declared) = package.get("packageManager").and_then(|v| v.as_str()) {
+        let name = declared.split('@').next().unwrap_or_default();
+        if let Some((manager, _)) = MANAGERS.iter().find(|(m, _)| *m == name) {
+            return manager;
+        }
+    }
+    MANAGERS
+        .iter()
+        .find(|(_, lockfiles)| lockfiles.iter().any(|f| dir.join(f).is_file()))
+        .map(|(manager, _)| *manager)
+        .unwrap_or("npm")
+}
+
+// ============================================================================
+// Makefile
+// ============================================================================
+
+/// Explicit Makefile targets, run with `make`.
+pub struct MakeSource;
+
+impl MakeSource {
+    fn find_makefile(dir: &Path) -> Option<PathBuf> {
+        MAKEFILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    fn read_targets(dir: &Path) -> Result<Option<Vec<JustCommand>>, String> {
+        let Some(path) = Self::find_makefile(dir) else {
+            return Ok(None);
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Some(parse_makefile(&content)))
+    }
+}
+
+impl TaskSource for MakeSource {
+    fn kind(&self) -> TaskSourceKind {
+        TaskSourceKind::Make
+    }
+
+    fn discover(&self, dir: &Path) -> Result<Vec<JustCommand>, String> {
+        Ok(Self::read_targets(dir)?.unwrap_or_default())
+    }
+
+    /// Named arguments become `NAME=value` variable overrides.
+    fn command(
+        &self,
+        dir: &Path,
+        task: &str,
+        args: &[String],
+        named: &HashMap<String, String>,
+    ) -> Result<(String, Vec<String>), String> {
+        let targets = Self::read_targets(dir)?
+            .ok_or_else(|| format!("No Makefile found in {}", dir.display()))?;
+        require_task(&targets, task, "make target")?;
+
+        let mut variables: Vec<_> = named.iter().collect();
+        variables.sort();
+        let mut argv = vec![task.to_string()];
+        argv.extend(variables.into_iter().map(|(k, v)| format!("{}={}", k, v)));
+        argv.extend(args.iter().cloned());
+        Ok(("make".to_string(), argv))
+    }
+}
+
+/// Parse explicit targets out of a Makefile.
+///
+/// Special targets (`.PHONY`), pattern rules (`%.o`) and targets built from
+/// variables are skipped. A target's description is its trailing `## text`
+/// or the comment line directly above it.
+pub fn parse_makefile(content: &str) -> Vec<JustCommand> {
+    let mut targets: Vec<JustCommand> = Vec::new();
+    let mut current: Vec<usize> = Vec::new();
+    let mut last_comment: Option<String> = None;
+    let mut in_define = false;
+
+    // Join backslash continuations first
+    let mut lines: Vec<String> = Vec::new();
+    let mut pending = String::new();
+    for line in content.lines() {
+        if let Some(stripped) = line.strip_suffix('\\') {
+            pending.push_str(stripped);
+            pending.push(' ');
+        } else {
+            pending.push_str(line);
+            lines.push(std::mem::take(&mut pending));
+        }
+    }
+    if !pending.is_empty() {
+        lines.push(pending);
+    }
+
+    for line in &lines {
+        let trimmed = line.trim();
+
+        if in_define {
+            if trimmed == "endef" {
+                in_define = false;
+            }
+            continue;
+        }
+
+        if let Some(recipe_line) = line.strip_prefix('\t') {
+            for &index in &current {
+                let recipe = &mut targets[index].recipe;
+                if !recipe.is_empty() {
+                    recipe.push('\n');
+                }
+                recipe.push_str(recipe_line.trim());
+            }
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            last_comment = None;
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            last_comment = Some(comment.trim_start_matches('#').trim().to_string());
+            continue;
+        }
+
+        current.clear();
+        let comment = last_comment.take();
+
+        let first_word = trimmed.split_whitespace().next().unwrap_or_default();
+        if first_word == "define" {
+            in_define = true;
+            continue;
+        }
+        if matches!(
+            first_word,
+            "include" | "-include" | "sinclude" | "ifeq" | "ifneq" | "ifdef" | "ifndef"
+                | "else" | "endif" | "export" | "unexport" | "override" | "vpath"
+        ) {
+            continue;
+        }
+
+        // A rule has a ':' before any '=' that isn't part of ':=' / '::='
+        let Some(colon) = trimmed.find(':') else {
+            continue;
+        };
+        if trimmed[..colon].contains('=') || trimmed[colon..].trim_start_matches(':').starts_with('=')
+        {
+            continue;
+        }
+
+        let names = &trimmed[..colon];
+        let rest = trimmed[colon..].trim_start_matches(':');
+        let (rest, inline_doc) = match rest.split_once("##") {
+            Some((rest, doc)) => (rest, Some(doc.trim().to_string())),
+            None => (rest, None),
+        };
+        let (prereqs, inline_recipe) = match rest.split_once(';') {
+            Some((prereqs, recipe)) => (prereqs, recipe.trim()),
+            None => (rest, ""),
+        };
+        let description = inline_doc.filter(|d| !d.is_empty()).or(comment);
+        let dependencies: Vec<String> = prereqs
+            .split_whitespace()
+            .filter(|dep| *dep != "|" && !dep.contains('$') && !dep.contains('%'))
+            .map(str::to_string)
+            .collect();
+
+        for name in names.split_whitespace() {
+            if name.starts_with('.') || name.contains('%') || name.contains('$') {
+                continue;
+            }
+            let index = match targets.iter().position(|t| t.name == name) {
+                Some(index) => index,
+                None => {
+                    targets.push(JustCommand {
+                        name: name.to_string(),
+                        ..Default::default()
+                    });
+                    targets.len() - 1
+                }
+            };
+            let target = &mut targets[index];
+            if target.description.is_none() {
+                target.description = description.clone();
+            }
+            for dep in &dependencies {
+                if !target.dependencies.contains(dep) {
+                    target.dependencies.push(dep.clone());
+                }
+            }
+            if !inline_recipe.is_empty() {
+                target.recipe = inline_recipe.to_string();
+            }
+            current.push(index);
+        }
+    }
+
+    targets
+}
+
+// ============================================================================
+// Cargo aliases
+// ============================================================================
+
+/// `[alias]` entries from the worktree's `.cargo/config.toml`, run with `cargo`.
+pub struct CargoAliasSource;
+
+impl CargoAliasSource {
+    fn read_aliases(dir: &Path) -> Result<Option<Vec<JustCommand>>, String> {
+        let Some(path) = CARGO_CONFIG_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        parse_cargo_aliases(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+}
+
+impl TaskSource for CargoAliasSource {
+    fn kind(&self) -> TaskSourceKind {
+        TaskSourceKind::Cargo
+    }
+
+    fn discover(&self, dir: &Path) -> Result<Vec<JustCommand>, String> {
+        Ok(Self::read_aliases(dir)?.unwrap_or_default())
+    }
+
+    fn command(
+        &self,
+        dir: &Path,
+        task: &str,
+        args: &[String],
+        named: &HashMap<String, String>,
+    ) -> Result<(String, Vec<String>), String> {
+        reject_named_args(self.kind(), named)?;
+        let aliases = Self::read_aliases(dir)?
+            .ok_or_else(|| format!("No .cargo/config.toml found in {}", dir.display()))?;
+        require_task(&aliases, task, "cargo alias")?;
+
+        let mut argv = vec![task.to_string()];
+        argv.extend(args.iter().cloned());
+        Ok(("cargo".to_string(), argv))
+    }
+}
+
+/// The part of a cargo config file that defines aliases
+#[derive(Deserialize)]
+struct CargoConfig {
+    #[serde(default)]
+    alias: BTreeMap<String, CargoAlias>,
+}
+
+/// An alias value: a command line split on whitespace, or its words
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CargoAlias {
+    Line(String),
+    Words(Vec<String>),
+}
+
+/// Parse cargo aliases from a cargo config file, in name order.
+///
+/// Covers an `[alias]` table and dotted `alias.name = ...` keys. The recipe
+/// is the expanded `cargo ...` command line.
+pub fn parse_cargo_aliases(content: &str) -> Result<Vec<JustCommand>, String> {
+    let config: CargoConfig = toml::from_str(content).map_err(|e| e.to_string())?;
+    Ok(config
+        .alias
+        .into_iter()
+        .filter_map(|(name, alias)| {
+            let words = match alias {
+                CargoAlias::Line(line) => line.split_whitespace().map(str::to_string).collect(),
+                CargoAlias::Words(words) => words,
+            };
+            (!words.is_empty()).then(|| JustCommand {
+                name,
+                recipe: format!("cargo {}", words.join(" ")),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn names(commands: &[JustCommand]) -> Vec<&str> {
+        commands.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_split_task_name() {
+        assert_eq!(split_task_name("npm:build"), (TaskSourceKind::Npm, "build"));
+        assert_eq!(split_task_name("make:test"), (TaskSourceKind::Make, "test"));
+        assert_eq!(split_task_name("cargo:xtask"), (TaskSourceKind::Cargo, "xtask"));
+        assert_eq!(split_task_name("npm::lint"), (TaskSourceKind::Just, "npm::lint"));
+        assert_eq!(split_task_name("build"), (TaskSourceKind::Just, "build"));
+        assert_eq!(split_task_name("npm:"), (TaskSourceKind::Just, "npm:"));
+    }
+
+    #[test]
+    fn test_parse_package_scripts() {
+        let package = serde_json::json!({
+            "scripts": {
+                "build": "tsc -p .",
+                "prebuild": "rm -rf dist",
+                "pretty": "prettier .",
+                "test": "vitest"
+            }
+        });
+        let scripts = parse_package_scripts(&package);
+        assert_eq!(names(&scripts), vec!["build", "prebuild", "pretty", "test"]);
+
+        let build = &scripts[0];
+        assert_eq!(build.recipe, "tsc -p .");
+        assert_eq!(build.dependencies, vec!["prebuild"]);
+        assert!(scripts[1].private);
+        // "pretty" starts with "pre" but there's no "tty" script
+        assert!(!scripts[2].private);
+    }
+
+    #[test]
+    fn test_detect_package_manager() {
+        let dir = TempDir::new().unwrap();
+        let plain = serde_json::json!({});
+        assert_eq!(detect_package_manager(dir.path(), &plain), "npm");
+
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(detect_package_manager(dir.path(), &plain), "pnpm");
+
+        let declared = serde_json::json!({ "packageManager": "yarn@4.1.0" });
+        assert_eq!(detect_package_manager(dir.path(), &declared), "yarn");
+    }
+
+    #[test]
+    fn test_parse_makefile() {
+        let content = "\
+CC := gcc
+FLAGS = -O2 \\
+\t-Wall
+.PHONY: build test
+
+# Build everything
+build: deps ## Compile the project
+\t$(CC) $(FLAGS) main.c
+\t@echo done
+
+test: build
+\t./run-tests
+
+%.o: %.c
+\t$(CC) -c $<
+
+define HELP
+usage: fake: target
+endef
+
+clean:; rm -rf out
+";
+        let targets = parse_makefile(content);
+        assert_eq!(names(&targets), vec!["build", "test", "clean"]);
+
+        let build = &targets[0];
+        assert_eq!(build.description.as_deref(), Some("Compile the project"));
+        assert_eq!(build.dependencies, vec!["deps"]);
+        assert_eq!(build.recipe, "$(CC) $(FLAGS) main.c\n@echo done");
+
+        assert_eq!(targets[1].description, None);
+        assert_eq!(targets[1].dependencies, vec!["build"]);
+        assert_eq!(targets[2].recipe, "rm -rf out");
+    }
+
+    #[test]
+    fn test_parse_makefile_comment_description() {
+        let targets = parse_makefile("# Run the linter\nlint:\n\tcargo clippy\n");
+        assert_eq!(targets[0].description.as_deref(), Some("Run the linter"));
+    }
+
+    #[test]
+    fn test_parse_cargo_aliases() {
+        let content = r#"
+[build]
+rustflags = ["-C", "target-cpu=native"]
+
+[alias]
+b = "build --release"  # comment
+xtask = ["run", "--package", "xtask", "--"]
+ci = [
+    "clippy",
+    "--all-targets",
+]
+
+[env]
+FOO = "bar"
+"#;
+        let aliases = parse_cargo_aliases(content).unwrap();
+        assert_eq!(names(&aliases), vec!["b", "ci", "xtask"]);
+        assert_eq!(aliases[0].recipe, "cargo build --release");
+        assert_eq!(aliases[1].recipe, "cargo clippy --all-targets");
+        assert_eq!(aliases[2].recipe, "cargo run --package xtask --");
+
+        let dotted = parse_cargo_aliases("alias.t = \"test --workspace\"\n").unwrap();
+        assert_eq!(names(&dotted), vec!["t"]);
+
+        let escaped = parse_cargo_aliases("[alias]\n'q' = \"test -- \\u0041\"\n").unwrap();
+        assert_eq!(escaped[0].recipe, "cargo test -- A");
+        assert!(parse_cargo_aliases("[alias]\nb = [\"build\"").is_err());
+    }
+
+    #[test]
+    fn test_discover_tasks_qualifies_names() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "scripts": { "build": "tsc", "prebuild": "clean" } }"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("Makefile"), "test: build\n\tgo test\n").unwrap();
+        fs::create_dir(dir.path().join(".cargo")).unwrap();
+        fs::write(
+            dir.path().join(".cargo/config.toml"),
+            "[alias]\nxt = \"run -p xtask\"\n",
+        )
+        .unwrap();
+
+        let (tasks, errors) = discover_tasks(&dir.path().to_string_lossy());
+        assert!(errors.is_empty());
+        let listed: Vec<(TaskSourceKind, &str)> = tasks
+            .iter()
+            .map(|t| (t.source, t.command.name.as_str()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                (TaskSourceKind::Npm, "npm:build"),
+                (TaskSourceKind::Npm, "npm:prebuild"),
+                (TaskSourceKind::Make, "make:test"),
+                (TaskSourceKind::Cargo, "cargo:xt"),
+            ]
+        );
+        assert_eq!(tasks[0].command.dependencies, vec!["npm:prebuild"]);
+        assert_eq!(tasks[2].command.dependencies, vec!["make:build"]);
+    }
+
+    #[test]
+    fn test_discover_tasks_reports_broken_source() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), "{ not json").unwrap();
+        fs::write(dir.path().join("Makefile"), "all:\n\techo hi\n").unwrap();
+
+        let (tasks, errors) = discover_tasks(&dir.path().to_string_lossy());
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("package.json"));
+    }
+
+    #[test]
+    fn test_prepare_task() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "scripts": { "test": "vitest" } }"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("Makefile"), "deploy:\n\t./deploy.sh\n").unwrap();
+
+        let args = vec!["--watch".to_string()];
+        let none = HashMap::new();
+        assert_eq!(
+            prepare_task(&path, "npm:test", &args, &none).unwrap(),
+            (
+                "npm".to_string(),
+                vec!["run".into(), "test".into(), "--".into(), "--watch".into()]
+            )
+        );
+        assert!(prepare_task(&path, "npm:missing", &[], &none)
+            .unwrap_err()
+            .contains("Unknown npm script"));
+
+        let mut named = HashMap::new();
+        named.insert("ENV".to_string(), "prod".to_string());
+        assert_eq!(
+            prepare_task(&path, "make:deploy", &[], &named).unwrap(),
+            ("make".to_string(), vec!["deploy".into(), "ENV=prod".into()])
+        );
+        assert!(prepare_task(&path, "npm:test", &[], &named).is_err());
+        assert!(prepare_task(&path, "cargo:b", &[], &none).is_err());
+    }
+}
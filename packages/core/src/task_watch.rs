@@ -0,0 +1,324 @@
+//! File watching for task watch mode.
+//!
+//! A watch listens for file system events under its worktree (through
+//! `notify`), keeps the changed files that `.gitignore` and its glob
+//! patterns let through (via the `ignore` crate), and reports them once
+//! edits have settled for the debounce period. Patterns starting with `!`
+//! exclude files.
+
+use ignore::gitignore::Gitignore;
+use ignore::overrides::{Override, OverrideBuilder};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// How long files must stay unchanged before a watch fires.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Decides which changed files under a root a watch reports.
+///
+/// With no patterns, every file not excluded by `.gitignore` (or hidden) is
+/// reported; a file matched by a pattern is reported even if ignored.
+pub struct WatchFilter {
+    root: PathBuf,
+    overrides: Override,
+    /// `.gitignore` of each directory looked at so far
+    gitignores: HashMap<PathBuf, Gitignore>,
+}
+
+impl WatchFilter {
+    /// Fails on an invalid glob.
+    pub fn new(root: &Path, patterns: &[String]) -> Result<Self, String> {
+        let mut overrides = OverrideBuilder::new(root);
+        for pattern in patterns {
+            overrides
+                .add(pattern)
+                .map_err(|e| format!("Invalid watch pattern {}: {}", pattern, e))?;
+        }
+        let overrides = overrides
+            .build()
+            .map_err(|e| format!("Invalid watch patterns: {}", e))?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            overrides,
+            gitignores: HashMap::new(),
+        })
+    }
+
+    /// Whether a change to `path` (a file, possibly deleted) is reported.
+    pub fn matches(&mut self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        if path.is_dir() {
+            return false;
+        }
+        // An edited .gitignore applies from the next change on
+        if path.file_name().is_some_and(|name| name == ".gitignore") {
+            if let Some(dir) = path.parent() {
+                self.gitignores.remove(dir);
+            }
+        }
+
+        let matched = self.overrides.matched(path, false);
+        if matched.is_whitelist() {
+            return true;
+        }
+        if matched.is_ignore() {
+            return false;
+        }
+        let hidden = relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        !hidden && !self.gitignored(path)
+    }
+
+    /// Whether the nearest `.gitignore` with a say on `path` ignores it
+    fn gitignored(&mut self, path: &Path) -> bool {
+        let dirs: Vec<PathBuf> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.root))
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in dirs {
+            let gitignore = self.gitignores.entry(dir.clone()).or_insert_with(|| {
+                let file = dir.join(".gitignore");
+                if file.is_file() {
+                    Gitignore::new(file).0
+                } else {
+                    Gitignore::empty()
+                }
+            });
+            let matched = gitignore.matched_path_or_any_parents(path, false);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+/// Runs file watches keyed by caller-chosen IDs.
+#[derive(Default)]
+pub struct TaskWatcher {
+    watches: Arc<Mutex<HashMap<String, CancellationToken>>>,
+}
+
+impl TaskWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a watch is active under this key.
+    pub fn is_watching(&self, key: &str) -> bool {
+        self.watches
+            .lock()
+            .map(|watches| watches.contains_key(key))
+            .unwrap_or(false)
+    }
+
+    /// Watch `root` and send each debounced batch of changed paths.
+    ///
+    /// Must be called from within a tokio runtime. Replaces any watch already
+    /// under `key`. Fails if `root` doesn't exist or a pattern is invalid.
+    pub fn start(
+        &self,
+        key: &str,
+        root: &str,
+        patterns: &[String],
+        debounce: Duration,
+    ) -> Result<mpsc::UnboundedReceiver<Vec<String>>, String> {
+        let root = PathBuf::from(root);
+        if !root.is_dir() {
+            return Err(format!("Directory does not exist: {}", root.display()));
+        }
+        let filter = WatchFilter::new(&root, patterns)?;
+        let (event_tx, events) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = event_tx.send(event);
+        })
+        .map_err(|e| format!("Failed to watch {}: {}", root.display(), e))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {}: {}", root.display(), e))?;
+
+        let cancel = CancellationToken::new();
+        {
+            let mut watches = self
+                .watches
+                .lock()
+                .map_err(|_| "Watch registry poisoned".to_string())?;
+            if let Some(previous) = watches.insert(key.to_string(), cancel.clone()) {
+                previous.cancel();
+            }
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let registry = Arc::clone(&self.watches);
+        let key = key.to_string();
+        tokio::spawn(async move {
+            // Events stop when the watcher is dropped with the task
+            let _watcher = watcher;
+            collect_changes(root, filter, events, debounce, &cancel, tx).await;
+            cancel.cancel();
+            if let Ok(mut watches) = registry.lock() {
+                // A replacement watch may already be registered under this key
+                if watches.get(&key).is_some_and(|token| token.is_cancelled()) {
+                    watches.remove(&key);
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    /// Stop the watch under `key`. Returns false if there was none.
+    pub fn stop(&self, key: &str) -> bool {
+        let Ok(mut watches) = self.watches.lock() else {
+            return false;
+        };
+        match watches.remove(key) {
+            Some(cancel) => {
+                cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Batch matching changes until cancelled, the receiver is dropped or the
+/// root is deleted.
+async fn collect_changes(
+    root: PathBuf,
+    mut filter: WatchFilter,
+    mut events: mpsc::UnboundedReceiver<notify::Result<notify::Event>>,
+    debounce: Duration,
+    cancel: &CancellationToken,
+    tx: mpsc::UnboundedSender<Vec<String>>,
+) {
+    let mut pending = BTreeSet::new();
+    let mut last_change = Instant::now();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tx.closed() => return,
+            _ = tokio::time::sleep_until(last_change + debounce), if !pending.is_empty() => {
+                let batch = std::mem::take(&mut pending).into_iter().collect();
+                if tx.send(batch).is_err() {
+                    return;
+                }
+            }
+            event = events.recv() => {
+                let Some(event) = event else {
+                    return;
+                };
+                let Ok(event) = event else {
+                    continue;
+                };
+                if matches!(event.kind, EventKind::Access(_)) {
+                    continue;
+                }
+                if matches!(event.kind, EventKind::Remove(_)) && !root.is_dir() {
+                    return;
+                }
+                let changed: Vec<String> = event
+                    .paths
+                    .iter()
+                    .filter(|path| filter.matches(path))
+                    .map(|path| path.strip_prefix(&root).unwrap_or(path).to_string_lossy().to_string())
+                    .collect();
+                if !changed.is_empty() {
+                    pending.extend(changed);
+                    last_change = Instant::now();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_filter_respects_gitignore_and_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, ".gitignore", "target/\n");
+        write(root, "web/.gitignore", "dist/\n");
+        let matches = |patterns: &[&str], path: &str| {
+            let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+            WatchFilter::new(root, &patterns).unwrap().matches(&root.join(path))
+        };
+
+        assert!(matches(&[], "src/main.rs"));
+        assert!(matches(&[], "src/notes.md"));
+        assert!(!matches(&[], "target/debug/out"));
+        assert!(!matches(&[], "web/dist/app.js"));
+        assert!(!matches(&[], ".git/index"));
+
+        assert!(matches(&["**/*.rs"], "src/main.rs"));
+        assert!(!matches(&["**/*.rs"], "src/notes.md"));
+        assert!(matches(&["!*.md"], "src/main.rs"));
+        assert!(!matches(&["!*.md"], "src/notes.md"));
+
+        assert!(WatchFilter::new(root, &["src/[".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watch_debounces_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "src/lib.rs", "");
+        write(root, "README.md", "");
+
+        let watcher = TaskWatcher::new();
+        let mut rx = watcher
+            .start("w", root.to_str().unwrap(), &["src/**".to_string()], Duration::from_millis(100))
+            .unwrap();
+        assert!(watcher.is_watching("w"));
+
+        write(root, "README.md", "ignored by the pattern");
+        write(root, "src/lib.rs", "pub fn a() {}");
+        write(root, "src/new.rs", "");
+        let batch = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch, vec!["src/lib.rs", "src/new.rs"]);
+
+        assert!(watcher.stop("w"));
+        assert!(!watcher.is_watching("w"));
+        assert!(tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_start_rejects_missing_dir() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _guard = rt.enter();
+        let watcher = TaskWatcher::new();
+        assert!(watcher
+            .start("w", "/nonexistent/rstn-watch", &[], DEFAULT_DEBOUNCE)
+            .is_err());
+        assert!(!watcher.is_watching("w"));
+    }
+}
@@ -0,0 +1,150 @@
+//! Environment injected into spawned terminal sessions.
+//!
+//! Variables are layered from lowest to highest precedence:
+//! 1. Connection strings of running rstn-managed Docker services
+//! 2. The worktree's tracked dotenv files (in pattern order)
+//! 3. Per-project overrides (`None` suppresses a variable entirely)
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Where an injected variable came from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminalEnvSource {
+    /// Connection string of a running Docker service.
+    Service,
+    /// A tracked dotenv file in the worktree.
+    EnvFile,
+    /// A per-project override.
+    Override,
+}
+
+/// A variable injected into terminals spawned for a worktree.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalEnvVar {
+    pub key: String,
+    pub value: String,
+    pub source: TerminalEnvSource,
+    /// Service ID, env file pattern, or project path the value came from.
+    pub origin: String,
+}
+
+/// Merge the environment layers into a key-sorted list of variables.
+///
+/// When two services expose the same key (e.g. PostgreSQL and MySQL both
+/// provide `DATABASE_URL`), the first service wins.
+pub fn resolve_terminal_env(
+    services: &[(String, Vec<(String, String)>)],
+    env_files: &[(String, Vec<(String, String)>)],
+    overrides: &BTreeMap<String, Option<String>>,
+    project_path: &str,
+) -> Vec<TerminalEnvVar> {
+    let mut vars: HashMap<String, TerminalEnvVar> = HashMap::new();
+
+    for (service_id, service_vars) in services {
+        for (key, value) in service_vars {
+            vars.entry(key.clone()).or_insert_with(|| TerminalEnvVar {
+                key: key.clone(),
+                value: value.clone(),
+                source: TerminalEnvSource::Service,
+                origin: service_id.clone(),
+            });
+        }
+    }
+
+    for (pattern, file_vars) in env_files {
+        for (key, value) in file_vars {
+            vars.insert(
+                key.clone(),
+                TerminalEnvVar {
+                    key: key.clone(),
+                    value: value.clone(),
+                    source: TerminalEnvSource::EnvFile,
+                    origin: pattern.clone(),
+                },
+            );
+        }
+    }
+
+    for (key, value) in overrides {
+        match value {
+            Some(value) => {
+                vars.insert(
+                    key.clone(),
+                    TerminalEnvVar {
+                        key: key.clone(),
+                        value: value.clone(),
+                        source: TerminalEnvSource::Override,
+                        origin: project_path.to_string(),
+                    },
+                );
+            }
+            None => {
+                vars.remove(key);
+            }
+        }
+    }
+
+    let mut vars: Vec<TerminalEnvVar> = vars.into_values().collect();
+    vars.sort_by(|a, b| a.key.cmp(&b.key));
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let services = vec![
+            (
+                "rstn-postgres".to_string(),
+                pairs(&[("DATABASE_URL", "postgresql://pg")]),
+            ),
+            (
+                "rstn-mysql".to_string(),
+                pairs(&[("DATABASE_URL", "mysql://my")]),
+            ),
+            ("rstn-redis".to_string(), pairs(&[("REDIS_URL", "redis://r")])),
+        ];
+        let env_files = vec![
+            (".env".to_string(), pairs(&[("REDIS_URL", "redis://file"), ("A", "1")])),
+            (".envrc".to_string(), pairs(&[("A", "2")])),
+        ];
+        let mut overrides = BTreeMap::new();
+        overrides.insert("B".to_string(), Some("override".to_string()));
+
+        let vars = resolve_terminal_env(&services, &env_files, &overrides, "/project");
+        let keys: Vec<&str> = vars.iter().map(|v| v.key.as_str()).collect();
+        assert_eq!(keys, vec!["A", "B", "DATABASE_URL", "REDIS_URL"]);
+
+        assert_eq!(vars[0].value, "2");
+        assert_eq!(vars[0].origin, ".envrc");
+        assert_eq!(vars[1].source, TerminalEnvSource::Override);
+        assert_eq!(vars[2].value, "postgresql://pg");
+        assert_eq!(vars[2].origin, "rstn-postgres");
+        assert_eq!(vars[3].value, "redis://file");
+        assert_eq!(vars[3].source, TerminalEnvSource::EnvFile);
+    }
+
+    #[test]
+    fn test_resolve_override_suppresses() {
+        let services = vec![(
+            "rstn-redis".to_string(),
+            pairs(&[("REDIS_URL", "redis://r")]),
+        )];
+        let mut overrides = BTreeMap::new();
+        overrides.insert("REDIS_URL".to_string(), None);
+
+        let vars = resolve_terminal_env(&services, &[], &overrides, "/project");
+        assert!(vars.is_empty());
+    }
+}
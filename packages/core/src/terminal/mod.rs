@@ -0,0 +1,1082 @@
+//! Integrated PTY Terminal for worktree-scoped terminal sessions.
+//!
+//! Uses portable-pty to spawn shell sessions and stream I/O. Sessions can
+//! also be backed by a process running elsewhere (a shell inside a Docker
+//! container), bridged over channels.
+
+mod environment;
+mod recording;
+mod scrollback;
+mod shell_integration;
+
+pub use environment::{resolve_terminal_env, TerminalEnvSource, TerminalEnvVar};
+pub use recording::{
+    read_cast, read_cast_header, recordings_dir, CastEvent, CastHeader, Recorder,
+};
+pub use scrollback::{
+    strip_ansi, Scrollback, ScrollbackMatch, ScrollbackPage, DEFAULT_SCROLLBACK_LINES,
+    DEFAULT_SEARCH_LIMIT,
+};
+pub use shell_integration::{CommandRecord, CommandTracker, ShellMark, MAX_COMMAND_HISTORY};
+
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+// ============================================================================
+// Terminal State (serializable part)
+// ============================================================================
+
+/// Number of recent commands per session mirrored into state.
+pub const MAX_STATE_COMMANDS: usize = 50;
+
+/// Default name for sessions created without an explicit name.
+pub const DEFAULT_SESSION_NAME: &str = "shell";
+
+/// A named terminal session within a worktree (e.g. "server", "tests", "shell").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalSessionInfo {
+    /// Session ID (stable across restarts; the PTY is spawned under this ID).
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Whether a shell is currently running for this session.
+    #[serde(default)]
+    pub is_running: bool,
+    /// Recent commands reported by shell integration (oldest first).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<CommandRecord>,
+    /// Path of the in-progress asciicast recording (if recording).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording_path: Option<String>,
+    /// Container the shell runs in (None = local shell).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerShellTarget>,
+}
+
+/// Shell command run inside a Docker service's container.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContainerShellTarget {
+    pub service_id: String,
+    /// Command line, e.g. "psql -U postgres" or "sh".
+    pub shell: String,
+}
+
+/// Terminal state stored in WorktreeState (serializable).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TerminalState {
+    /// Active session ID (if any).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Named sessions in display order.
+    #[serde(default)]
+    pub sessions: Vec<TerminalSessionInfo>,
+    /// Results of the last scrollback search (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<TerminalSearchState>,
+    /// Recording currently being replayed (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay: Option<TerminalReplayState>,
+    /// Variables injected into shells spawned for this worktree (as of the last spawn).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<TerminalEnvVar>,
+    /// Terminal dimensions.
+    #[serde(default)]
+    pub cols: u16,
+    #[serde(default)]
+    pub rows: u16,
+}
+
+impl TerminalState {
+    pub fn new() -> Self {
+        Self {
+            session_id: None,
+            sessions: Vec::new(),
+            search: None,
+            replay: None,
+            env: Vec::new(),
+            cols: 80,
+            rows: 24,
+        }
+    }
+
+    /// Get a session by ID.
+    pub fn session(&self, session_id: &str) -> Option<&TerminalSessionInfo> {
+        self.sessions.iter().find(|s| s.id == session_id)
+    }
+
+    /// Get a session by ID mutably.
+    pub fn session_mut(&mut self, session_id: &str) -> Option<&mut TerminalSessionInfo> {
+        self.sessions.iter_mut().find(|s| s.id == session_id)
+    }
+
+    /// Return `base` if no session uses it yet, otherwise "base 2", "base 3", ...
+    pub fn unique_name(&self, base: &str) -> String {
+        let taken = |name: &str| self.sessions.iter().any(|s| s.name == name);
+        if !taken(base) {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{} {}", base, n))
+            .find(|name| !taken(name))
+            .unwrap_or_else(|| base.to_string())
+    }
+
+    /// Add a new (not yet running) session and make it active. Returns its ID.
+    pub fn add_session(&mut self, name: Option<String>) -> String {
+        let name = match name {
+            Some(n) if !n.trim().is_empty() => self.unique_name(n.trim()),
+            _ => self.unique_name(DEFAULT_SESSION_NAME),
+        };
+        let id = uuid::Uuid::new_v4().to_string();
+        self.sessions.push(TerminalSessionInfo {
+            id: id.clone(),
+            name,
+            is_running: false,
+            commands: Vec::new(),
+            recording_path: None,
+            container: None,
+        });
+        self.session_id = Some(id.clone());
+        id
+    }
+
+    /// Add a new (not yet running) session whose shell runs in a container
+    /// and make it active. Returns its ID.
+    pub fn add_container_session(&mut self, name: String, target: ContainerShellTarget) -> String {
+        let id = self.add_session(Some(name));
+        if let Some(session) = self.session_mut(&id) {
+            session.container = Some(target);
+        }
+        id
+    }
+
+    /// Remove a session. If it was active, the neighbouring session becomes active.
+    pub fn remove_session(&mut self, session_id: &str) {
+        let Some(index) = self.sessions.iter().position(|s| s.id == session_id) else {
+            return;
+        };
+        self.sessions.remove(index);
+        if self.session_id.as_deref() == Some(session_id) {
+            self.session_id = self
+                .sessions
+                .get(index.min(self.sessions.len().saturating_sub(1)))
+                .map(|s| s.id.clone());
+        }
+    }
+
+    /// Move a session to a new position (clamped to the list bounds).
+    pub fn move_session(&mut self, session_id: &str, to_index: usize) {
+        if let Some(from) = self.sessions.iter().position(|s| s.id == session_id) {
+            let session = self.sessions.remove(from);
+            let to = to_index.min(self.sessions.len());
+            self.sessions.insert(to, session);
+        }
+    }
+}
+
+/// A recording being replayed through the output callback.
+///
+/// Output is streamed under `id` as if it were a session ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalReplayState {
+    pub id: String,
+    pub path: String,
+    pub speed: f64,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// Scrollback search results shown in the terminal panel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerminalSearchState {
+    pub session_id: String,
+    pub pattern: String,
+    pub matches: Vec<ScrollbackMatch>,
+}
+
+// ============================================================================
+// Session History (scrollback + shell integration)
+// ============================================================================
+
+/// Output history of a session: scrollback plus commands recognised from
+/// shell integration markers, and an optional asciicast recording.
+#[derive(Debug, Default)]
+pub struct SessionHistory {
+    pub scrollback: Scrollback,
+    pub commands: CommandTracker,
+    parser: shell_integration::OscParser,
+    recorder: Option<Recorder>,
+}
+
+impl SessionHistory {
+    /// Append raw PTY output. Returns commands that finished in this chunk.
+    pub fn push(&mut self, data: &[u8]) -> Vec<CommandRecord> {
+        if let Some(recorder) = self.recorder.as_mut() {
+            if let Err(e) = recorder.output(data) {
+                tracing::warn!("Stopping terminal recording: {}", e);
+                self.recorder = None;
+            }
+        }
+
+        let mut finished = Vec::new();
+        let mut segment_start = 0;
+
+        for (i, &byte) in data.iter().enumerate() {
+            if let Some(mark) = self.parser.feed(byte) {
+                // Markers apply at their exact position in the line stream
+                self.push_segment(&data[segment_start..=i]);
+                segment_start = i + 1;
+
+                let current = self.scrollback.current_line();
+                let end = self.scrollback.content_end();
+                if let Some(record) = self.commands.apply(mark, current, end) {
+                    finished.push(record);
+                }
+            }
+        }
+        self.push_segment(&data[segment_start..]);
+
+        finished
+    }
+
+    fn push_segment(&mut self, segment: &[u8]) {
+        self.scrollback.push(segment);
+        self.commands.observe(segment);
+    }
+
+    /// Plain-text output of a command (at most the last `max_lines` lines).
+    pub fn command_output(&self, record: &CommandRecord, max_lines: usize) -> Vec<String> {
+        let end = record.output_end.unwrap_or_else(|| self.scrollback.total_lines());
+        let start = record.output_start.max(end.saturating_sub(max_lines as u64));
+        self.scrollback
+            .page(Some(start), end.saturating_sub(start) as usize, true)
+            .lines
+    }
+}
+
+/// Session histories shared with the PTY reader threads, keyed by session ID.
+type SharedHistories = Arc<std::sync::Mutex<HashMap<String, SessionHistory>>>;
+
+// ============================================================================
+// Terminal Session (non-serializable PTY handle)
+// ============================================================================
+
+/// Input for a remote session's process.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteInput {
+    Data(Vec<u8>),
+    Resize { cols: u16, rows: u16 },
+}
+
+/// Channels of a session whose process is not a local PTY (e.g. a shell
+/// inside a container). The session exits when the output sender is dropped.
+pub struct RemoteSession {
+    pub input: mpsc::UnboundedSender<RemoteInput>,
+    pub output: mpsc::UnboundedReceiver<Vec<u8>>,
+}
+
+/// Where a session's process runs.
+enum SessionIo {
+    /// Local shell in a PTY. The slave end is dropped after spawn so EOF is
+    /// seen on exit.
+    Pty {
+        master: Box<dyn MasterPty + Send>,
+        writer: Box<dyn Write + Send>,
+        child: Box<dyn Child + Send + Sync>,
+    },
+    /// Process driven over channels; dropping the sender closes it.
+    Remote(mpsc::UnboundedSender<RemoteInput>),
+}
+
+/// Active terminal session with PTY handle.
+pub struct TerminalSession {
+    /// Unique session ID.
+    pub id: String,
+    /// Display name (mirrors TerminalSessionInfo.name).
+    pub name: String,
+    /// Worktree ID this session belongs to.
+    pub worktree_id: String,
+    /// Working directory.
+    pub cwd: String,
+    /// PTY handles or remote input channel.
+    io: SessionIo,
+    /// Channel to stop the reader task.
+    stop_tx: Option<mpsc::Sender<()>>,
+}
+
+impl TerminalSession {
+    /// Resize the terminal.
+    pub fn resize(&self, cols: u16, rows: u16) -> Result<(), String> {
+        match &self.io {
+            SessionIo::Pty { master, .. } => master
+                .resize(PtySize {
+                    rows,
+                    cols,
+                    pixel_width: 0,
+                    pixel_height: 0,
+                })
+                .map_err(|e| format!("Failed to resize PTY: {}", e)),
+            SessionIo::Remote(input) => input
+                .send(RemoteInput::Resize { cols, rows })
+                .map_err(|_| "Remote session closed".to_string()),
+        }
+    }
+
+    /// Write data to the terminal (user input).
+    pub fn write(&mut self, data: &[u8]) -> Result<(), String> {
+        match &mut self.io {
+            SessionIo::Pty { writer, .. } => {
+                writer
+                    .write_all(data)
+                    .map_err(|e| format!("Failed to write to PTY: {}", e))?;
+                writer
+                    .flush()
+                    .map_err(|e| format!("Failed to flush PTY: {}", e))
+            }
+            SessionIo::Remote(input) => input
+                .send(RemoteInput::Data(data.to_vec()))
+                .map_err(|_| "Remote session closed".to_string()),
+        }
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        // Signal the reader task to stop
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.try_send(());
+        }
+        // Make sure the shell does not outlive its session
+        if let SessionIo::Pty { child, .. } = &mut self.io {
+            let _ = child.kill();
+        }
+        // The PTY will be closed when master is dropped, a remote process
+        // when its input channel is
+    }
+}
+
+/// Output handling shared by PTY and remote session readers.
+struct OutputSink {
+    session_id: String,
+    histories: SharedHistories,
+    output_callback: Option<OutputCallback>,
+    command_callback: Option<CommandCallback>,
+}
+
+impl OutputSink {
+    /// Record output in the session's history and forward it to the callbacks.
+    fn push(&self, data: Vec<u8>) {
+        let finished = match self.histories.lock() {
+            Ok(mut histories) => histories
+                .get_mut(&self.session_id)
+                .map(|history| history.push(&data))
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+        if let Some(ref callback) = self.command_callback {
+            for record in finished {
+                callback(self.session_id.clone(), record);
+            }
+        }
+
+        // Call output callback if set
+        if let Some(ref callback) = self.output_callback {
+            callback(self.session_id.clone(), data);
+        }
+    }
+}
+
+// ============================================================================
+// Terminal Manager
+// ============================================================================
+
+/// Callback type for terminal output.
+pub type OutputCallback = Arc<dyn Fn(String, Vec<u8>) + Send + Sync>;
+
+/// Callback type for session exit (session_id), fired when the PTY reaches EOF.
+pub type ExitCallback = Arc<dyn Fn(String) + Send + Sync>;
+
+/// Callback type for finished commands (session_id, record), from shell integration.
+pub type CommandCallback = Arc<dyn Fn(String, CommandRecord) + Send + Sync>;
+
+/// Process options for spawning a session's shell.
+#[derive(Debug, Clone, Default)]
+pub struct SpawnOptions {
+    /// Working directory (the worktree path).
+    pub cwd: String,
+    pub cols: u16,
+    pub rows: u16,
+    /// Extra environment variables for the shell.
+    pub env: Vec<(String, String)>,
+}
+
+/// Manager for all terminal sessions.
+pub struct TerminalManager {
+    /// Active sessions by session ID.
+    /// A Mutex (not RwLock) because PTY handles are Send but not Sync.
+    sessions: Mutex<HashMap<String, TerminalSession>>,
+    /// Output callback (session_id, data).
+    /// Uses a std lock so it can be set from synchronous napi calls.
+    output_callback: std::sync::RwLock<Option<OutputCallback>>,
+    /// Exit callback (session_id).
+    exit_callback: std::sync::RwLock<Option<ExitCallback>>,
+    /// Command callback (session_id, record).
+    command_callback: std::sync::RwLock<Option<CommandCallback>>,
+    /// History per session. Outlives the shell so it survives exit and restart.
+    histories: SharedHistories,
+    /// Running replays by replay ID.
+    replays: std::sync::Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl Default for TerminalManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerminalManager {
+    pub fn new() -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            output_callback: std::sync::RwLock::new(None),
+            exit_callback: std::sync::RwLock::new(None),
+            command_callback: std::sync::RwLock::new(None),
+            histories: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            replays: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the output callback for streaming PTY output.
+    ///
+    /// Only sessions spawned after this call will use the new callback.
+    pub fn set_output_callback(&self, callback: OutputCallback) {
+        if let Ok(mut cb) = self.output_callback.write() {
+            *cb = Some(callback);
+        }
+    }
+
+    /// Set the callback invoked when shell integration reports a finished command.
+    ///
+    /// Only sessions spawned after this call will use the new callback.
+    pub fn set_command_callback(&self, callback: CommandCallback) {
+        if let Ok(mut cb) = self.command_callback.write() {
+            *cb = Some(callback);
+        }
+    }
+
+    /// Set the callback invoked when a session's shell exits.
+    pub fn set_exit_callback(&self, callback: ExitCallback) {
+        if let Ok(mut cb) = self.exit_callback.write() {
+            *cb = Some(callback);
+        }
+    }
+
+    /// Spawn a new terminal session with a generated ID and the default name.
+    pub async fn spawn(
+        &self,
+        worktree_id: String,
+        cwd: String,
+        cols: u16,
+        rows: u16,
+    ) -> Result<String, String> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        self.spawn_session(
+            session_id.clone(),
+            DEFAULT_SESSION_NAME.to_string(),
+            worktree_id,
+            SpawnOptions {
+                cwd,
+                cols,
+                rows,
+                env: Vec::new(),
+            },
+        )
+        .await?;
+        Ok(session_id)
+    }
+
+    /// Spawn a terminal session under a caller-chosen ID and name.
+    ///
+    /// Used for named sessions whose ID is tracked in `TerminalState`.
+    /// Fails if a session with this ID is already running.
+    pub async fn spawn_session(
+        &self,
+        session_id: String,
+        name: String,
+        worktree_id: String,
+        options: SpawnOptions,
+    ) -> Result<(), String> {
+        let SpawnOptions {
+            cwd,
+            cols,
+            rows,
+            env,
+        } = options;
+
+        if self.has_session(&session_id).await {
+            return Err(format!("Session already running: {}", session_id));
+        }
+
+        // Create PTY
+        let pty_system = native_pty_system();
+        let pty_pair = pty_system
+            .openpty(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| format!("Failed to open PTY: {}", e))?;
+
+        // Determine shell
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| {
+            if cfg!(target_os = "windows") {
+                "powershell.exe".to_string()
+            } else {
+                "/bin/zsh".to_string()
+            }
+        });
+
+        // Build command
+        let mut cmd = CommandBuilder::new(&shell);
+        cmd.cwd(&cwd);
+
+        // Set up environment (injected variables first so TERM can't be clobbered)
+        for (key, value) in &env {
+            cmd.env(key, value);
+        }
+        cmd.env("TERM", "xterm-256color");
+        cmd.env("COLORTERM", "truecolor");
+
+        // Spawn child process
+        let child = pty_pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| format!("Failed to spawn shell: {}", e))?;
+
+        // Release our copy of the slave so the reader sees EOF when the shell exits
+        drop(pty_pair.slave);
+        let master = pty_pair.master;
+
+        // Get writer for input
+        let writer = master
+            .take_writer()
+            .map_err(|e| format!("Failed to get PTY writer: {}", e))?;
+
+        // Get reader for output
+        let mut reader = master
+            .try_clone_reader()
+            .map_err(|e| format!("Failed to get PTY reader: {}", e))?;
+
+        // Create stop channel
+        let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+
+        let sink = self.output_sink(&session_id);
+        let exit_callback = self.exit_callback.read().ok().and_then(|cb| cb.clone());
+
+        // Spawn reader task
+        tokio::task::spawn_blocking(move || {
+            let mut buf = [0u8; 4096];
+            loop {
+                // Check for stop signal (non-blocking)
+                if stop_rx.try_recv().is_ok() {
+                    break;
+                }
+
+                match reader.read(&mut buf) {
+                    Ok(0) => {
+                        // EOF - shell exited (unless the session was killed)
+                        if stop_rx.try_recv().is_err() {
+                            if let Some(ref callback) = exit_callback {
+                                callback(sink.session_id.clone());
+                            }
+                        }
+                        break;
+                    }
+                    Ok(n) => sink.push(buf[..n].to_vec()),
+                    Err(e) => {
+                        // Check if it's just a would-block or interrupted
+                        if e.kind() == std::io::ErrorKind::WouldBlock
+                            || e.kind() == std::io::ErrorKind::Interrupted
+                        {
+                            std::thread::sleep(std::time::Duration::from_millis(10));
+                            continue;
+                        }
+                        // Real error (EIO once the shell exits on Linux), stop reading
+                        tracing::warn!("PTY read error: {}", e);
+                        if stop_rx.try_recv().is_err() {
+                            if let Some(ref callback) = exit_callback {
+                                callback(sink.session_id.clone());
+                            }
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+
+        // Store session
+        let session = TerminalSession {
+            id: session_id.clone(),
+            name,
+            worktree_id,
+            cwd,
+            io: SessionIo::Pty {
+                master,
+                writer,
+                child,
+            },
+            stop_tx: Some(stop_tx),
+        };
+
+        let mut sessions = self.sessions.lock().await;
+        sessions.insert(session_id, session);
+
+        Ok(())
+    }
+
+    /// Attach a session whose process runs elsewhere (e.g. a container exec).
+    ///
+    /// Output, history and exit are handled exactly like a PTY session's,
+    /// and `resize`/`write` are forwarded to `remote.input`.
+    pub async fn spawn_remote_session(
+        &self,
+        session_id: String,
+        name: String,
+        worktree_id: String,
+        cwd: String,
+        remote: RemoteSession,
+    ) -> Result<(), String> {
+        if self.has_session(&session_id).await {
+            return Err(format!("Session already running: {}", session_id));
+        }
+
+        let RemoteSession { input, mut output } = remote;
+        let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+
+        let sink = self.output_sink(&session_id);
+        let exit_callback = self.exit_callback.read().ok().and_then(|cb| cb.clone());
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = stop_rx.recv() => break,
+                    data = output.recv() => match data {
+                        Some(data) => sink.push(data),
+                        None => {
+                            // Remote process exited (unless the session was killed)
+                            if stop_rx.try_recv().is_err() {
+                                if let Some(ref callback) = exit_callback {
+                                    callback(sink.session_id.clone());
+                                }
+                            }
+                            break;
+                        }
+                    },
+                }
+            }
+        });
+
+        let session = TerminalSession {
+            id: session_id.clone(),
+            name,
+            worktree_id,
+            cwd,
+            io: SessionIo::Remote(input),
+            stop_tx: Some(stop_tx),
+        };
+
+        let mut sessions = self.sessions.lock().await;
+        sessions.insert(session_id, session);
+
+        Ok(())
+    }
+
+    /// Output sink for a session being (re)started.
+    fn output_sink(&self, session_id: &str) -> OutputSink {
+        // Keep existing history when a session's shell is restarted
+        if let Ok(mut histories) = self.histories.lock() {
+            histories.entry(session_id.to_string()).or_default();
+        }
+        OutputSink {
+            session_id: session_id.to_string(),
+            histories: Arc::clone(&self.histories),
+            output_callback: self.output_callback.read().ok().and_then(|cb| cb.clone()),
+            command_callback: self.command_callback.read().ok().and_then(|cb| cb.clone()),
+        }
+    }
+
+    /// Resize a terminal session.
+    pub async fn resize(&self, session_id: &str, cols: u16, rows: u16) -> Result<(), String> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        session.resize(cols, rows)?;
+
+        let _ = self.with_history(session_id, |h| {
+            if let Some(recorder) = h.recorder.as_mut() {
+                let _ = recorder.resize(cols, rows);
+            }
+        });
+        Ok(())
+    }
+
+    /// Write data to a terminal session.
+    pub async fn write(&self, session_id: &str, data: &[u8]) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        session.write(data)
+    }
+
+    /// Kill a terminal session.
+    pub async fn kill(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().await;
+        sessions
+            .remove(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        Ok(())
+    }
+
+    /// Kill all sessions for a worktree.
+    pub async fn kill_worktree_sessions(&self, worktree_id: &str) {
+        let mut sessions = self.sessions.lock().await;
+        sessions.retain(|_, s| s.worktree_id != worktree_id);
+    }
+
+    /// Kill all sessions.
+    pub async fn kill_all(&self) {
+        let mut sessions = self.sessions.lock().await;
+        sessions.clear();
+    }
+
+    /// Check if a session exists.
+    pub async fn has_session(&self, session_id: &str) -> bool {
+        let sessions = self.sessions.lock().await;
+        sessions.contains_key(session_id)
+    }
+
+    /// Rename a terminal session.
+    pub async fn rename(&self, session_id: &str, name: String) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        session.name = name;
+        Ok(())
+    }
+
+    /// Get all running session IDs and names for a worktree.
+    pub async fn get_worktree_sessions(&self, worktree_id: &str) -> Vec<(String, String)> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .values()
+            .filter(|s| s.worktree_id == worktree_id)
+            .map(|s| (s.id.clone(), s.name.clone()))
+            .collect()
+    }
+
+    /// Page through a session's scrollback (see `Scrollback::page`).
+    pub fn scrollback_page(
+        &self,
+        session_id: &str,
+        start: Option<u64>,
+        count: usize,
+        plain: bool,
+    ) -> Result<ScrollbackPage, String> {
+        self.with_history(session_id, |h| h.scrollback.page(start, count, plain))
+    }
+
+    /// Last `count` lines of a session's output as plain text.
+    pub fn scrollback_last_lines(&self, session_id: &str, count: usize) -> Result<Vec<String>, String> {
+        self.with_history(session_id, |h| h.scrollback.last_lines(count))
+    }
+
+    /// Regex-search a session's scrollback.
+    pub fn search_scrollback(
+        &self,
+        session_id: &str,
+        pattern: &str,
+        limit: usize,
+    ) -> Result<Vec<ScrollbackMatch>, String> {
+        let regex = Regex::new(pattern).map_err(|e| format!("Invalid search pattern: {}", e))?;
+        self.with_history(session_id, |h| h.scrollback.search(&regex, limit))
+    }
+
+    /// Clear a session's scrollback.
+    pub fn clear_scrollback(&self, session_id: &str) -> Result<(), String> {
+        self.with_history(session_id, |h| h.scrollback.clear())
+    }
+
+    /// Drop a session's history entirely (when the session is closed).
+    pub fn discard_history(&self, session_id: &str) {
+        if let Ok(mut histories) = self.histories.lock() {
+            histories.remove(session_id);
+        }
+    }
+
+    /// Recent failed commands of a session (newest first), each with the
+    /// last `max_output_lines` lines of its plain-text output.
+    pub fn failed_commands(
+        &self,
+        session_id: &str,
+        limit: usize,
+        max_output_lines: usize,
+    ) -> Result<Vec<(CommandRecord, Vec<String>)>, String> {
+        self.with_history(session_id, |h| {
+            h.commands
+                .commands()
+                .rev()
+                .filter(|c| c.failed())
+                .take(limit)
+                .map(|record| (record.clone(), h.command_output(record, max_output_lines)))
+                .collect()
+        })
+    }
+
+    /// Start recording a session's output to an asciicast v2 file.
+    pub fn start_recording(
+        &self,
+        session_id: &str,
+        path: &Path,
+        cols: u16,
+        rows: u16,
+        title: Option<String>,
+    ) -> Result<(), String> {
+        self.with_history(session_id, |h| {
+            if h.recorder.is_some() {
+                return Err(format!("Session is already being recorded: {}", session_id));
+            }
+            h.recorder = Some(Recorder::create(path, cols, rows, title)?);
+            Ok(())
+        })?
+    }
+
+    /// Stop recording a session. Returns the path of the saved recording.
+    pub fn stop_recording(&self, session_id: &str) -> Result<PathBuf, String> {
+        let recorder = self
+            .with_history(session_id, |h| h.recorder.take())?
+            .ok_or_else(|| format!("Session is not being recorded: {}", session_id))?;
+        recorder.finish()
+    }
+
+    /// Replay a recording through the output callback under `replay_id`.
+    ///
+    /// Event timing is preserved and divided by `speed`. Resolves when the
+    /// replay finishes or is stopped with `stop_replay`.
+    pub async fn replay(&self, replay_id: &str, path: &Path, speed: f64) -> Result<(), String> {
+        if !(speed.is_finite() && speed > 0.0) {
+            return Err(format!("Invalid replay speed: {}", speed));
+        }
+        let (_, events) = read_cast(path)?;
+        let output_callback = self
+            .output_callback
+            .read()
+            .ok()
+            .and_then(|cb| cb.clone())
+            .ok_or_else(|| "No terminal output listener registered".to_string())?;
+
+        let token = CancellationToken::new();
+        if let Ok(mut replays) = self.replays.lock() {
+            replays.insert(replay_id.to_string(), token.clone());
+        }
+
+        let started = tokio::time::Instant::now();
+        for event in events.iter().filter(|e| e.code == "o") {
+            let at = started + std::time::Duration::from_secs_f64(event.time.max(0.0) / speed);
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = tokio::time::sleep_until(at) => {}
+            }
+            output_callback(replay_id.to_string(), event.data.clone().into_bytes());
+        }
+
+        if let Ok(mut replays) = self.replays.lock() {
+            replays.remove(replay_id);
+        }
+        Ok(())
+    }
+
+    /// Stop a running replay.
+    pub fn stop_replay(&self, replay_id: &str) -> Result<(), String> {
+        let token = self
+            .replays
+            .lock()
+            .map_err(|_| "Replay lock poisoned".to_string())?
+            .remove(replay_id)
+            .ok_or_else(|| format!("Replay not found: {}", replay_id))?;
+        token.cancel();
+        Ok(())
+    }
+
+    fn with_history<T>(
+        &self,
+        session_id: &str,
+        f: impl FnOnce(&mut SessionHistory) -> T,
+    ) -> Result<T, String> {
+        let mut histories = self
+            .histories
+            .lock()
+            .map_err(|_| "Terminal history lock poisoned".to_string())?;
+        let history = histories
+            .get_mut(session_id)
+            .ok_or_else(|| format!("No history for session: {}", session_id))?;
+        Ok(f(history))
+    }
+
+    /// Get session info for a worktree.
+    pub async fn get_worktree_session(&self, worktree_id: &str) -> Option<String> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .values()
+            .find(|s| s.worktree_id == worktree_id)
+            .map(|s| s.id.clone())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminal_state_default() {
+        let state = TerminalState::new();
+        assert!(state.session_id.is_none());
+        assert_eq!(state.cols, 80);
+        assert_eq!(state.rows, 24);
+    }
+
+    #[test]
+    fn test_terminal_state_serialization() {
+        let state = TerminalState {
+            session_id: Some("test-123".to_string()),
+            sessions: vec![TerminalSessionInfo {
+                id: "test-123".to_string(),
+                name: "server".to_string(),
+                is_running: true,
+                commands: Vec::new(),
+                recording_path: None,
+                container: None,
+            }],
+            search: None,
+            replay: None,
+            env: Vec::new(),
+            cols: 120,
+            rows: 40,
+        };
+
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded: TerminalState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state, loaded);
+    }
+
+    #[tokio::test]
+    async fn test_terminal_manager_new() {
+        let manager = TerminalManager::new();
+        assert!(!manager.has_session("nonexistent").await);
+    }
+
+    #[test]
+    fn test_session_history_tracks_commands() {
+        let mut history = SessionHistory::default();
+        let finished = history.push(b"\x1b]133;A\x07$ \x1b]133;B\x07false\r\n\x1b]133;C\x07");
+        assert!(finished.is_empty());
+
+        let finished = history.push(b"oops\r\nbad\r\n\x1b]133;D;1\x07\x1b]133;A\x07$ ");
+        assert_eq!(finished.len(), 1);
+        let record = &finished[0];
+        assert_eq!(record.command, "false");
+        assert_eq!(record.exit_code, Some(1));
+        assert_eq!(history.command_output(record, 100), vec!["oops", "bad"]);
+        assert_eq!(history.command_output(record, 1), vec!["bad"]);
+    }
+
+    #[tokio::test]
+    async fn test_replay_feeds_output_callback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("demo.cast");
+        let mut recorder = Recorder::create(&path, 80, 24, None).unwrap();
+        recorder.output(b"one ").unwrap();
+        recorder.output(b"two").unwrap();
+        recorder.finish().unwrap();
+
+        let manager = TerminalManager::new();
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        manager.set_output_callback(Arc::new(move |id, data| {
+            sink.lock().unwrap().push((id, data));
+        }));
+
+        manager.replay("replay-1", &path, 50.0).await.unwrap();
+        {
+            let received = received.lock().unwrap();
+            assert_eq!(received.len(), 2);
+            assert_eq!(received[0].0, "replay-1");
+            assert_eq!(received[1].1, b"two".to_vec());
+        }
+
+        assert!(manager.replay("replay-2", &path, 0.0).await.is_err());
+        assert!(manager.stop_replay("replay-1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remote_session_bridges_io() {
+        let manager = TerminalManager::new();
+        let exited = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&exited);
+        manager.set_exit_callback(Arc::new(move |id| sink.lock().unwrap().push(id)));
+
+        let (input_tx, mut input_rx) = mpsc::unbounded_channel();
+        let (output_tx, output_rx) = mpsc::unbounded_channel();
+        manager
+            .spawn_remote_session(
+                "s1".to_string(),
+                "psql".to_string(),
+                "wt".to_string(),
+                "/tmp".to_string(),
+                RemoteSession {
+                    input: input_tx,
+                    output: output_rx,
+                },
+            )
+            .await
+            .unwrap();
+
+        manager.write("s1", b"select 1;\r").await.unwrap();
+        manager.resize("s1", 120, 40).await.unwrap();
+        assert_eq!(input_rx.recv().await, Some(RemoteInput::Data(b"select 1;\r".to_vec())));
+        assert_eq!(input_rx.recv().await, Some(RemoteInput::Resize { cols: 120, rows: 40 }));
+
+        output_tx.send(b"one\r\ntwo\r\n".to_vec()).unwrap();
+        drop(output_tx);
+        for _ in 0..100 {
+            if !exited.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        assert_eq!(*exited.lock().unwrap(), vec!["s1".to_string()]);
+        let page = manager.scrollback_page("s1", None, 10, true).unwrap();
+        assert_eq!(page.lines, vec!["one", "two"]);
+
+        // Killing the session closes the remote input
+        manager.kill("s1").await.unwrap();
+        assert_eq!(input_rx.recv().await, None);
+    }
+
+    // Note: Full PTY tests require a real terminal environment
+    // and are better suited for integration tests
+}
@@ -0,0 +1,233 @@
+//! Asciicast v2 recording and replay.
+//!
+//! Format: a JSON header line followed by one `[time, code, data]` event per
+//! line (see <https://docs.asciinema.org/manual/asciicast/v2/>). We write
+//! output (`"o"`) and resize (`"r"`) events.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Asciicast v2 header.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CastHeader {
+    pub version: u32,
+    pub width: u16,
+    pub height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+}
+
+/// A single recorded event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastEvent {
+    /// Seconds since the start of the recording.
+    pub time: f64,
+    /// Event code ("o" output, "i" input, "r" resize, "m" marker).
+    pub code: String,
+    pub data: String,
+}
+
+/// Writes a session's output to a `.cast` file as it arrives.
+pub struct Recorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    started: Instant,
+    /// Trailing bytes of an incomplete UTF-8 character from the last read.
+    pending: Vec<u8>,
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder").field("path", &self.path).finish()
+    }
+}
+
+impl Recorder {
+    /// Create the file (and parent directories) and write the header.
+    pub fn create(path: &Path, cols: u16, rows: u16, title: Option<String>) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create recordings dir: {}", e))?;
+        }
+        let file = File::create(path).map_err(|e| format!("Failed to create recording: {}", e))?;
+        let mut writer = BufWriter::new(file);
+
+        let mut env = HashMap::new();
+        if let Ok(shell) = std::env::var("SHELL") {
+            env.insert("SHELL".to_string(), shell);
+        }
+        env.insert("TERM".to_string(), "xterm-256color".to_string());
+
+        let header = CastHeader {
+            version: 2,
+            width: cols,
+            height: rows,
+            timestamp: Some(chrono::Utc::now().timestamp()),
+            title,
+            env,
+        };
+        let line = serde_json::to_string(&header)
+            .map_err(|e| format!("Failed to serialize recording header: {}", e))?;
+        writeln!(writer, "{}", line).map_err(|e| format!("Failed to write recording: {}", e))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer,
+            started: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record raw PTY output.
+    pub fn output(&mut self, data: &[u8]) -> Result<(), String> {
+        self.pending.extend_from_slice(data);
+        // Events must be valid UTF-8; hold back a character split across reads
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if valid == 0 {
+            return Ok(());
+        }
+        let bytes: Vec<u8> = self.pending.drain(..valid).collect();
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+        self.event("o", &text)
+    }
+
+    /// Record a terminal resize.
+    pub fn resize(&mut self, cols: u16, rows: u16) -> Result<(), String> {
+        self.event("r", &format!("{}x{}", cols, rows))
+    }
+
+    fn event(&mut self, code: &str, data: &str) -> Result<(), String> {
+        let time = self.started.elapsed().as_secs_f64();
+        let line = serde_json::to_string(&(time, code, data))
+            .map_err(|e| format!("Failed to serialize recording event: {}", e))?;
+        writeln!(self.writer, "{}", line).map_err(|e| format!("Failed to write recording: {}", e))
+    }
+
+    /// Flush buffered events and return the file path.
+    pub fn finish(mut self) -> Result<PathBuf, String> {
+        if !self.pending.is_empty() {
+            let bytes = std::mem::take(&mut self.pending);
+            self.event("o", &String::from_utf8_lossy(&bytes))?;
+        }
+        self.writer
+            .flush()
+            .map_err(|e| format!("Failed to write recording: {}", e))?;
+        Ok(self.path)
+    }
+}
+
+/// Read only the header of a `.cast` file.
+pub fn read_cast_header(path: &Path) -> Result<CastHeader, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open recording: {}", e))?;
+    parse_header(BufReader::new(file).lines().next())
+}
+
+fn parse_header(line: Option<std::io::Result<String>>) -> Result<CastHeader, String> {
+    let line = line
+        .ok_or_else(|| "Recording is empty".to_string())?
+        .map_err(|e| format!("Failed to read recording: {}", e))?;
+    let header: CastHeader =
+        serde_json::from_str(&line).map_err(|e| format!("Invalid recording header: {}", e))?;
+    if header.version != 2 {
+        return Err(format!("Unsupported asciicast version: {}", header.version));
+    }
+    Ok(header)
+}
+
+/// Read a `.cast` file.
+pub fn read_cast(path: &Path) -> Result<(CastHeader, Vec<CastEvent>), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open recording: {}", e))?;
+    let mut lines = BufReader::new(file).lines();
+    let header = parse_header(lines.next())?;
+
+    let mut events = Vec::new();
+    for line in lines {
+        let line = line.map_err(|e| format!("Failed to read recording: {}", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (time, code, data): (f64, String, String) = serde_json::from_str(&line)
+            .map_err(|e| format!("Invalid recording event: {}", e))?;
+        events.push(CastEvent { time, code, data });
+    }
+
+    Ok((header, events))
+}
+
+/// Default directory for a project's recordings (`~/.rstn/recordings/<project>/`).
+pub fn recordings_dir(project_name: &str) -> PathBuf {
+    let safe: String = project_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' })
+        .collect();
+    crate::persistence::get_rstn_dir().join("recordings").join(safe)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_record_and_read_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("session.cast");
+
+        let mut recorder = Recorder::create(&path, 100, 30, Some("demo".to_string())).unwrap();
+        recorder.output(b"hello\r\n").unwrap();
+        recorder.resize(120, 40).unwrap();
+        // "é" split across two reads
+        recorder.output(&[b'c', b'a', b'f', 0xc3]).unwrap();
+        recorder.output(&[0xa9, b'\n']).unwrap();
+        let saved = recorder.finish().unwrap();
+        assert_eq!(saved, path);
+
+        let (header, events) = read_cast(&path).unwrap();
+        assert_eq!(header.version, 2);
+        assert_eq!((header.width, header.height), (100, 30));
+        assert_eq!(header.title.as_deref(), Some("demo"));
+
+        let codes: Vec<_> = events.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, vec!["o", "r", "o", "o"]);
+        assert_eq!(events[0].data, "hello\r\n");
+        assert_eq!(events[1].data, "120x40");
+        assert_eq!(events[2].data, "caf");
+        assert_eq!(events[3].data, "é\n");
+        assert!(events.windows(2).all(|w| w[0].time <= w[1].time));
+    }
+
+    #[test]
+    fn test_read_cast_rejects_other_versions() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("v1.cast");
+        std::fs::write(&path, "{\"version\":1,\"width\":80,\"height\":24}\n").unwrap();
+        assert!(read_cast(&path).is_err());
+        assert!(read_cast_header(&path).is_err());
+    }
+
+    #[test]
+    fn test_recordings_dir_sanitizes_name() {
+        let dir = recordings_dir("my project/../x");
+        assert!(dir.ends_with("recordings/my_project_.._x"));
+    }
+}
@@ -0,0 +1,312 @@
+//! Bounded per-session scrollback with paging and regex search.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Default number of lines kept per session.
+pub const DEFAULT_SCROLLBACK_LINES: usize = 10_000;
+
+/// Maximum number of matches returned by a scrollback search by default.
+pub const DEFAULT_SEARCH_LIMIT: usize = 500;
+
+/// Lines longer than this are split so a single runaway line can't grow unbounded.
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// A page of scrollback lines.
+///
+/// Line numbers are absolute (counted since the session started) so they stay
+/// stable while old lines are evicted from the front of the buffer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScrollbackPage {
+    /// Line number of the first line in `lines`.
+    pub start: u64,
+    pub lines: Vec<String>,
+    /// Oldest line number still available.
+    pub first_line: u64,
+    /// Total number of lines (including a trailing partial line).
+    pub total_lines: u64,
+}
+
+/// A scrollback line matching a search pattern.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScrollbackMatch {
+    /// Absolute line number.
+    pub line: u64,
+    /// Line text with escape sequences removed.
+    pub text: String,
+    /// Byte range of the first match within `text`.
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Bounded ring buffer of terminal output lines.
+///
+/// Raw lines (with escape sequences) are kept so the renderer can replay
+/// them; searches and plain-text retrieval strip escapes on the fly.
+#[derive(Debug, Clone)]
+pub struct Scrollback {
+    lines: VecDeque<String>,
+    /// Bytes of the current, not yet terminated line.
+    partial: Vec<u8>,
+    /// Absolute line number of `lines[0]`.
+    first_line: u64,
+    max_lines: usize,
+}
+
+impl Default for Scrollback {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCROLLBACK_LINES)
+    }
+}
+
+impl Scrollback {
+    pub fn new(max_lines: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            partial: Vec::new(),
+            first_line: 0,
+            max_lines: max_lines.max(1),
+        }
+    }
+
+    /// Append raw PTY output.
+    pub fn push(&mut self, data: &[u8]) {
+        for &byte in data {
+            if byte == b'\n' {
+                self.finish_line();
+            } else {
+                self.partial.push(byte);
+                if self.partial.len() >= MAX_LINE_BYTES {
+                    self.finish_line();
+                }
+            }
+        }
+    }
+
+    fn finish_line(&mut self) {
+        let mut line = String::from_utf8_lossy(&self.partial).into_owned();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+        self.partial.clear();
+        self.lines.push_back(line);
+        while self.lines.len() > self.max_lines {
+            self.lines.pop_front();
+            self.first_line += 1;
+        }
+    }
+
+    /// Discard all buffered output (line numbering continues).
+    pub fn clear(&mut self) {
+        self.first_line += self.lines.len() as u64;
+        self.lines.clear();
+        if !self.partial.is_empty() {
+            self.first_line += 1;
+            self.partial.clear();
+        }
+    }
+
+    /// Oldest line number still available.
+    pub fn first_line(&self) -> u64 {
+        self.first_line
+    }
+
+    /// Line number that the next output byte will land on.
+    pub fn current_line(&self) -> u64 {
+        self.first_line + self.lines.len() as u64
+    }
+
+    /// Line after the last line with visible content. Unlike `total_lines`, a
+    /// partial line holding only escape sequences (e.g. a prompt marker) is not counted.
+    pub fn content_end(&self) -> u64 {
+        let partial_visible =
+            !strip_ansi(&String::from_utf8_lossy(&self.partial)).trim().is_empty();
+        self.current_line() + u64::from(partial_visible)
+    }
+
+    /// Total number of lines, counting a non-empty partial line.
+    pub fn total_lines(&self) -> u64 {
+        self.first_line + self.lines.len() as u64 + u64::from(!self.partial.is_empty())
+    }
+
+    /// Line by absolute number (raw).
+    fn line(&self, number: u64) -> Option<String> {
+        let index = number.checked_sub(self.first_line)? as usize;
+        if index < self.lines.len() {
+            Some(self.lines[index].clone())
+        } else if index == self.lines.len() && !self.partial.is_empty() {
+            Some(String::from_utf8_lossy(&self.partial).into_owned())
+        } else {
+            None
+        }
+    }
+
+    /// Get up to `count` lines starting at `start`, or the last `count` lines if
+    /// `start` is None. With `plain`, escape sequences are stripped.
+    pub fn page(&self, start: Option<u64>, count: usize, plain: bool) -> ScrollbackPage {
+        let total = self.total_lines();
+        let start = start
+            .unwrap_or_else(|| total.saturating_sub(count as u64))
+            .clamp(self.first_line, total);
+        let end = start.saturating_add(count as u64).min(total);
+        let lines = (start..end)
+            .filter_map(|n| self.line(n))
+            .map(|l| if plain { strip_ansi(&l) } else { l })
+            .collect();
+
+        ScrollbackPage {
+            start,
+            lines,
+            first_line: self.first_line,
+            total_lines: total,
+        }
+    }
+
+    /// Last `count` lines as plain text.
+    pub fn last_lines(&self, count: usize) -> Vec<String> {
+        self.page(None, count, true).lines
+    }
+
+    /// Search plain-text lines, returning at most `limit` of the most recent
+    /// matches in chronological order.
+    pub fn search(&self, pattern: &Regex, limit: usize) -> Vec<ScrollbackMatch> {
+        let mut matches: Vec<ScrollbackMatch> = (self.first_line..self.total_lines())
+            .rev()
+            .filter_map(|n| {
+                let text = strip_ansi(&self.line(n)?);
+                let (start, end) = pattern.find(&text).map(|m| (m.start(), m.end()))?;
+                Some(ScrollbackMatch {
+                    line: n,
+                    text,
+                    start,
+                    end,
+                })
+            })
+            .take(limit)
+            .collect();
+        matches.reverse();
+        matches
+    }
+}
+
+/// Strip terminal escape sequences and control characters from a line.
+///
+/// Carriage returns rewrite the line in a terminal, so only the text after
+/// the last non-empty `\r` segment is kept.
+pub fn strip_ansi(line: &str) -> String {
+    let line = line
+        .rsplit('\r')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or("");
+
+    strip_escapes(line)
+        .chars()
+        .filter(|&c| !c.is_control() || c == '\t')
+        .collect()
+}
+
+/// Remove escape sequences (CSI, OSC, DCS, ...) but keep plain control characters.
+pub(crate) fn strip_escapes(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters, then a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC / DCS / APC / PM: terminated by BEL or ST (ESC \)
+            Some(']') | Some('P') | Some('_') | Some('^') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Two-byte escapes (charset selection etc.)
+            _ => {}
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrollback_lines_and_partial() {
+        let mut sb = Scrollback::new(100);
+        sb.push(b"one\r\ntw");
+        sb.push(b"o\r\nprompt$ ");
+
+        assert_eq!(sb.total_lines(), 3);
+        let page = sb.page(None, 10, false);
+        assert_eq!(page.start, 0);
+        assert_eq!(page.lines, vec!["one", "two", "prompt$ "]);
+        assert_eq!(sb.last_lines(2), vec!["two", "prompt$ "]);
+    }
+
+    #[test]
+    fn test_scrollback_ring_evicts_oldest() {
+        let mut sb = Scrollback::new(3);
+        for i in 0..5 {
+            sb.push(format!("line {}\n", i).as_bytes());
+        }
+
+        assert_eq!(sb.first_line(), 2);
+        assert_eq!(sb.total_lines(), 5);
+
+        // Requests before the first available line are clamped
+        let page = sb.page(Some(0), 2, true);
+        assert_eq!(page.start, 2);
+        assert_eq!(page.lines, vec!["line 2", "line 3"]);
+
+        sb.clear();
+        assert_eq!(sb.page(None, 10, true).lines, Vec::<String>::new());
+        assert_eq!(sb.first_line(), 5);
+    }
+
+    #[test]
+    fn test_scrollback_search_strips_escapes() {
+        let mut sb = Scrollback::new(100);
+        sb.push(b"\x1b[31merror\x1b[0m: first\n");
+        sb.push(b"ok\n");
+        sb.push(b"\x1b]0;title\x07error: second\n");
+
+        let regex = Regex::new("^error").unwrap();
+        let matches = sb.search(&regex, 10);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].line, 0);
+        assert_eq!(matches[0].text, "error: first");
+        assert_eq!((matches[0].start, matches[0].end), (0, 5));
+
+        // Limit keeps the most recent matches
+        let matches = sb.search(&regex, 1);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, 2);
+    }
+
+    #[test]
+    fn test_strip_ansi_carriage_return() {
+        assert_eq!(strip_ansi("10%\r50%\r100%"), "100%");
+        assert_eq!(strip_ansi("\x1b[1;32mgreen\x1b[m\ttab"), "green\ttab");
+    }
+}
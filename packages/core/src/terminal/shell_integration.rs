@@ -0,0 +1,449 @@
+//! Shell integration: OSC 133 / OSC 633 prompt and command markers.
+//!
+//! Shells that emit these markers (fish, VS Code-style integration scripts,
+//! iTerm2/WezTerm/Starship setups) let us split the PTY stream into
+//! structured command records:
+//!
+//! - `A` prompt start, `B` prompt end (command input starts)
+//! - `C` command executed (output starts), `D[;exit]` command finished
+//! - `633;E;<cmdline>` explicit command line, `633;P;Cwd=<path>` cwd
+
+use super::scrollback::strip_escapes;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Maximum OSC payload we buffer before giving up on a sequence.
+const MAX_OSC_LEN: usize = 4096;
+
+/// Maximum echoed input captured between `B` and `C`.
+const MAX_INPUT_LEN: usize = 4096;
+
+/// Number of finished commands kept per session.
+pub const MAX_COMMAND_HISTORY: usize = 200;
+
+/// A command run in a terminal session, delimited by shell integration markers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommandRecord {
+    /// Sequence number within the session.
+    pub id: u64,
+    /// Command line as typed.
+    pub command: String,
+    /// Working directory reported by the shell (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Start time (RFC 3339).
+    pub started_at: String,
+    /// Wall-clock duration once finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Exit code once finished (None if the shell didn't report one).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// First scrollback line of the command's output.
+    pub output_start: u64,
+    /// Scrollback line after the last output line (None while running).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_end: Option<u64>,
+}
+
+impl CommandRecord {
+    /// Whether the command finished with a non-zero exit code.
+    pub fn failed(&self) -> bool {
+        self.exit_code.is_some_and(|code| code != 0)
+    }
+}
+
+/// A parsed shell integration marker.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShellMark {
+    PromptStart,
+    CommandStart,
+    CommandExecuted,
+    CommandFinished(Option<i32>),
+    CommandLine(String),
+    Cwd(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParseState {
+    Normal,
+    Escape,
+    Osc,
+    OscEscape,
+}
+
+/// Incremental OSC parser (sequences may be split across PTY reads).
+#[derive(Debug, Clone)]
+pub struct OscParser {
+    state: ParseState,
+    payload: Vec<u8>,
+}
+
+impl Default for OscParser {
+    fn default() -> Self {
+        Self {
+            state: ParseState::Normal,
+            payload: Vec::new(),
+        }
+    }
+}
+
+impl OscParser {
+    /// Feed one byte; returns a marker when a 133/633 sequence completes on it.
+    pub fn feed(&mut self, byte: u8) -> Option<ShellMark> {
+        match self.state {
+            ParseState::Normal => {
+                if byte == 0x1b {
+                    self.state = ParseState::Escape;
+                }
+                None
+            }
+            ParseState::Escape => {
+                if byte == b']' {
+                    self.state = ParseState::Osc;
+                    self.payload.clear();
+                } else {
+                    self.state = if byte == 0x1b {
+                        ParseState::Escape
+                    } else {
+                        ParseState::Normal
+                    };
+                }
+                None
+            }
+            ParseState::Osc => match byte {
+                0x07 => self.finish(),
+                0x1b => {
+                    self.state = ParseState::OscEscape;
+                    None
+                }
+                _ => {
+                    if self.payload.len() >= MAX_OSC_LEN {
+                        self.state = ParseState::Normal;
+                        self.payload.clear();
+                    } else {
+                        self.payload.push(byte);
+                    }
+                    None
+                }
+            },
+            ParseState::OscEscape => {
+                if byte == b'\\' {
+                    self.finish()
+                } else {
+                    // Unterminated OSC followed by a new escape sequence
+                    self.payload.clear();
+                    self.state = if byte == b']' {
+                        ParseState::Osc
+                    } else {
+                        ParseState::Normal
+                    };
+                    None
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Option<ShellMark> {
+        self.state = ParseState::Normal;
+        let payload = String::from_utf8_lossy(&self.payload).into_owned();
+        self.payload.clear();
+        parse_mark(&payload)
+    }
+}
+
+/// Parse an OSC payload (without `ESC ]` and terminator).
+fn parse_mark(payload: &str) -> Option<ShellMark> {
+    let (code, rest) = payload.split_once(';')?;
+    if code != "133" && code != "633" {
+        return None;
+    }
+    let mut parts = rest.splitn(2, ';');
+    let kind = parts.next()?;
+    let args = parts.next().unwrap_or("");
+
+    match kind {
+        "A" => Some(ShellMark::PromptStart),
+        "B" => Some(ShellMark::CommandStart),
+        "C" => Some(ShellMark::CommandExecuted),
+        "D" => {
+            // Exit code may be followed by extra key=value args
+            let code = args.split(';').next().and_then(|c| c.trim().parse().ok());
+            Some(ShellMark::CommandFinished(code))
+        }
+        "E" if code == "633" => {
+            // Command line, optionally followed by ";<nonce>"
+            let line = args.split(';').next().unwrap_or("");
+            Some(ShellMark::CommandLine(unescape_633(line)))
+        }
+        "P" if code == "633" => args
+            .strip_prefix("Cwd=")
+            .map(|cwd| ShellMark::Cwd(unescape_633(cwd))),
+        _ => None,
+    }
+}
+
+/// Undo OSC 633 escaping (`\\` and `\xAB` hex escapes).
+fn unescape_633(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let raw = value.as_bytes();
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'\\' {
+            if raw.get(i + 1) == Some(&b'\\') {
+                bytes.push(b'\\');
+                i += 2;
+                continue;
+            }
+            if raw.get(i + 1) == Some(&b'x') {
+                if let Some(byte) = value
+                    .get(i + 2..i + 4)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    bytes.push(byte);
+                    i += 4;
+                    continue;
+                }
+            }
+        }
+        bytes.push(raw[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Reconstruct a typed command line from echoed input (handles backspace).
+fn command_from_echo(input: &[u8]) -> String {
+    let text = String::from_utf8_lossy(input);
+    let mut line = String::new();
+    for c in strip_escapes(&text).chars() {
+        if c == '\x08' || c == '\x7f' {
+            line.pop();
+        } else if !c.is_control() || c == '\t' {
+            line.push(c);
+        }
+    }
+    line.trim().to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    Idle,
+    Input,
+    Running,
+}
+
+/// Per-session command tracking driven by shell integration markers.
+#[derive(Debug, Clone)]
+pub struct CommandTracker {
+    phase: Phase,
+    /// Echoed input between `B` and `C`.
+    input: Vec<u8>,
+    /// Command line reported via `633;E` (preferred over echoed input).
+    command_line: Option<String>,
+    cwd: Option<String>,
+    running: Option<(CommandRecord, std::time::Instant)>,
+    commands: VecDeque<CommandRecord>,
+    next_id: u64,
+}
+
+impl Default for CommandTracker {
+    fn default() -> Self {
+        Self {
+            phase: Phase::Idle,
+            input: Vec::new(),
+            command_line: None,
+            cwd: None,
+            running: None,
+            commands: VecDeque::new(),
+            next_id: 1,
+        }
+    }
+}
+
+impl CommandTracker {
+    /// Record output bytes that arrived outside of a marker.
+    pub fn observe(&mut self, data: &[u8]) {
+        if self.phase == Phase::Input && self.input.len() < MAX_INPUT_LEN {
+            let room = MAX_INPUT_LEN - self.input.len();
+            self.input.extend_from_slice(&data[..data.len().min(room)]);
+        }
+    }
+
+    /// Apply a marker. `current_line` is the scrollback line the next output
+    /// lands on and `end_line` is the line after the last visible output.
+    /// Returns a command record when a command finishes.
+    pub fn apply(
+        &mut self,
+        mark: ShellMark,
+        current_line: u64,
+        end_line: u64,
+    ) -> Option<CommandRecord> {
+        match mark {
+            ShellMark::PromptStart => {
+                self.phase = Phase::Idle;
+                // A new prompt without `D` means the shell didn't report completion
+                self.finish(None, end_line)
+            }
+            ShellMark::CommandStart => {
+                self.phase = Phase::Input;
+                self.input.clear();
+                self.command_line = None;
+                None
+            }
+            ShellMark::CommandLine(line) => {
+                self.command_line = Some(line);
+                None
+            }
+            ShellMark::Cwd(cwd) => {
+                self.cwd = Some(cwd);
+                None
+            }
+            ShellMark::CommandExecuted => {
+                let command = self
+                    .command_line
+                    .take()
+                    .unwrap_or_else(|| command_from_echo(&self.input));
+                self.input.clear();
+                self.phase = Phase::Running;
+
+                let record = CommandRecord {
+                    id: self.next_id,
+                    command,
+                    cwd: self.cwd.clone(),
+                    started_at: chrono::Utc::now().to_rfc3339(),
+                    duration_ms: None,
+                    exit_code: None,
+                    output_start: current_line,
+                    output_end: None,
+                };
+                self.next_id += 1;
+                self.running = Some((record, std::time::Instant::now()));
+                None
+            }
+            ShellMark::CommandFinished(exit_code) => {
+                self.phase = Phase::Idle;
+                self.finish(exit_code, end_line)
+            }
+        }
+    }
+
+    fn finish(&mut self, exit_code: Option<i32>, end_line: u64) -> Option<CommandRecord> {
+        let (mut record, started) = self.running.take()?;
+        record.exit_code = exit_code;
+        record.duration_ms = Some(started.elapsed().as_millis() as u64);
+        record.output_end = Some(end_line.max(record.output_start));
+
+        self.commands.push_back(record.clone());
+        while self.commands.len() > MAX_COMMAND_HISTORY {
+            self.commands.pop_front();
+        }
+        Some(record)
+    }
+
+    /// Finished commands, oldest first.
+    pub fn commands(&self) -> impl DoubleEndedIterator<Item = &CommandRecord> {
+        self.commands.iter()
+    }
+
+    /// Most recent failed command.
+    pub fn last_failure(&self) -> Option<&CommandRecord> {
+        self.commands.iter().rev().find(|c| c.failed())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_all(parser: &mut OscParser, data: &[u8]) -> Vec<ShellMark> {
+        data.iter().filter_map(|&b| parser.feed(b)).collect()
+    }
+
+    #[test]
+    fn test_parse_osc_133_markers() {
+        let mut parser = OscParser::default();
+        let marks = parse_all(
+            &mut parser,
+            b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x1b\\out\r\n\x1b]133;D;2\x07",
+        );
+        assert_eq!(
+            marks,
+            vec![
+                ShellMark::PromptStart,
+                ShellMark::CommandStart,
+                ShellMark::CommandExecuted,
+                ShellMark::CommandFinished(Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_split_across_reads_and_other_osc() {
+        let mut parser = OscParser::default();
+        assert!(parse_all(&mut parser, b"\x1b]0;title\x07\x1b]13").is_empty());
+        assert_eq!(
+            parse_all(&mut parser, b"3;D\x07"),
+            vec![ShellMark::CommandFinished(None)]
+        );
+    }
+
+    #[test]
+    fn test_parse_633_command_line_and_cwd() {
+        let mut parser = OscParser::default();
+        let marks = parse_all(
+            &mut parser,
+            b"\x1b]633;E;echo a\\x3bb\\\\;nonce\x07\x1b]633;P;Cwd=/tmp/x\x07",
+        );
+        assert_eq!(
+            marks,
+            vec![
+                ShellMark::CommandLine("echo a;b\\".to_string()),
+                ShellMark::Cwd("/tmp/x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tracker_records_command() {
+        let mut tracker = CommandTracker::default();
+        assert!(tracker.apply(ShellMark::PromptStart, 0, 0).is_none());
+        tracker.apply(ShellMark::CommandStart, 0, 1);
+        tracker.observe(b"cargo tesx\x08t\r\n");
+        tracker.apply(ShellMark::CommandExecuted, 1, 1);
+        let record = tracker
+            .apply(ShellMark::CommandFinished(Some(101)), 5, 5)
+            .unwrap();
+
+        assert_eq!(record.command, "cargo test");
+        assert_eq!(record.exit_code, Some(101));
+        assert_eq!(record.output_start, 1);
+        assert_eq!(record.output_end, Some(5));
+        assert!(record.failed());
+        assert_eq!(tracker.last_failure().unwrap().id, record.id);
+    }
+
+    #[test]
+    fn test_tracker_prefers_reported_command_line() {
+        let mut tracker = CommandTracker::default();
+        tracker.apply(ShellMark::CommandStart, 0, 1);
+        tracker.observe(b"\x1b[2Kgarbled");
+        tracker.apply(ShellMark::CommandLine("make build".to_string()), 0, 1);
+        tracker.apply(ShellMark::CommandExecuted, 1, 1);
+        let record = tracker.apply(ShellMark::CommandFinished(Some(0)), 2, 2).unwrap();
+        assert_eq!(record.command, "make build");
+        assert!(!record.failed());
+        assert!(tracker.last_failure().is_none());
+    }
+
+    #[test]
+    fn test_finish_without_execute_is_ignored() {
+        let mut tracker = CommandTracker::default();
+        tracker.apply(ShellMark::CommandStart, 0, 1);
+        assert!(tracker.apply(ShellMark::CommandFinished(Some(0)), 1, 1).is_none());
+    }
+}
@@ -382,6 +382,42 @@ pub fn remove_worktree(repo_path: &str, worktree_path: &str) -> Result<(), Strin
     Ok(())
 }
 
+/// Longest database name Postgres accepts (MySQL allows 64)
+const MAX_DATABASE_NAME_LEN: usize = 63;
+
+/// Database name for a worktree of `project` on `branch`.
+///
+/// Lowercase letters, digits and single underscores, e.g. `rstn_feature_auth`
+/// for project `rstn` and branch `feature/auth`. `taken` holds the names
+/// other worktrees already use: a name found there (`feature/auth` and
+/// `feature-auth` map to the same one), or one cut at the length limit,
+/// ends with a short hash of the full branch name instead.
+pub fn database_name(project: &str, branch: &str, taken: &[String]) -> String {
+    let mut name = String::new();
+    for c in format!("{}_{}", project, branch).to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "wt_");
+    }
+    let name = match name.trim_end_matches('_') {
+        "" => "worktree",
+        name => name,
+    };
+    if name.len() <= MAX_DATABASE_NAME_LEN && !taken.iter().any(|t| t == name) {
+        return name.to_string();
+    }
+
+    let hash = crate::persistence::path_to_hash(branch);
+    let mut name = name.to_string();
+    name.truncate(MAX_DATABASE_NAME_LEN - hash.len() - 1);
+    format!("{}_{}", name.trim_end_matches('_'), hash)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -390,6 +426,26 @@ pub fn remove_worktree(repo_path: &str, worktree_path: &str) -> Result<(), Strin
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_database_name() {
+        assert_eq!(database_name("rustation", "feature/auth", &[]), "rustation_feature_auth");
+        assert_eq!(database_name("My-App", "fix/Bug--123", &[]), "my_app_fix_bug_123");
+        assert_eq!(database_name("2024app", "main", &[]), "wt_2024app_main");
+        assert_eq!(database_name("", "/", &[]), "worktree");
+        let long = database_name("app", &"x".repeat(100), &[]);
+        assert_eq!(long.len(), 63);
+        assert_ne!(long, database_name("app", &"x".repeat(101), &[]));
+    }
+
+    #[test]
+    fn test_database_name_avoids_taken_names() {
+        let taken = vec![database_name("app", "feature/auth", &[])];
+        let name = database_name("app", "feature-auth", &taken);
+        assert_ne!(name, taken[0]);
+        assert!(name.starts_with("app_feature_auth_"));
+        assert_eq!(name, database_name("app", "feature-auth", &taken));
+    }
+
     #[test]
     fn test_parse_single_worktree() {
         let output = "/Users/chris/projects/rustation  abc1234 [main]";